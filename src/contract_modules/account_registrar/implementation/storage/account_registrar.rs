@@ -1,5 +1,5 @@
 // SPDX-License-Identifier: BUSL-1.1
-use ink::storage::Mapping;
+use ink::{prelude::vec::Vec, storage::Mapping};
 use pendzl::traits::AccountId;
 
 #[derive(Default, Debug)]
@@ -20,4 +20,15 @@ impl AccountRegistrar {
         self.account_to_counter.insert(account, &counter);
         self.next_counter = counter.checked_add(1).unwrap();
     }
+
+    /// returns accounts registered with counters in range [`from_counter`, `to_counter`).
+    pub fn accounts_in_range(
+        &self,
+        from_counter: u128,
+        to_counter: u128,
+    ) -> Vec<AccountId> {
+        (from_counter..to_counter.min(self.next_counter))
+            .filter_map(|counter| self.counter_to_account.get(counter))
+            .collect()
+    }
 }
@@ -26,3 +26,6 @@ pub const ROLE_ADMIN: RoleType = 0; // 0
 
 /// can withdraw protocol income
 pub const TREASURY: RoleType = ink::selector_id!("TREASURY"); // 2_434_241_257_u32
+
+/// time (in milliseconds) after the eta during which a queued parameter change can be executed. Afterwards it expires.
+pub const MANAGE_ACTION_GRACE_PERIOD: u64 = 14 * 24 * 60 * 60 * 1000; // 14 days
@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: BUSL-1.1
 use abax_library::structs::{
-    AssetRulesError, ReserveDataError, ReserveRestrictionsError,
+    ActionsPauseError, AssetRulesError, CloseFactorError,
+    LiquidationAuctionError, ParameterBoundsError, ReserveDataError,
+    ReserveDelistingError, ReserveIsolationError, ReserveRestrictionsError,
+    TwError,
 };
 use pendzl::{
     contracts::{access_control::AccessControlError, psp22::PSP22Error},
@@ -23,8 +26,15 @@ pub enum LendingPoolError {
     MathError(MathError),
 
     AssetRulesError(AssetRulesError),
+    ActionsPauseError(ActionsPauseError),
+    CloseFactorError(CloseFactorError),
+    LiquidationAuctionError(LiquidationAuctionError),
     ReserveDataError(ReserveDataError),
     ReserveRestrictionsError(ReserveRestrictionsError),
+    ReserveIsolationError(ReserveIsolationError),
+    ReserveDelistingError(ReserveDelistingError),
+    ParameterBoundsError(ParameterBoundsError),
+    TwError(TwError),
 
     PriceFeedError(PriceFeedError),
     FlashLoanReceiverError(FlashLoanReceiverError),
@@ -59,16 +69,42 @@ pub enum LendingPoolError {
     NothingToCompensateWith,
     /// returned if a liquidator tries to take an asset that is not a collateral as a compensation.
     TakingNotACollateral,
+    /// returned if the `data` passed to the liquidation can't be decoded.
+    InvalidLiquidationData,
+    /// returned if the pool doesn't hold enough of the underlying asset to be transferred out.
+    InsufficientLiquidity,
     /// returned if a pair of vectors used during the operation has inconsistent lengths.
     VectorsInconsistentLengths,
     /// returned if passed 'market_rule_id' that is not used.
     MarketRuleInvalidId,
-    /// returned if the fee is too high (greater then 1 = 10^6).
-    DepositFeeTooHigh,
-    /// returned if the calculated to be used tw entry's index is invalid - points to a non existing entry or the entry's value is too recent.
-    TwEntryInvalidIndex(u8),
     /// returned if the attempt to adjust the rate is made earlier then the minimal time between adjustments.
     TooEarlyToAdjustRate,
+    /// returned if one tries to adjust the rate of a reserve whose interest rate model is not the adaptive one.
+    InterestRateModelNotAdaptive,
+    /// returned if one tries to settle bad debt of an account that has collateral or has no debt.
+    NoBadDebt,
+    /// returned if one tries to borrow with the stable rate from a reserve that has no stable rate model set.
+    StableBorrowDisabled,
+    /// returned if the account's stable rate doesn't diverge enough from the currently offered stable rate to be rebalanced.
+    StableRateRebalanceNotAllowed,
+    /// returned if one tries to set the stable rate model of a reserve that has no stable debt token registered.
+    StableDebtTokenNotRegistered,
+    /// returned if a timelocked parameter change is made directly while the timelock is enabled - it must be queued.
+    TimelockActive,
+    /// returned if a parameter change is queued while the timelock is disabled - it should be made directly.
+    TimelockDisabled,
+    /// returned if there is no queued parameter change with the given id.
+    ManageActionNotQueued,
+    /// returned if the queued parameter change is executed before its eta.
+    ManageActionNotReady,
+    /// returned if the queued parameter change is executed after the grace period following its eta.
+    ManageActionExpired,
+    /// returned if the queued parameter change is executed while its proposer no longer has the role required to queue it.
+    ManageActionProposerLostRole,
+    /// returned if the bad debt left after the earned fee and the safety fund exceeds the total deposit of its reserve, so it can't be socialized.
+    BadDebtExceedsDeposits,
+    /// returned if a reserve is isolated before all accounts are migrated from the legacy layouts, so its collateral users can't be counted.
+    AccountsNotMigrated,
 }
 
 impl From<AssetRulesError> for LendingPoolError {
@@ -76,6 +112,31 @@ impl From<AssetRulesError> for LendingPoolError {
         LendingPoolError::AssetRulesError(error)
     }
 }
+impl From<ActionsPauseError> for LendingPoolError {
+    fn from(error: ActionsPauseError) -> Self {
+        LendingPoolError::ActionsPauseError(error)
+    }
+}
+impl From<CloseFactorError> for LendingPoolError {
+    fn from(error: CloseFactorError) -> Self {
+        LendingPoolError::CloseFactorError(error)
+    }
+}
+impl From<LiquidationAuctionError> for LendingPoolError {
+    fn from(error: LiquidationAuctionError) -> Self {
+        LendingPoolError::LiquidationAuctionError(error)
+    }
+}
+impl From<TwError> for LendingPoolError {
+    fn from(error: TwError) -> Self {
+        LendingPoolError::TwError(error)
+    }
+}
+impl From<ParameterBoundsError> for LendingPoolError {
+    fn from(error: ParameterBoundsError) -> Self {
+        LendingPoolError::ParameterBoundsError(error)
+    }
+}
 impl From<ReserveDataError> for LendingPoolError {
     fn from(error: ReserveDataError) -> Self {
         LendingPoolError::ReserveDataError(error)
@@ -88,6 +149,18 @@ impl From<ReserveRestrictionsError> for LendingPoolError {
     }
 }
 
+impl From<ReserveIsolationError> for LendingPoolError {
+    fn from(error: ReserveIsolationError) -> Self {
+        LendingPoolError::ReserveIsolationError(error)
+    }
+}
+
+impl From<ReserveDelistingError> for LendingPoolError {
+    fn from(error: ReserveDelistingError) -> Self {
+        LendingPoolError::ReserveDelistingError(error)
+    }
+}
+
 impl From<MathError> for LendingPoolError {
     fn from(error: MathError) -> Self {
         LendingPoolError::MathError(error)
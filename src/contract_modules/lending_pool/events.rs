@@ -1,9 +1,13 @@
 // SPDX-License-Identifier: BUSL-1.1
-use abax_library::structs::{InterestRateModelParams, ReserveRestrictions};
+use abax_library::structs::{
+    CloseFactor, InterestAccrualMode, InterestRateModelParams,
+    LiquidationAuctionParams, PausableAction, ReserveAccountLimits,
+    ReserveIsolation, ReserveRestrictions, StableRateModel,
+};
 use ink::{prelude::string::String, primitives::AccountId};
-use pendzl::traits::Balance;
+use pendzl::traits::{Balance, Timestamp};
 
-use super::{RuleId, SetReserveFeesArgs};
+use super::{ManageAction, RuleId, SetReserveFeesArgs};
 
 /// Emitted when a deposit of 'amount' in 'asset' is made by 'caller' on behalf of 'on_behalf_of'.
 /// The data coresponding to asset and (asset, on_behalf_of) is updated (interests are accumulated).
@@ -70,6 +74,43 @@ pub struct Repay {
     pub amount: Balance,
 }
 
+/// Emitted when a stable rate borrow of 'amount' in 'asset' is made by 'caller' on behalf of 'on_behalf_of'.
+/// 'stable_rate_e18' is the resulting stable rate of 'on_behalf_of' (weighted with the already existing stable debt).
+/// The stable debt data coresponding to asset and (asset, on_behalf_of) is updated (stable interests are accumulated).
+#[ink::event]
+pub struct BorrowStable {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub caller: AccountId,
+    #[ink(topic)]
+    pub on_behalf_of: AccountId,
+    pub amount: Balance,
+    pub stable_rate_e18: u64,
+}
+
+/// Emitted when a repay of 'amount' of stable debt in 'asset' is made by 'caller' on behalf of 'on_behalf_of'.
+/// The stable debt data coresponding to asset and (asset, on_behalf_of) is updated (stable interests are accumulated).
+#[ink::event]
+pub struct RepayStable {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub caller: AccountId,
+    #[ink(topic)]
+    pub on_behalf_of: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the stable rate of 'account' in 'asset' is rebalanced by 'caller' to the currently offered stable rate.
+#[ink::event]
+pub struct StableRateRebalanced {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub caller: AccountId,
+    #[ink(topic)]
+    pub account: AccountId,
+    pub new_stable_rate_e18: u64,
+}
+
 /// Emitted when a flash loan of `amount` in `asset` is initiated by `caller` to the `receiver`.
 /// The receiver pays back the `amount` and `fee` in the same transaction.
 #[ink::event]
@@ -86,11 +127,13 @@ pub struct FlashLoan {
 
 /// Emitted when a liquidation is made by 'liquidator' on 'liquidated_account'.
 /// The liquidator takes 'amount_taken' of 'asset_to_take' and repays 'amount_repaid' of 'asset_to_repay'.
+/// 'max_amount_to_repay' is the cap on the repaid amount resulting from the close factor of 'asset_to_repay'.
+/// If 'received_underlying' is true the liquidator receives the underlying 'asset_to_take' instead of the deposit.
 ///
 /// # Note
 /// The data coresponding to asset_to_take, amount_repaid
 /// and (asset_to_repay, liquidated_account)
-/// and (asset_to_take, liquidator) and (asset_to_take, liquidated_account) is updated (interests are accumulated).
+/// and (asset_to_take, liquidator) (only if 'received_underlying' is false) and (asset_to_take, liquidated_account) is updated (interests are accumulated).
 #[ink::event]
 pub struct Liquidation {
     pub liquidator: AccountId,
@@ -102,6 +145,95 @@ pub struct Liquidation {
     pub asset_to_take: AccountId,
     pub amount_repaid: Balance,
     pub amount_taken: Balance,
+    pub max_amount_to_repay: Balance,
+    pub received_underlying: bool,
+}
+
+/// Emitted when a liquidation auction of the undercollateralized 'account' is started by 'caller'.
+#[ink::event]
+pub struct LiquidationAuctionStarted {
+    #[ink(topic)]
+    pub account: AccountId,
+    pub caller: AccountId,
+    pub start_timestamp: Timestamp,
+}
+
+/// Emitted (together with `Liquidation`) when the 'account' is liquidated in the auction mode. 'penalty_part_e6' is the part of the penalties paid to the liquidator.
+#[ink::event]
+pub struct LiquidationAuctionFilled {
+    #[ink(topic)]
+    pub account: AccountId,
+    pub liquidator: AccountId,
+    pub asset_to_repay: AccountId,
+    pub asset_to_take: AccountId,
+    pub amount_repaid: Balance,
+    pub amount_taken: Balance,
+    pub penalty_part_e6: u128,
+}
+
+/// Emitted when the liquidation auction of the (collateralized again) 'account' is closed by 'caller'.
+#[ink::event]
+pub struct LiquidationAuctionClosed {
+    #[ink(topic)]
+    pub account: AccountId,
+    pub caller: AccountId,
+}
+
+/// Emitted when the debt of 'account' in 'asset' that had no collateral left is written off by 'caller'.
+/// The written off 'amount' is covered by 'covered_by_earned_fee' taken from the protocol's earned fee,
+/// 'covered_by_safety_fund' taken from the reserve's safety fund and the 'socialized' part that decreased depositors' deposits.
+///
+/// # Note
+/// The data coresponding to asset and (asset, account) is updated (interests are accumulated).
+#[ink::event]
+pub struct BadDebtSettled {
+    pub caller: AccountId,
+    #[ink(topic)]
+    pub account: AccountId,
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub amount: Balance,
+    pub covered_by_earned_fee: Balance,
+    pub covered_by_safety_fund: Balance,
+    pub socialized: Balance,
+}
+
+/// Emitted when the deposit ('amount') of 'account' in 'asset', that was not used as collateral, is moved to the safety fund of the reserve by 'caller',
+/// before the bad debt of 'account' is written off.
+///
+/// # Note
+/// The data coresponding to asset and (asset, account) is updated (interests are accumulated).
+#[ink::event]
+pub struct BadDebtDepositSeized {
+    pub caller: AccountId,
+    #[ink(topic)]
+    pub account: AccountId,
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when 'amount' of 'asset' is added by 'caller' to the safety fund of the reserve.
+#[ink::event]
+pub struct SafetyFundIncreased {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub caller: AccountId,
+    pub amount: Balance,
+}
+
+/// Emitted when the whole deposit ('amount') of 'account' in the delisted 'asset' is withdrawn to the 'account' by 'caller'.
+///
+/// # Note
+/// The data coresponding to asset and (asset, account) is updated (interests are accumulated).
+#[ink::event]
+pub struct DelistedDepositSettled {
+    pub caller: AccountId,
+    #[ink(topic)]
+    pub account: AccountId,
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub amount: Balance,
 }
 
 /// Emitted when a interest indexes are updated in reserve coreespoding to asset.
@@ -128,6 +260,61 @@ pub struct AssetRegistered {
     pub v_token_address: AccountId,
 }
 
+/// Emitted when the delisting of a reserve is started. The collateral coefficients of the asset are ramped down to 0 in 'ramp_duration'.
+#[ink::event]
+pub struct ReserveDelistingStarted {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub ramp_duration: Timestamp,
+}
+
+/// Emitted when a delisted reserve is closed.
+#[ink::event]
+pub struct ReserveClosed {
+    #[ink(topic)]
+    pub asset: AccountId,
+}
+
+/// Emitted when the timelock delay is changed. The delay equal to 0 means that the timelock is disabled.
+#[ink::event]
+pub struct TimelockDelayChanged {
+    pub timelock_delay: Timestamp,
+}
+
+/// Emitted when a parameter change 'action' is queued by 'proposer'. It can be executed from 'eta'.
+#[ink::event]
+pub struct ManageActionQueued {
+    #[ink(topic)]
+    pub action_id: u32,
+    pub proposer: AccountId,
+    pub eta: Timestamp,
+    pub action: ManageAction,
+}
+
+/// Emitted when a queued parameter change is executed by 'caller'.
+#[ink::event]
+pub struct ManageActionExecuted {
+    #[ink(topic)]
+    pub action_id: u32,
+    pub caller: AccountId,
+}
+
+/// Emitted when a queued parameter change is cancelled by 'caller'.
+#[ink::event]
+pub struct ManageActionCancelled {
+    #[ink(topic)]
+    pub action_id: u32,
+    pub caller: AccountId,
+}
+
+/// Emitted when the liquidation auction mode of a market rule is changed. None means that the auction mode is disabled.
+#[ink::event]
+pub struct MarketRuleLiquidationAuctionChanged {
+    #[ink(topic)]
+    pub market_rule_id: RuleId,
+    pub auction_params: Option<LiquidationAuctionParams>,
+}
+
 /// Emitted when a price feed provider is changed.
 #[ink::event]
 pub struct PriceFeedProviderChanged {
@@ -154,12 +341,29 @@ pub struct ReserveActivated {
     pub active: bool,
 }
 
-/// Emitted when a reserve is frozen.
+/// Emitted when an action is paused or unpaused in all reserves.
+#[ink::event]
+pub struct GlobalActionPauseChanged {
+    pub action: PausableAction,
+    pub paused: bool,
+}
+
+/// Emitted when an action is paused or unpaused in a reserve.
+#[ink::event]
+pub struct ReserveActionPauseChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub action: PausableAction,
+    pub paused: bool,
+}
+
+/// Emitted when a reserve is frozen or unfrozen. The 'reason_code' is defined off-chain, 0 means unspecified.
 #[ink::event]
 pub struct ReserveFrozen {
     #[ink(topic)]
     pub asset: AccountId,
     pub frozen: bool,
+    pub reason_code: u32,
 }
 
 /// Emitted when a interest rate model is changed.
@@ -178,6 +382,63 @@ pub struct ReserveRestrictionsChanged {
     pub reserve_restrictions: ReserveRestrictions,
 }
 
+/// Emitted when the isolation mode parameters of a reserve are changed.
+#[ink::event]
+pub struct ReserveIsolationChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub reserve_isolation: ReserveIsolation,
+}
+
+/// Emitted when the limits on a single account's position in a reserve are changed.
+#[ink::event]
+pub struct ReserveAccountLimitsChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub reserve_account_limits: ReserveAccountLimits,
+}
+
+/// Emitted when a close factor of a reserve is changed.
+#[ink::event]
+pub struct ReserveCloseFactorChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub close_factor: Option<CloseFactor>,
+}
+
+/// Emitted when a stable rate model of a reserve is changed.
+#[ink::event]
+pub struct ReserveStableRateModelChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub stable_rate_model: Option<StableRateModel>,
+}
+
+/// Emitted when a stable debt token of a reserve is registered.
+#[ink::event]
+pub struct StableDebtTokenRegistered {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub s_token_code_hash: [u8; 32],
+    pub s_token_address: AccountId,
+}
+
+/// Emitted when the interest accrual mode of a reserve is changed.
+#[ink::event]
+pub struct ReserveAccrualModeChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub accrual_mode: InterestAccrualMode,
+}
+
+/// Emitted when the size of the ring of the TW utilization rate entries of a reserve is changed.
+#[ink::event]
+pub struct ReserveTwSizeChanged {
+    #[ink(topic)]
+    pub asset: AccountId,
+    pub size: u32,
+}
+
 /// Emitted when a reserve fees are changed.
 #[ink::event]
 pub struct ReserveFeesChanged {
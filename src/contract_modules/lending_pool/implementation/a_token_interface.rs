@@ -7,6 +7,7 @@ use crate::{
         LendingPoolError,
     },
 };
+use abax_library::structs::PausableAction;
 use ink::codegen::TraitCallBuilder;
 use ink::{env::DefaultEnvironment, prelude::*};
 use pendzl::{
@@ -46,13 +47,16 @@ pub trait LendingPoolATokenInterfaceImpl:
 
         let reserve_abacus_tokens_tokens = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(underlying_asset)
+            .find_reserve_abacus_tokens(&underlying_asset)
             .ok_or(LendingPoolError::AssetNotRegistered)?;
         if Self::env().caller() != reserve_abacus_tokens_tokens.a_token_address
         {
             return Err((AccessControlError::MissingRole).into());
         }
+        self.data::<LendingPoolStorage>().ensure_action_not_paused(
+            &underlying_asset,
+            PausableAction::Transfer,
+        )?;
 
         let timestamp = Self::env().block_timestamp();
         let (
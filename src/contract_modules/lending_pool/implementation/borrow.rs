@@ -1,6 +1,9 @@
 // SPDX-License-Identifier: BUSL-1.1
 use crate::lending_pool::{
-    events::{Borrow, CollateralSet, MarketRuleChosen, Repay},
+    events::{
+        Borrow, BorrowStable, CollateralSet, MarketRuleChosen, Repay,
+        RepayStable,
+    },
     LendingPoolError, RuleId,
 };
 use ink::{
@@ -88,6 +91,8 @@ pub trait LendingPoolBorrowImpl:
             op: Operation::Borrow,
             args: OperationArgs { asset, amount },
         }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
         let res = self
             .data::<LendingPoolStorage>()
             .account_for_account_actions(&on_behalf_of, &mut actions)?;
@@ -102,8 +107,7 @@ pub trait LendingPoolBorrowImpl:
         //// ABACUS TOKEN EVENTS
         let abacus_tokens = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset)
+            .find_reserve_abacus_tokens(&asset)
             .unwrap();
         // ATOKEN
         _emit_abacus_token_transfer_event(
@@ -144,6 +148,8 @@ pub trait LendingPoolBorrowImpl:
             op: Operation::Repay,
             args: OperationArgs { asset, amount },
         }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
         let res = self
             .data::<LendingPoolStorage>()
             .account_for_account_actions(&on_behalf_of, &mut actions)?;
@@ -160,8 +166,7 @@ pub trait LendingPoolBorrowImpl:
         //// ABACUS TOKEN EVENTS
         let abacus_tokens = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset)
+            .find_reserve_abacus_tokens(&asset)
             .unwrap();
         // ATOKEN
         _emit_abacus_token_transfer_event(
@@ -186,4 +191,113 @@ pub trait LendingPoolBorrowImpl:
         });
         Ok(actions[0].args.amount)
     }
+
+    fn borrow_stable(
+        &mut self,
+        asset: AccountId,
+        on_behalf_of: AccountId,
+        amount: Balance,
+        #[allow(unused_variables)] data: Vec<u8>,
+    ) -> Result<(), LendingPoolError> {
+        _check_amount_not_zero(amount)?;
+
+        let mut actions = vec![Action {
+            op: Operation::BorrowStable,
+            args: OperationArgs { asset, amount },
+        }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
+        let res = self
+            .data::<LendingPoolStorage>()
+            .account_for_account_actions(&on_behalf_of, &mut actions)?;
+        let (_, account_accumulated_stable_debt_interest) =
+            res.first().unwrap();
+
+        //// TOKEN TRANSFER
+        self._transfer_out(&asset, &Self::env().caller(), &amount)?;
+
+        //// ABACUS TOKEN EVENTS
+        let s_token_address = self
+            .data::<LendingPoolStorage>()
+            .find_reserve_abacus_tokens(&asset)
+            .unwrap()
+            .s_token_address
+            .ok_or(LendingPoolError::StableDebtTokenNotRegistered)?;
+        // STOKEN
+        _emit_abacus_token_transfer_event_and_decrease_allowance(
+            &s_token_address,
+            &on_behalf_of,
+            (account_accumulated_stable_debt_interest
+                .checked_add(amount)
+                .ok_or(MathError::Overflow)?) as i128,
+            &(Self::env().caller()),
+            amount,
+        )?;
+        //// emit event
+        let asset_id = self.data::<LendingPoolStorage>().asset_id(&asset)?;
+        let (account_reserve_data, _) = self
+            .data::<LendingPoolStorage>()
+            .get_account_reserve_data(asset_id, &on_behalf_of);
+        ink::env::emit_event::<DefaultEnvironment, BorrowStable>(
+            BorrowStable {
+                asset,
+                caller: Self::env().caller(),
+                on_behalf_of,
+                amount,
+                stable_rate_e18: account_reserve_data.stable_rate_e18,
+            },
+        );
+        Ok(())
+    }
+
+    fn repay_stable(
+        &mut self,
+        asset: AccountId,
+        on_behalf_of: AccountId,
+        amount: Balance,
+        #[allow(unused_variables)] data: Vec<u8>,
+    ) -> Result<Balance, LendingPoolError> {
+        _check_amount_not_zero(amount)?;
+
+        let mut actions = vec![Action {
+            op: Operation::RepayStable,
+            args: OperationArgs { asset, amount },
+        }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
+        let res = self
+            .data::<LendingPoolStorage>()
+            .account_for_account_actions(&on_behalf_of, &mut actions)?;
+        let (_, account_accumulated_stable_debt_interest) =
+            res.first().unwrap();
+        //// TOKEN TRANSFER
+        self._transfer_in(
+            &asset,
+            &Self::env().caller(),
+            &actions[0].args.amount,
+        )?;
+        //// ABACUS TOKEN EVENTS
+        let s_token_address = self
+            .data::<LendingPoolStorage>()
+            .find_reserve_abacus_tokens(&asset)
+            .unwrap()
+            .s_token_address
+            .ok_or(LendingPoolError::StableDebtTokenNotRegistered)?;
+        // STOKEN
+        _emit_abacus_token_transfer_event(
+            &s_token_address,
+            &on_behalf_of,
+            (*account_accumulated_stable_debt_interest as i128)
+                .overflowing_sub(actions[0].args.amount as i128)
+                .0,
+        )?;
+        //// EVENT
+        ink::env::emit_event::<DefaultEnvironment, RepayStable>(RepayStable {
+            asset,
+            caller: Self::env().caller(),
+            on_behalf_of,
+            amount: actions[0].args.amount,
+        });
+        Ok(actions[0].args.amount)
+    }
 }
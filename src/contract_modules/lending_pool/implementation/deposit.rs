@@ -38,6 +38,8 @@ pub trait LendingPoolDepositImpl:
             op: Operation::Deposit,
             args: OperationArgs { asset, amount },
         }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
         let res = self
             .data::<LendingPoolStorage>()
             .account_for_account_actions(&on_behalf_of, &mut actions)?;
@@ -54,8 +56,7 @@ pub trait LendingPoolDepositImpl:
         //// ABACUS TOKEN EVENTS
         let abacus_tokens = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset)
+            .find_reserve_abacus_tokens(&asset)
             .unwrap();
         // ATOKEN
         _emit_abacus_token_transfer_event(
@@ -96,6 +97,8 @@ pub trait LendingPoolDepositImpl:
             op: Operation::Withdraw,
             args: OperationArgs { asset, amount },
         }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
         let res = self
             .data::<LendingPoolStorage>()
             .account_for_account_actions(&on_behalf_of, &mut actions)?;
@@ -114,8 +117,7 @@ pub trait LendingPoolDepositImpl:
         //// ABACUS TOKEN EVENTS
         let abacus_tokens = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset)
+            .find_reserve_abacus_tokens(&asset)
             .unwrap();
         // ATOKEN
         _emit_abacus_token_transfer_event_and_decrease_allowance(
@@ -6,7 +6,10 @@ use crate::{
     lending_pool::{events::FlashLoan, LendingPoolError},
 };
 
-use abax_library::math::{E6_U128, E6_U32};
+use abax_library::{
+    math::{E6_U128, E6_U32},
+    structs::PausableAction,
+};
 use ink::{
     env::{
         call::{build_call, ExecutionInput},
@@ -15,16 +18,21 @@ use ink::{
     prelude::{vec, vec::Vec},
 };
 
-use pendzl::math::operations::{mul_div, Rounding};
+use pendzl::math::{
+    errors::MathError,
+    operations::{mul_div, Rounding},
+};
 use pendzl::traits::{AccountId, Balance, StorageFieldGetter};
 
 use super::{
-    internal::{Transfer, _check_amount_not_zero},
+    internal::_check_amount_not_zero, liquidate::LendingPoolLiquidateImpl,
     storage::LendingPoolStorage,
 };
 use ink::codegen::TraitCallBuilder;
 
-pub trait LendingPoolFlashImpl: StorageFieldGetter<LendingPoolStorage> {
+pub trait LendingPoolFlashImpl:
+    StorageFieldGetter<LendingPoolStorage> + LendingPoolLiquidateImpl
+{
     fn flash_loan(
         &mut self,
         receiver: AccountId,
@@ -37,6 +45,114 @@ pub trait LendingPoolFlashImpl: StorageFieldGetter<LendingPoolStorage> {
         }
 
         let mut fees: Vec<u128> = vec![];
+        let (flash_fee_e6, fee_part_e6) = self._flash_loan_fee_e6_and_part_e6();
+
+        for i in 0..assets.len() {
+            _check_amount_not_zero(amounts[i])?;
+            self.data::<LendingPoolStorage>().ensure_action_not_paused(
+                &assets[i],
+                PausableAction::FlashLoan,
+            )?;
+
+            fees.push(_calculate_flash_loan_fee(
+                amounts[i],
+                flash_fee_e6,
+                fee_part_e6,
+            )?);
+            self._transfer_out(&assets[i], &receiver, &amounts[i])?;
+        }
+
+        self._call_flash_loan_receiver(
+            &receiver,
+            assets.clone(),
+            amounts.clone(),
+            fees.clone(),
+            receiver_params,
+        )?;
+
+        for i in 0..assets.len() {
+            self._transfer_in(
+                &assets[i],
+                &receiver,
+                &amounts[i].checked_add(fees[i]).unwrap(),
+            )?;
+
+            ink::env::emit_event::<DefaultEnvironment, FlashLoan>(FlashLoan {
+                receiver,
+                caller: Self::env().account_id(),
+                asset: assets[i],
+                amount: amounts[i],
+                fee: fees[i],
+            });
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn flash_liquidate(
+        &mut self,
+        liquidated_account: AccountId,
+        asset_to_repay: AccountId,
+        asset_to_take: AccountId,
+        amount_to_repay: Balance,
+        minimum_recieved_for_one_repaid_token_e18: u128,
+        receiver: AccountId,
+        receiver_params: Vec<u8>,
+    ) -> Result<(Balance, Balance), LendingPoolError> {
+        _check_amount_not_zero(amount_to_repay)?;
+        self.data::<LendingPoolStorage>().ensure_action_not_paused(
+            &asset_to_repay,
+            PausableAction::FlashLoan,
+        )?;
+        let caller = Self::env().caller();
+
+        // the debt is repaid with the pool's own liquidity (flash loaned to the caller)
+        // and the underlying of the seized collateral is transferred to the receiver
+        let (amount_to_repay, amount_to_take) = self._liquidate(
+            &caller,
+            liquidated_account,
+            asset_to_repay,
+            asset_to_take,
+            amount_to_repay,
+            minimum_recieved_for_one_repaid_token_e18,
+            Some(receiver),
+        )?;
+
+        // the receiver swaps the seized collateral and repays the flash loan with the fee
+        let (flash_fee_e6, fee_part_e6) = self._flash_loan_fee_e6_and_part_e6();
+        let fee = _calculate_flash_loan_fee(
+            amount_to_repay,
+            flash_fee_e6,
+            fee_part_e6,
+        )?;
+        self._call_flash_loan_receiver(
+            &receiver,
+            vec![asset_to_repay],
+            vec![amount_to_repay],
+            vec![fee],
+            receiver_params,
+        )?;
+        self._transfer_in(
+            &asset_to_repay,
+            &receiver,
+            &amount_to_repay
+                .checked_add(fee)
+                .ok_or(MathError::Overflow)?,
+        )?;
+
+        ink::env::emit_event::<DefaultEnvironment, FlashLoan>(FlashLoan {
+            receiver,
+            caller,
+            asset: asset_to_repay,
+            amount: amount_to_repay,
+            fee,
+        });
+
+        Ok((amount_to_repay, amount_to_take))
+    }
+
+    /// Returns the flash loan fee and the part of it (after the caller's fee reduction) to be paid. 10^6 = 100%
+    fn _flash_loan_fee_e6_and_part_e6(&self) -> (u128, u32) {
         let flash_fee_e6 = self
             .data::<LendingPoolStorage>()
             .flash_loan_fee_e6
@@ -60,22 +176,19 @@ pub trait LendingPoolFlashImpl: StorageFieldGetter<LendingPoolStorage> {
                 0
             }
         };
-        let fee_part_e6 = E6_U32.saturating_sub(fee_reduction_e6);
-
-        for i in 0..assets.len() {
-            _check_amount_not_zero(amounts[i])?;
-
-            let pre_fee =
-                mul_div(amounts[i], flash_fee_e6, E6_U128, Rounding::Up)?;
-            let fee =
-                mul_div(pre_fee, fee_part_e6 as u128, E6_U128, Rounding::Up)?;
-
-            fees.push(fee);
-            self._transfer_out(&assets[i], &receiver, &amounts[i])?;
-        }
+        (flash_fee_e6, E6_U32.saturating_sub(fee_reduction_e6))
+    }
 
+    fn _call_flash_loan_receiver(
+        &self,
+        receiver: &AccountId,
+        assets: Vec<AccountId>,
+        amounts: Vec<Balance>,
+        fees: Vec<u128>,
+        receiver_params: Vec<u8>,
+    ) -> Result<(), LendingPoolError> {
         build_call::<DefaultEnvironment>()
-            .call_v1(receiver)
+            .call_v1(*receiver)
             .call_flags(CallFlags::ALLOW_REENTRY)
             .exec_input(
                 ExecutionInput::new(ink::env::call::Selector::new(
@@ -83,31 +196,24 @@ pub trait LendingPoolFlashImpl: StorageFieldGetter<LendingPoolStorage> {
                         "FlashLoanReceiver::execute_operation"
                     ),
                 ))
-                .push_arg(assets.clone())
-                .push_arg(amounts.clone())
-                .push_arg(fees.clone())
+                .push_arg(assets)
+                .push_arg(amounts)
+                .push_arg(fees)
                 .push_arg(receiver_params),
             )
             .returns::<Result<(), FlashLoanReceiverError>>()
             .try_invoke()
             .unwrap()
             .unwrap()?;
-
-        for i in 0..assets.len() {
-            self._transfer_in(
-                &assets[i],
-                &receiver,
-                &amounts[i].checked_add(fees[i]).unwrap(),
-            )?;
-
-            ink::env::emit_event::<DefaultEnvironment, FlashLoan>(FlashLoan {
-                receiver,
-                caller: Self::env().account_id(),
-                asset: assets[i],
-                amount: amounts[i],
-                fee: fees[i],
-            });
-        }
         Ok(())
     }
 }
+
+fn _calculate_flash_loan_fee(
+    amount: Balance,
+    flash_fee_e6: u128,
+    fee_part_e6: u32,
+) -> Result<Balance, MathError> {
+    let pre_fee = mul_div(amount, flash_fee_e6, E6_U128, Rounding::Up)?;
+    mul_div(pre_fee, fee_part_e6 as u128, E6_U128, Rounding::Up)
+}
@@ -149,6 +149,13 @@ pub trait Transfer {
         to: &AccountId,
         amount: &Balance,
     ) -> Result<(), LendingPoolError>;
+
+    /// Ensures that self holds at least `amount` of `asset` that can be transferred out. Protocol stablecoins are minted so they are always available.
+    fn _ensure_liquidity(
+        &self,
+        asset: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), LendingPoolError>;
 }
 
 impl<T: StorageFieldGetter<LendingPoolStorage>> Transfer for T {
@@ -160,8 +167,8 @@ impl<T: StorageFieldGetter<LendingPoolStorage>> Transfer for T {
     ) -> Result<(), LendingPoolError> {
         if self
             .data()
-            .interest_rate_model
-            .contains(self.data().asset_id(asset)?)
+            .find_interest_rate_model(self.data().asset_id(asset)?)
+            .is_some()
         {
             let mut psp22: PSP22Ref = (*asset).into();
             psp22
@@ -190,8 +197,8 @@ impl<T: StorageFieldGetter<LendingPoolStorage>> Transfer for T {
     ) -> Result<(), LendingPoolError> {
         if self
             .data()
-            .interest_rate_model
-            .contains(self.data().asset_id(asset)?)
+            .find_interest_rate_model(self.data().asset_id(asset)?)
+            .is_some()
         {
             let mut psp22: PSP22Ref = (*asset).into();
             psp22
@@ -205,6 +212,29 @@ impl<T: StorageFieldGetter<LendingPoolStorage>> Transfer for T {
         }
         Ok(())
     }
+
+    fn _ensure_liquidity(
+        &self,
+        asset: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), LendingPoolError> {
+        if self
+            .data()
+            .find_interest_rate_model(self.data().asset_id(asset)?)
+            .is_some()
+        {
+            let psp22: PSP22Ref = (*asset).into();
+            let balance = psp22
+                .call()
+                .balance_of(Self::env().account_id())
+                .call_v1()
+                .invoke();
+            if balance < *amount {
+                return Err(LendingPoolError::InsufficientLiquidity);
+            }
+        }
+        Ok(())
+    }
 }
 
 pub trait InternalIncome {
@@ -229,8 +259,7 @@ impl<T: StorageFieldGetter<LendingPoolStorage>> InternalIncome for T {
             let asset_id = self.data().asset_id(asset)?;
             let mut reserve_indexes_and_fees = self
                 .data()
-                .reserve_indexes_and_fees
-                .get(asset_id)
+                .find_reserve_indexes_and_fees(asset_id)
                 .ok_or(LendingPoolError::AssetNotRegistered)?;
 
             let income = reserve_indexes_and_fees.fees.take_earned_fee();
@@ -248,14 +277,13 @@ impl<T: StorageFieldGetter<LendingPoolStorage>> InternalIncome for T {
             let asset_id = self.data().asset_id(asset)?;
             let mut reserve_indexes_and_fees = self
                 .data()
-                .reserve_indexes_and_fees
-                .get(asset_id)
+                .find_reserve_indexes_and_fees(asset_id)
                 .ok_or(LendingPoolError::AssetNotRegistered)?;
 
             let income = reserve_indexes_and_fees.fees.take_earned_fee();
 
             self.data()
-                .reserve_indexes_and_fees
+                .reserve_indexes_and_fees_v2
                 .insert(asset_id, &reserve_indexes_and_fees);
 
             result.push((*asset, income));
@@ -1,6 +1,17 @@
 // SPDX-License-Identifier: BUSL-1.1
-use crate::lending_pool::{events::Liquidation, LendingPoolError};
-use abax_library::math::E18_U128;
+use crate::lending_pool::{
+    events::{
+        Liquidation, LiquidationAuctionClosed, LiquidationAuctionFilled,
+        LiquidationAuctionStarted,
+    },
+    LendingPoolError,
+};
+use abax_library::{
+    math::{E18_U128, E6_U128},
+    structs::PausableAction,
+};
+use scale::DecodeAll;
+
 use ink::{
     env::DefaultEnvironment,
     prelude::{vec::Vec, *},
@@ -30,24 +41,94 @@ pub trait LendingPoolLiquidateImpl:
         liquidated_account: AccountId,
         asset_to_repay: AccountId,
         asset_to_take: AccountId,
+        amount_to_repay: Balance,
+        minimum_recieved_for_one_repaid_token_e18: u128,
+        data: Vec<u8>,
+    ) -> Result<(Balance, Balance), LendingPoolError> {
+        let receive_underlying = if data.is_empty() {
+            false
+        } else {
+            bool::decode_all(&mut &data[..])
+                .map_err(|_| LendingPoolError::InvalidLiquidationData)?
+        };
+        let caller = Self::env().caller();
+        let (amount_to_repay, amount_to_take) = self._liquidate(
+            &caller,
+            liquidated_account,
+            asset_to_repay,
+            asset_to_take,
+            amount_to_repay,
+            minimum_recieved_for_one_repaid_token_e18,
+            receive_underlying.then_some(caller),
+        )?;
+
+        //// TOKEN TRANSFERS
+        self._transfer_in(&asset_to_repay, &caller, &amount_to_repay)?;
+
+        Ok((amount_to_repay, amount_to_take))
+    }
+
+    /// performs the liquidation by the `liquidator` - accounts for it and emits the events. Doesn't transfer in the repaid `asset_to_repay`.
+    /// If `underlying_receiver` is Some the taken `asset_to_take` is withdrawn and transferred to it, otherwise it is deposited to the `liquidator`.
+    #[allow(clippy::too_many_arguments)]
+    fn _liquidate(
+        &mut self,
+        liquidator: &AccountId,
+        liquidated_account: AccountId,
+        asset_to_repay: AccountId,
+        asset_to_take: AccountId,
         mut amount_to_repay: Balance,
         minimum_recieved_for_one_repaid_token_e18: u128,
-        #[allow(unused_variables)] data: Vec<u8>,
+        underlying_receiver: Option<AccountId>,
     ) -> Result<(Balance, Balance), LendingPoolError> {
+        self.data::<LendingPoolStorage>().ensure_action_not_paused(
+            &asset_to_repay,
+            PausableAction::Liquidate,
+        )?;
+        self.data::<LendingPoolStorage>().ensure_action_not_paused(
+            &asset_to_take,
+            PausableAction::Liquidate,
+        )?;
+        if underlying_receiver.is_some() {
+            self.data::<LendingPoolStorage>().ensure_action_not_paused(
+                &asset_to_take,
+                PausableAction::Withdraw,
+            )?;
+        }
+
+        let timestamp = Self::env().block_timestamp();
+        let caller = *liquidator;
+
         // ensure account is undercollaterized
-        match self
+        // the debt in a delisted asset (in the settlement stage) can be liquidated regardless of collateralization
+        let is_collateralized = self
             .data::<LendingPoolStorage>()
             .ensure_collateralized_by_account(&liquidated_account)
-        {
-            Ok(_) => Err(LendingPoolError::Collaterized),
-            Err(_) => Ok(()),
-        }?;
+            .is_ok();
+        if is_collateralized {
+            self.data::<LendingPoolStorage>()
+                .ensure_delisting_settlement(&asset_to_repay, &timestamp)
+                .map_err(|_| LendingPoolError::Collaterized)?;
+        }
 
-        let timestamp = Self::env().block_timestamp();
-        let caller = Self::env().caller();
+        let penalty_part_e6 = self
+            .data::<LendingPoolStorage>()
+            .get_liquidation_penalty_part_e6(
+                &liquidated_account,
+                &asset_to_repay,
+                &timestamp,
+            )?;
+        // the collateralized account is settled without the penalty
+        let applied_penalty_part_e6 = if is_collateralized {
+            0
+        } else {
+            penalty_part_e6.unwrap_or(E6_U128)
+        };
 
         let (
             amount_to_take,
+            max_amount_to_repay,
+            is_stable_debt_repaid,
             (
                 account_accumulated_deposit_interest_to_repay,
                 account_accumulated_debt_interest_to_repay,
@@ -66,6 +147,8 @@ pub trait LendingPoolLiquidateImpl:
             &asset_to_repay,
             &asset_to_take,
             &mut amount_to_repay,
+            &applied_penalty_part_e6,
+            underlying_receiver.is_some(),
             &timestamp,
         )?;
 
@@ -77,17 +160,19 @@ pub trait LendingPoolLiquidateImpl:
         {
             return Err(LendingPoolError::MinimumRecieved);
         }
-        //// TOKEN TRANSFERS
 
-        self._transfer_in(&asset_to_repay, &caller, &amount_to_repay)?;
+        //// TOKEN TRANSFERS
+        if let Some(receiver) = underlying_receiver {
+            self._ensure_liquidity(&asset_to_take, &amount_to_take)?;
+            self._transfer_out(&asset_to_take, &receiver, &amount_to_take)?;
+        }
 
         //// ABACUS TOKEN EVENTS
         //// to_repay_token
 
         let abacus_tokens_to_repay = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset_to_repay)
+            .find_reserve_abacus_tokens(&asset_to_repay)
             .unwrap();
         // ATOKEN
         _emit_abacus_token_transfer_event(
@@ -95,9 +180,16 @@ pub trait LendingPoolLiquidateImpl:
             &liquidated_account,
             (account_accumulated_deposit_interest_to_repay) as i128,
         )?;
-        // VTOKEN
+        // VTOKEN or STOKEN
+        let debt_token_to_repay = if is_stable_debt_repaid {
+            abacus_tokens_to_repay
+                .s_token_address
+                .ok_or(LendingPoolError::StableDebtTokenNotRegistered)?
+        } else {
+            abacus_tokens_to_repay.v_token_address
+        };
         _emit_abacus_token_transfer_event(
-            &abacus_tokens_to_repay.v_token_address,
+            &debt_token_to_repay,
             &liquidated_account,
             (account_accumulated_debt_interest_to_repay as i128)
                 .overflowing_sub(amount_to_repay as i128)
@@ -107,42 +199,41 @@ pub trait LendingPoolLiquidateImpl:
         //// to_take_token
         let abacus_tokens_to_take = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset_to_take)
+            .find_reserve_abacus_tokens(&asset_to_take)
             .unwrap();
+        let mut a_token_transfers = vec![TransferEventDataSimplified {
+            account: liquidated_account,
+            amount: (account_accumulated_deposit_interest_to_take as i128)
+                .overflowing_sub(amount_to_take as i128)
+                .0,
+        }];
+        let mut v_token_transfers = vec![TransferEventDataSimplified {
+            account: liquidated_account,
+            amount: account_accumulated_debt_interest_to_take as i128,
+        }];
+        // the liquidator's deposit changes only if the taken asset is not received as underlying
+        if underlying_receiver.is_none() {
+            a_token_transfers.push(TransferEventDataSimplified {
+                account: caller,
+                amount: caller_accumulated_deposit_interest_to_take
+                    .checked_add(amount_to_take)
+                    .ok_or(MathError::Overflow)?
+                    as i128,
+            });
+            v_token_transfers.push(TransferEventDataSimplified {
+                account: caller,
+                amount: caller_accumulated_debt_interest_to_take as i128,
+            });
+        }
         // ATOKEN
         _emit_abacus_token_transfer_events(
             &abacus_tokens_to_take.a_token_address,
-            &vec![
-                TransferEventDataSimplified {
-                    account: liquidated_account,
-                    amount: (account_accumulated_deposit_interest_to_take
-                        as i128)
-                        .overflowing_sub(amount_to_take as i128)
-                        .0,
-                },
-                TransferEventDataSimplified {
-                    account: caller,
-                    amount: caller_accumulated_deposit_interest_to_take
-                        .checked_add(amount_to_take)
-                        .ok_or(MathError::Overflow)?
-                        as i128,
-                },
-            ],
+            &a_token_transfers,
         )?;
         // VTOKEN
         _emit_abacus_token_transfer_events(
             &abacus_tokens_to_take.v_token_address,
-            &vec![
-                TransferEventDataSimplified {
-                    account: liquidated_account,
-                    amount: account_accumulated_debt_interest_to_take as i128,
-                },
-                TransferEventDataSimplified {
-                    account: caller,
-                    amount: caller_accumulated_debt_interest_to_take as i128,
-                },
-            ],
+            &v_token_transfers,
         )?;
 
         // EVENT
@@ -153,11 +244,71 @@ pub trait LendingPoolLiquidateImpl:
             asset_to_take,
             amount_repaid: amount_to_repay,
             amount_taken: amount_to_take,
+            max_amount_to_repay,
+            received_underlying: underlying_receiver.is_some(),
         });
+        if let Some(penalty_part_e6) = penalty_part_e6 {
+            ink::env::emit_event::<DefaultEnvironment, LiquidationAuctionFilled>(
+                LiquidationAuctionFilled {
+                    account: liquidated_account,
+                    liquidator: caller,
+                    asset_to_repay,
+                    asset_to_take,
+                    amount_repaid: amount_to_repay,
+                    amount_taken: amount_to_take,
+                    penalty_part_e6,
+                },
+            );
+        }
 
         Ok((amount_to_repay, amount_to_take))
     }
 
+    fn start_liquidation_auction(
+        &mut self,
+        account: AccountId,
+    ) -> Result<(), LendingPoolError> {
+        match self
+            .data::<LendingPoolStorage>()
+            .ensure_collateralized_by_account(&account)
+        {
+            Ok(_) => Err(LendingPoolError::Collaterized),
+            Err(_) => Ok(()),
+        }?;
+
+        let timestamp = Self::env().block_timestamp();
+        self.data::<LendingPoolStorage>()
+            .account_for_start_liquidation_auction(&account, &timestamp)?;
+
+        ink::env::emit_event::<DefaultEnvironment, LiquidationAuctionStarted>(
+            LiquidationAuctionStarted {
+                account,
+                caller: Self::env().caller(),
+                start_timestamp: timestamp,
+            },
+        );
+        Ok(())
+    }
+
+    fn close_liquidation_auction(
+        &mut self,
+        account: AccountId,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .ensure_collateralized_by_account(&account)?;
+
+        self.data::<LendingPoolStorage>()
+            .account_for_close_liquidation_auction(&account)?;
+
+        ink::env::emit_event::<DefaultEnvironment, LiquidationAuctionClosed>(
+            LiquidationAuctionClosed {
+                account,
+                caller: Self::env().caller(),
+            },
+        );
+        Ok(())
+    }
+
     // fn liquidate_outside(
     //     &mut self,
     //     account: AccountId,
@@ -1,12 +1,31 @@
 // SPDX-License-Identifier: BUSL-1.1
-use crate::lending_pool::{events::InterestsAccumulated, LendingPoolError};
-use ink::{env::DefaultEnvironment, primitives::AccountId};
-use pendzl::{math::errors::MathError, traits::StorageFieldGetter};
+use crate::lending_pool::{
+    events::{
+        BadDebtDepositSeized, BadDebtSettled, DelistedDepositSettled,
+        InterestsAccumulated, SafetyFundIncreased, StableRateRebalanced,
+    },
+    LendingPoolError,
+};
+use abax_library::structs::{Action, Operation, OperationArgs};
+use ink::{
+    env::DefaultEnvironment,
+    prelude::{vec, vec::Vec},
+    primitives::AccountId,
+};
+use pendzl::{
+    math::errors::MathError,
+    traits::{Balance, StorageFieldGetter},
+};
 
-use super::storage::LendingPoolStorage;
+use super::{
+    internal::{
+        Transfer, _check_amount_not_zero, _emit_abacus_token_transfer_event,
+    },
+    storage::LendingPoolStorage,
+};
 
 pub trait LendingPoolMaintainImpl:
-    StorageFieldGetter<LendingPoolStorage>
+    StorageFieldGetter<LendingPoolStorage> + Transfer
 {
     fn accumulate_interest(
         &mut self,
@@ -26,7 +45,7 @@ pub trait LendingPoolMaintainImpl:
     fn adjust_rate_at_target(
         &mut self,
         asset: AccountId,
-        guessed_index: u32,
+        guessed_index: Option<u32>,
     ) -> Result<u64, LendingPoolError> {
         let timestamp = Self::env().block_timestamp();
 
@@ -34,14 +53,16 @@ pub trait LendingPoolMaintainImpl:
 
         if let Some(mut interest_rate_model) = self
             .data::<LendingPoolStorage>()
-            .interest_rate_model
-            .get(asset_id)
+            .find_interest_rate_model(asset_id)
         {
+            let adaptive_model = interest_rate_model
+                .as_adaptive_mut()
+                .ok_or(LendingPoolError::InterestRateModelNotAdaptive)?;
             if timestamp
-                < interest_rate_model
+                < adaptive_model
                     .last_adjustment_timestamp
                     .checked_add(
-                        interest_rate_model.minimal_time_between_adjustments,
+                        adaptive_model.minimal_time_between_adjustments,
                     )
                     .ok_or(MathError::Overflow)?
             {
@@ -51,16 +72,16 @@ pub trait LendingPoolMaintainImpl:
             let twa_ur_e6 = self
                 .data::<LendingPoolStorage>()
                 .get_tw_ur_from_shortest_period_longer_than(
-                    interest_rate_model.minimal_time_between_adjustments,
+                    adaptive_model.minimal_time_between_adjustments,
                     asset_id,
                     guessed_index,
                 )?;
 
-            let res = interest_rate_model
-                .adjust_rate_at_target(twa_ur_e6, timestamp)?;
+            let res =
+                adaptive_model.adjust_rate_at_target(twa_ur_e6, timestamp)?;
 
             self.data::<LendingPoolStorage>()
-                .interest_rate_model
+                .interest_rate_model_v2
                 .insert(asset_id, &interest_rate_model);
 
             Ok(res)
@@ -68,4 +89,280 @@ pub trait LendingPoolMaintainImpl:
             Err(LendingPoolError::AssetNotRegistered)
         }
     }
+
+    fn settle_bad_debt(
+        &mut self,
+        account: AccountId,
+    ) -> Result<(), LendingPoolError> {
+        let timestamp = Self::env().block_timestamp();
+        let caller = Self::env().caller();
+
+        let (seizures, write_offs) = self
+            .data::<LendingPoolStorage>()
+            .account_for_settle_bad_debt(&account, &timestamp)?;
+
+        for seizure in seizures {
+            let asset = self
+                .data::<LendingPoolStorage>()
+                .id_to_asset
+                .get(seizure.asset_id)
+                .unwrap(); // asset_id exists => id_to_asset exists
+
+            //// ABACUS TOKEN EVENTS
+            let abacus_tokens = self
+                .data::<LendingPoolStorage>()
+                .find_reserve_abacus_tokens(&asset)
+                .unwrap();
+            // ATOKEN
+            _emit_abacus_token_transfer_event(
+                &abacus_tokens.a_token_address,
+                &account,
+                (seizure.interests.0 as i128)
+                    .overflowing_sub(seizure.amount as i128)
+                    .0,
+            )?;
+            // VTOKEN
+            _emit_abacus_token_transfer_event(
+                &abacus_tokens.v_token_address,
+                &account,
+                seizure.interests.1 as i128,
+            )?;
+
+            //// EVENT
+            ink::env::emit_event::<DefaultEnvironment, BadDebtDepositSeized>(
+                BadDebtDepositSeized {
+                    caller,
+                    account,
+                    asset,
+                    amount: seizure.amount,
+                },
+            );
+        }
+
+        for write_off in write_offs {
+            let asset = self
+                .data::<LendingPoolStorage>()
+                .id_to_asset
+                .get(write_off.asset_id)
+                .unwrap(); // asset_id exists => id_to_asset exists
+
+            //// ABACUS TOKEN EVENTS
+            let abacus_tokens = self
+                .data::<LendingPoolStorage>()
+                .find_reserve_abacus_tokens(&asset)
+                .unwrap();
+            // ATOKEN
+            _emit_abacus_token_transfer_event(
+                &abacus_tokens.a_token_address,
+                &account,
+                write_off.interests.0 as i128,
+            )?;
+            // VTOKEN
+            _emit_abacus_token_transfer_event(
+                &abacus_tokens.v_token_address,
+                &account,
+                (write_off.interests.1 as i128)
+                    .overflowing_sub(
+                        write_off
+                            .amount
+                            .checked_sub(write_off.stable_amount)
+                            .ok_or(MathError::Underflow)?
+                            as i128,
+                    )
+                    .0,
+            )?;
+            // STOKEN
+            if let Some(s_token_address) = abacus_tokens.s_token_address {
+                _emit_abacus_token_transfer_event(
+                    &s_token_address,
+                    &account,
+                    (write_off.stable_interest as i128)
+                        .overflowing_sub(write_off.stable_amount as i128)
+                        .0,
+                )?;
+            }
+
+            //// EVENT
+            ink::env::emit_event::<DefaultEnvironment, BadDebtSettled>(
+                BadDebtSettled {
+                    caller,
+                    account,
+                    asset,
+                    amount: write_off.amount,
+                    covered_by_earned_fee: write_off.covered_by_earned_fee,
+                    covered_by_safety_fund: write_off.covered_by_safety_fund,
+                    socialized: write_off.socialized,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    fn rebalance_stable_rate(
+        &mut self,
+        asset: AccountId,
+        account: AccountId,
+    ) -> Result<u64, LendingPoolError> {
+        let timestamp = Self::env().block_timestamp();
+        let caller = Self::env().caller();
+
+        let (new_stable_rate_e18, account_accumulated_stable_debt_interest) =
+            self.data::<LendingPoolStorage>()
+                .account_for_stable_rate_rebalance(
+                    &asset, &account, &timestamp,
+                )?;
+
+        //// ABACUS TOKEN EVENTS
+        let s_token_address = self
+            .data::<LendingPoolStorage>()
+            .find_reserve_abacus_tokens(&asset)
+            .unwrap()
+            .s_token_address
+            .ok_or(LendingPoolError::StableDebtTokenNotRegistered)?;
+        // STOKEN
+        _emit_abacus_token_transfer_event(
+            &s_token_address,
+            &account,
+            account_accumulated_stable_debt_interest as i128,
+        )?;
+
+        //// EVENT
+        ink::env::emit_event::<DefaultEnvironment, StableRateRebalanced>(
+            StableRateRebalanced {
+                asset,
+                caller,
+                account,
+                new_stable_rate_e18,
+            },
+        );
+        Ok(new_stable_rate_e18)
+    }
+
+    fn settle_delisted_deposit(
+        &mut self,
+        asset: AccountId,
+        account: AccountId,
+    ) -> Result<Balance, LendingPoolError> {
+        let timestamp = Self::env().block_timestamp();
+        self.data::<LendingPoolStorage>()
+            .ensure_delisting_settlement(&asset, &timestamp)?;
+
+        // the amount is capped at the account's deposit
+        let mut actions = vec![Action {
+            op: Operation::Withdraw,
+            args: OperationArgs {
+                asset,
+                amount: Balance::MAX,
+            },
+        }];
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&actions)?;
+        let res = self
+            .data::<LendingPoolStorage>()
+            .account_for_account_actions(&account, &mut actions)?;
+        let (
+            account_accumulated_deposit_interest,
+            account_accumulated_debt_interest,
+        ) = res.first().unwrap();
+        let amount = actions[0].args.amount;
+
+        //// TOKEN TRANSFERS
+        self._transfer_out(&asset, &account, &amount)?;
+
+        //// ABACUS TOKEN EVENTS
+        let abacus_tokens = self
+            .data::<LendingPoolStorage>()
+            .find_reserve_abacus_tokens(&asset)
+            .unwrap();
+        // ATOKEN
+        _emit_abacus_token_transfer_event(
+            &abacus_tokens.a_token_address,
+            &account,
+            (*account_accumulated_deposit_interest as i128)
+                .overflowing_sub(amount as i128)
+                .0,
+        )?;
+        // VTOKEN
+        _emit_abacus_token_transfer_event(
+            &abacus_tokens.v_token_address,
+            &account,
+            *account_accumulated_debt_interest as i128,
+        )?;
+
+        //// EVENT
+        ink::env::emit_event::<DefaultEnvironment, DelistedDepositSettled>(
+            DelistedDepositSettled {
+                caller: Self::env().caller(),
+                account,
+                asset,
+                amount,
+            },
+        );
+        Ok(amount)
+    }
+
+    fn increase_safety_fund(
+        &mut self,
+        asset: AccountId,
+        amount: Balance,
+    ) -> Result<(), LendingPoolError> {
+        _check_amount_not_zero(amount)?;
+        let caller = Self::env().caller();
+
+        self.data::<LendingPoolStorage>()
+            .account_for_safety_fund_increase(&asset, &amount)?;
+
+        //// TOKEN TRANSFERS
+        self._transfer_in(&asset, &caller, &amount)?;
+
+        //// EVENT
+        ink::env::emit_event::<DefaultEnvironment, SafetyFundIncreased>(
+            SafetyFundIncreased {
+                asset,
+                caller,
+                amount,
+            },
+        );
+        Ok(())
+    }
+
+    fn migrate_reserves(&mut self) -> Result<u32, LendingPoolError> {
+        let next_asset_id = self
+            .data::<LendingPoolStorage>()
+            .next_asset_id
+            .get()
+            .unwrap_or(0);
+        let mut migrated: u32 = 0;
+        for asset_id in 0..next_asset_id {
+            if self.data::<LendingPoolStorage>().migrate_reserve(asset_id) {
+                migrated =
+                    migrated.checked_add(1).ok_or(MathError::Overflow)?;
+            }
+        }
+        Ok(migrated)
+    }
+
+    fn migrate_accounts(
+        &mut self,
+        accounts: Vec<AccountId>,
+        from_counter: u128,
+        to_counter: u128,
+        next_counter: u128,
+    ) -> Result<u32, LendingPoolError> {
+        let mut migrated: u32 = 0;
+        for account in accounts {
+            if self.data::<LendingPoolStorage>().migrate_account(&account) {
+                migrated =
+                    migrated.checked_add(1).ok_or(MathError::Overflow)?;
+            }
+        }
+        self.data::<LendingPoolStorage>()
+            .account_for_accounts_migration(
+                from_counter,
+                to_counter,
+                next_counter,
+            );
+        Ok(migrated)
+    }
 }
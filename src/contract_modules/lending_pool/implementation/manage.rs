@@ -4,17 +4,26 @@ use crate::lending_pool::events::FeeReductionChanged;
 use crate::lending_pool::SetReserveFeesArgs;
 use crate::lending_pool::{
     events::{
-        AssetRegistered, AssetRulesChanged, FlashLoanFeeChanged, IncomeTaken,
-        PriceFeedProviderChanged, ReserveActivated, ReserveFeesChanged,
+        AssetRegistered, AssetRulesChanged, FlashLoanFeeChanged,
+        GlobalActionPauseChanged, IncomeTaken,
+        MarketRuleLiquidationAuctionChanged, PriceFeedProviderChanged,
+        ReserveAccountLimitsChanged, ReserveAccrualModeChanged,
+        ReserveActionPauseChanged, ReserveActivated, ReserveCloseFactorChanged,
+        ReserveClosed, ReserveDelistingStarted, ReserveFeesChanged,
         ReserveFrozen, ReserveInterestRateModelChanged,
-        ReserveRestrictionsChanged, StablecoinDebtRateChanged,
+        ReserveIsolationChanged, ReserveRestrictionsChanged,
+        ReserveStableRateModelChanged, ReserveTwSizeChanged,
+        StableDebtTokenRegistered, StablecoinDebtRateChanged,
+        TimelockDelayChanged,
     },
-    LendingPoolError, MarketRule, ASSET_LISTING_ADMIN, EMERGENCY_ADMIN,
-    PARAMETERS_ADMIN, STABLECOIN_RATE_ADMIN, TREASURY,
+    LendingPoolError, ManageAction, MarketRule, ASSET_LISTING_ADMIN,
+    EMERGENCY_ADMIN, PARAMETERS_ADMIN, STABLECOIN_RATE_ADMIN, TREASURY,
 };
 use abax_library::structs::{
-    AssetRules, InterestRateModel, InterestRateModelParams,
-    ReserveAbacusTokens, ReserveData, ReserveFees, ReserveRestrictions,
+    validate_flash_loan_fee_e6, AssetRules, CloseFactor, InterestAccrualMode,
+    InterestRateModel, InterestRateModelParams, LiquidationAuctionParams,
+    PausableAction, ReserveAbacusTokens, ReserveAccountLimits, ReserveData,
+    ReserveFees, ReserveIsolation, ReserveRestrictions, StableRateModel,
 };
 use ink::env::DefaultEnvironment;
 use ink::prelude::string::{String, ToString};
@@ -24,8 +33,8 @@ use ink::{
     primitives::{AccountId, Hash},
     ToAccountId,
 };
-use pendzl::contracts::access_control;
-use pendzl::traits::{Balance, StorageFieldGetter};
+use pendzl::contracts::access_control::{self, RoleType};
+use pendzl::traits::{Balance, StorageFieldGetter, Timestamp};
 
 use super::internal::InternalIncome;
 use super::storage::LendingPoolStorage;
@@ -38,49 +47,23 @@ pub trait LendingPoolManageImpl:
         &mut self,
         price_feed_provider: AccountId,
     ) -> Result<(), LendingPoolError> {
-        let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
-
-        self.data::<LendingPoolStorage>()
-            .account_for_price_feed_provider_change(&price_feed_provider);
-        ink::env::emit_event::<DefaultEnvironment, PriceFeedProviderChanged>(
-            PriceFeedProviderChanged {
-                price_feed_provider,
-            },
-        );
-        Ok(())
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_price_feed_provider(price_feed_provider)
     }
     fn set_fee_reduction_provider(
         &mut self,
         fee_reduction_provider: AccountId,
     ) -> Result<(), LendingPoolError> {
-        let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
-
-        self.data::<LendingPoolStorage>()
-            .account_for_fee_reduction_provider_change(&fee_reduction_provider);
-        ink::env::emit_event::<DefaultEnvironment, FeeReductionChanged>(
-            FeeReductionChanged {
-                fee_reduction_provider,
-            },
-        );
-        Ok(())
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_fee_reduction_provider(fee_reduction_provider)
     }
 
     fn set_flash_loan_fee_e6(
         &mut self,
         flash_loan_fee_e6: u128,
     ) -> Result<(), LendingPoolError> {
-        let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
-
-        self.data::<LendingPoolStorage>()
-            .flash_loan_fee_e6
-            .set(&flash_loan_fee_e6);
-        ink::env::emit_event::<DefaultEnvironment, FlashLoanFeeChanged>(
-            FlashLoanFeeChanged { flash_loan_fee_e6 },
-        );
-        Ok(())
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_flash_loan_fee_e6(flash_loan_fee_e6)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -102,12 +85,9 @@ pub trait LendingPoolManageImpl:
 
         let timestamp = Self::env().block_timestamp();
 
-        if reserve_fees.deposit_fee_e6 > 1_000_000 {
-            return Err(LendingPoolError::DepositFeeTooHigh);
-        }
-
         let interest_rate_model = {
             if let Some(params) = interest_rate_model_params {
+                params.validate()?;
                 let model = InterestRateModel::new(params, timestamp);
                 Some(model)
             } else {
@@ -206,6 +186,56 @@ pub trait LendingPoolManageImpl:
         );
         Ok(())
     }
+    fn register_stable_debt_token(
+        &mut self,
+        asset: AccountId,
+        s_token_code_hash: [u8; 32],
+        name: String,
+        symbol: String,
+        decimals: u8,
+    ) -> Result<(), LendingPoolError> {
+        let caller = Self::env().caller();
+        self._ensure_has_role(ASSET_LISTING_ADMIN, Some(caller))?;
+
+        let asset_id = self.data::<LendingPoolStorage>().asset_id(&asset)?;
+        if self
+            .data::<LendingPoolStorage>()
+            .find_interest_rate_model(asset_id)
+            .is_none()
+        {
+            return Err(LendingPoolError::AssetIsProtocolStablecoin);
+        }
+
+        let mut abacus_tokens = self
+            .data::<LendingPoolStorage>()
+            .find_reserve_abacus_tokens(&asset)
+            .unwrap(); // asset is registered => abacus tokens are set
+        if abacus_tokens.s_token_address.is_some() {
+            return Err(LendingPoolError::AlreadyRegistered);
+        }
+
+        let s_token_address = self._instantiate_s_token_contract(
+            &s_token_code_hash,
+            &asset,
+            name,
+            symbol,
+            decimals,
+        );
+        abacus_tokens.s_token_address = Some(s_token_address);
+
+        self.data::<LendingPoolStorage>()
+            .account_for_set_abacus_tokens(&asset, &abacus_tokens)?;
+
+        ink::env::emit_event::<DefaultEnvironment, StableDebtTokenRegistered>(
+            StableDebtTokenRegistered {
+                asset,
+                s_token_code_hash,
+                s_token_address,
+            },
+        );
+        Ok(())
+    }
+
     fn set_reserve_is_active(
         &mut self,
         asset: AccountId,
@@ -228,163 +258,200 @@ pub trait LendingPoolManageImpl:
         &mut self,
         asset: AccountId,
         new_is_frozen: bool,
+        reason_code: u32,
     ) -> Result<(), LendingPoolError> {
         let caller = Self::env().caller();
         self._ensure_has_role(EMERGENCY_ADMIN, Some(caller))?;
 
+        let timestamp = Self::env().block_timestamp();
         self.data::<LendingPoolStorage>()
-            .account_for_changing_is_frozen(&asset, new_is_frozen)?;
+            .account_for_changing_is_frozen(
+                &asset,
+                new_is_frozen,
+                reason_code,
+                &timestamp,
+            )?;
         ink::env::emit_event::<DefaultEnvironment, ReserveFrozen>(
             ReserveFrozen {
                 asset,
                 frozen: new_is_frozen,
+                reason_code,
             },
         );
         Ok(())
     }
 
-    fn set_reserve_restrictions(
+    fn set_global_action_paused(
         &mut self,
-        asset: AccountId,
-        reserve_restrictions: ReserveRestrictions,
+        action: PausableAction,
+        paused: bool,
     ) -> Result<(), LendingPoolError> {
         let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
+        self._ensure_has_role(EMERGENCY_ADMIN, Some(caller))?;
 
         self.data::<LendingPoolStorage>()
-            .account_for_reserve_restricitions_change(
-                &asset,
-                &reserve_restrictions,
-            )?;
-
-        ink::env::emit_event::<DefaultEnvironment, ReserveRestrictionsChanged>(
-            ReserveRestrictionsChanged {
-                asset,
-                reserve_restrictions,
-            },
+            .account_for_global_action_pause_change(action, paused)?;
+        ink::env::emit_event::<DefaultEnvironment, GlobalActionPauseChanged>(
+            GlobalActionPauseChanged { action, paused },
         );
-
         Ok(())
     }
 
-    fn set_interest_rate_model(
+    fn set_reserve_action_paused(
         &mut self,
         asset: AccountId,
-        interest_rate_model_params: InterestRateModelParams,
+        action: PausableAction,
+        paused: bool,
     ) -> Result<(), LendingPoolError> {
         let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
-
-        let timestamp = Self::env().block_timestamp();
-
-        let interest_rate_model =
-            InterestRateModel::new(interest_rate_model_params, timestamp);
+        self._ensure_has_role(EMERGENCY_ADMIN, Some(caller))?;
 
         self.data::<LendingPoolStorage>()
-            .account_for_interest_rate_model_change(
-                &asset,
-                &interest_rate_model,
-                &timestamp,
-            )?;
-
-        ink::env::emit_event::<
-            DefaultEnvironment,
-            ReserveInterestRateModelChanged,
-        >(ReserveInterestRateModelChanged {
-            asset,
-            interest_rate_model_params,
-        });
+            .account_for_reserve_action_pause_change(&asset, action, paused)?;
+        ink::env::emit_event::<DefaultEnvironment, ReserveActionPauseChanged>(
+            ReserveActionPauseChanged {
+                asset,
+                action,
+                paused,
+            },
+        );
         Ok(())
     }
 
-    fn set_reserve_fees(
+    fn start_reserve_delisting(
         &mut self,
         asset: AccountId,
-        reserve_fees: SetReserveFeesArgs,
+        ramp_duration: Timestamp,
     ) -> Result<(), LendingPoolError> {
         let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
-
-        if reserve_fees.deposit_fee_e6 > 1_000_000 {
-            return Err(LendingPoolError::DepositFeeTooHigh);
-        }
+        self._ensure_has_role(ASSET_LISTING_ADMIN, Some(caller))?;
 
+        let timestamp = Self::env().block_timestamp();
         self.data::<LendingPoolStorage>()
-            .account_for_reserve_fees_change(
-                &asset,
-                &ReserveFees::new(
-                    reserve_fees.deposit_fee_e6,
-                    reserve_fees.debt_fee_e6,
-                ),
-            )?;
-        ink::env::emit_event::<DefaultEnvironment, ReserveFeesChanged>(
-            ReserveFeesChanged {
+            .account_for_start_delisting(&asset, &ramp_duration, &timestamp)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ReserveDelistingStarted>(
+            ReserveDelistingStarted {
                 asset,
-                reserve_fees,
+                ramp_duration,
             },
         );
         Ok(())
     }
 
-    fn set_stablecoin_debt_rate_e18(
+    fn close_reserve(
         &mut self,
         asset: AccountId,
-        debt_rate_e18: u64,
     ) -> Result<(), LendingPoolError> {
         let caller = Self::env().caller();
-        self._ensure_has_role(STABLECOIN_RATE_ADMIN, Some(caller))?;
+        self._ensure_has_role(ASSET_LISTING_ADMIN, Some(caller))?;
 
+        let timestamp = Self::env().block_timestamp();
         self.data::<LendingPoolStorage>()
-            .account_for_stablecoin_debt_rate_e18_change(
-                &asset,
-                &debt_rate_e18,
-            )?;
-        ink::env::emit_event::<DefaultEnvironment, StablecoinDebtRateChanged>(
-            StablecoinDebtRateChanged {
-                asset,
-                debt_rate_e18,
-            },
-        );
+            .account_for_close_reserve(&asset, &timestamp)?;
 
+        ink::env::emit_event::<DefaultEnvironment, ReserveClosed>(
+            ReserveClosed { asset },
+        );
         Ok(())
     }
 
-    fn add_market_rule(
+    fn set_reserve_restrictions(
         &mut self,
-        market_rule: MarketRule,
+        asset: AccountId,
+        reserve_restrictions: ReserveRestrictions,
     ) -> Result<(), LendingPoolError> {
-        let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_restrictions(asset, reserve_restrictions)
+    }
 
-        for asset_rule in market_rule.iter().flatten() {
-            asset_rule.validate_new_rule(&None)?;
-        }
+    fn set_interest_rate_model(
+        &mut self,
+        asset: AccountId,
+        interest_rate_model_params: InterestRateModelParams,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_interest_rate_model(asset, interest_rate_model_params)
+    }
 
-        let market_rule_id = self
-            .data::<LendingPoolStorage>()
-            .account_for_add_market_rule(&market_rule);
+    fn set_reserve_fees(
+        &mut self,
+        asset: AccountId,
+        reserve_fees: SetReserveFeesArgs,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_fees(asset, reserve_fees)
+    }
 
-        let registerd_assets = self
-            .data::<LendingPoolStorage>()
-            .get_all_registered_assets();
+    fn set_reserve_accrual_mode(
+        &mut self,
+        asset: AccountId,
+        accrual_mode: InterestAccrualMode,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_accrual_mode(asset, accrual_mode)
+    }
 
-        for asset_id in 0..market_rule.len() {
-            if let Some(asset_rules) = market_rule[asset_id] {
-                ink::env::emit_event::<DefaultEnvironment, AssetRulesChanged>(
-                    AssetRulesChanged {
-                        market_rule_id,
-                        asset: registerd_assets[asset_id],
-                        collateral_coefficient_e6: asset_rules
-                            .collateral_coefficient_e6,
-                        borrow_coefficient_e6: asset_rules
-                            .borrow_coefficient_e6,
-                        penalty_e6: asset_rules.penalty_e6,
-                    },
-                );
-            }
-        }
+    fn set_reserve_tw_size(
+        &mut self,
+        asset: AccountId,
+        size: u32,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_tw_size(asset, size)
+    }
 
-        Ok(())
+    fn set_reserve_isolation(
+        &mut self,
+        asset: AccountId,
+        reserve_isolation: ReserveIsolation,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_isolation(asset, reserve_isolation)
+    }
+
+    fn set_reserve_account_limits(
+        &mut self,
+        asset: AccountId,
+        reserve_account_limits: ReserveAccountLimits,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_account_limits(asset, reserve_account_limits)
+    }
+
+    fn set_reserve_close_factor(
+        &mut self,
+        asset: AccountId,
+        close_factor: Option<CloseFactor>,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_reserve_close_factor(asset, close_factor)
+    }
+
+    fn set_stable_rate_model(
+        &mut self,
+        asset: AccountId,
+        stable_rate_model: Option<StableRateModel>,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_stable_rate_model(asset, stable_rate_model)
+    }
+
+    fn set_stablecoin_debt_rate_e18(
+        &mut self,
+        asset: AccountId,
+        debt_rate_e18: u64,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(STABLECOIN_RATE_ADMIN)?;
+        self._set_stablecoin_debt_rate_e18(asset, debt_rate_e18)
+    }
+
+    fn add_market_rule(
+        &mut self,
+        market_rule: MarketRule,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._add_market_rule(market_rule)
     }
 
     fn modify_asset_rule(
@@ -393,28 +460,20 @@ pub trait LendingPoolManageImpl:
         asset: AccountId,
         asset_rules: AssetRules,
     ) -> Result<(), LendingPoolError> {
-        let caller = Self::env().caller();
-        self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
-
-        self.data::<LendingPoolStorage>()
-            .account_for_asset_rule_change(
-                &market_rule_id,
-                &asset,
-                &asset_rules,
-            )?;
-
-        ink::env::emit_event::<DefaultEnvironment, AssetRulesChanged>(
-            AssetRulesChanged {
-                market_rule_id,
-                asset,
-                collateral_coefficient_e6: asset_rules
-                    .collateral_coefficient_e6,
-                borrow_coefficient_e6: asset_rules.borrow_coefficient_e6,
-                penalty_e6: asset_rules.penalty_e6,
-            },
-        );
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._modify_asset_rule(market_rule_id, asset, asset_rules)
+    }
 
-        Ok(())
+    fn set_market_rule_liquidation_auction(
+        &mut self,
+        market_rule_id: u32,
+        auction_params: Option<LiquidationAuctionParams>,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(PARAMETERS_ADMIN)?;
+        self._set_market_rule_liquidation_auction(
+            market_rule_id,
+            auction_params,
+        )
     }
 
     fn take_protocol_income(
@@ -448,6 +507,18 @@ pub trait LendingPoolManageImpl:
 
         Ok(assets_and_amounts)
     }
+
+    /// ensures that the caller has the `role` and that the timelock is disabled - otherwise the parameter change must be queued.
+    fn _ensure_can_manage_directly(
+        &self,
+        role: RoleType,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_has_role(role, Some(Self::env().caller()))?;
+        if self.data::<LendingPoolStorage>().is_timelock_enabled() {
+            return Err(LendingPoolError::TimelockActive);
+        }
+        Ok(())
+    }
 }
 
 pub trait ManageInternal: StorageFieldGetter<LendingPoolStorage> {
@@ -503,21 +574,47 @@ pub trait ManageInternal: StorageFieldGetter<LendingPoolStorage> {
         )
     }
 
-    fn _instantiate_abacus_token(
+    fn _instantiate_s_token_contract(
         &self,
-        abacus_token_code_hash: &[u8; 32],
-        lending_pool: &AccountId,
+        s_token_code_hash: &[u8; 32],
         underlying_asset: &AccountId,
         name: String,
         symbol: String,
         decimals: u8,
     ) -> AccountId {
-        let create_params = ink::env::call::build_create::<DummyRef>()
-            .instantiate_v1()
-            .code_hash(Hash::from(*abacus_token_code_hash))
-            .gas_limit(10_000_000_000)
-            .endowment(0)
-            .exec_input(
+        let lending_pool: AccountId = Self::env().account_id();
+
+        let mut token_name = "Abax Stable Debt ".to_string();
+        token_name.push_str(&name);
+
+        let mut token_symbol = "s".to_string();
+        token_symbol.push_str(&symbol);
+
+        self._instantiate_abacus_token(
+            s_token_code_hash,
+            &lending_pool,
+            underlying_asset,
+            token_name,
+            token_symbol,
+            decimals,
+        )
+    }
+
+    fn _instantiate_abacus_token(
+        &self,
+        abacus_token_code_hash: &[u8; 32],
+        lending_pool: &AccountId,
+        underlying_asset: &AccountId,
+        name: String,
+        symbol: String,
+        decimals: u8,
+    ) -> AccountId {
+        let create_params = ink::env::call::build_create::<DummyRef>()
+            .instantiate_v1()
+            .code_hash(Hash::from(*abacus_token_code_hash))
+            .gas_limit(10_000_000_000)
+            .endowment(0)
+            .exec_input(
                 ExecutionInput::new(ink::env::call::Selector::new(
                     ink::selector_bytes!("new"),
                 ))
@@ -538,4 +635,430 @@ pub trait ManageInternal: StorageFieldGetter<LendingPoolStorage> {
             .unwrap_or_else(|error| panic!("LangError: {:?}", error));
         contract.to_account_id()
     }
+
+    fn _set_price_feed_provider(
+        &mut self,
+        price_feed_provider: AccountId,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_price_feed_provider_change(&price_feed_provider);
+        ink::env::emit_event::<DefaultEnvironment, PriceFeedProviderChanged>(
+            PriceFeedProviderChanged {
+                price_feed_provider,
+            },
+        );
+        Ok(())
+    }
+
+    fn _set_fee_reduction_provider(
+        &mut self,
+        fee_reduction_provider: AccountId,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_fee_reduction_provider_change(&fee_reduction_provider);
+        ink::env::emit_event::<DefaultEnvironment, FeeReductionChanged>(
+            FeeReductionChanged {
+                fee_reduction_provider,
+            },
+        );
+        Ok(())
+    }
+
+    fn _set_flash_loan_fee_e6(
+        &mut self,
+        flash_loan_fee_e6: u128,
+    ) -> Result<(), LendingPoolError> {
+        validate_flash_loan_fee_e6(flash_loan_fee_e6)?;
+        self.data::<LendingPoolStorage>()
+            .flash_loan_fee_e6
+            .set(&flash_loan_fee_e6);
+        ink::env::emit_event::<DefaultEnvironment, FlashLoanFeeChanged>(
+            FlashLoanFeeChanged { flash_loan_fee_e6 },
+        );
+        Ok(())
+    }
+
+    fn _set_reserve_restrictions(
+        &mut self,
+        asset: AccountId,
+        reserve_restrictions: ReserveRestrictions,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_reserve_restricitions_change(
+                &asset,
+                &reserve_restrictions,
+            )?;
+
+        ink::env::emit_event::<DefaultEnvironment, ReserveRestrictionsChanged>(
+            ReserveRestrictionsChanged {
+                asset,
+                reserve_restrictions,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn _set_interest_rate_model(
+        &mut self,
+        asset: AccountId,
+        interest_rate_model_params: InterestRateModelParams,
+    ) -> Result<(), LendingPoolError> {
+        interest_rate_model_params.validate()?;
+        let timestamp = Self::env().block_timestamp();
+
+        let interest_rate_model =
+            InterestRateModel::new(interest_rate_model_params, timestamp);
+
+        self.data::<LendingPoolStorage>()
+            .account_for_interest_rate_model_change(
+                &asset,
+                &interest_rate_model,
+                &timestamp,
+            )?;
+
+        ink::env::emit_event::<
+            DefaultEnvironment,
+            ReserveInterestRateModelChanged,
+        >(ReserveInterestRateModelChanged {
+            asset,
+            interest_rate_model_params,
+        });
+        Ok(())
+    }
+
+    fn _set_reserve_fees(
+        &mut self,
+        asset: AccountId,
+        reserve_fees: SetReserveFeesArgs,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_reserve_fees_change(
+                &asset,
+                &ReserveFees::new(
+                    reserve_fees.deposit_fee_e6,
+                    reserve_fees.debt_fee_e6,
+                ),
+            )?;
+        ink::env::emit_event::<DefaultEnvironment, ReserveFeesChanged>(
+            ReserveFeesChanged {
+                asset,
+                reserve_fees,
+            },
+        );
+        Ok(())
+    }
+
+    fn _set_reserve_accrual_mode(
+        &mut self,
+        asset: AccountId,
+        accrual_mode: InterestAccrualMode,
+    ) -> Result<(), LendingPoolError> {
+        let timestamp = Self::env().block_timestamp();
+        self.data::<LendingPoolStorage>()
+            .account_for_accrual_mode_change(
+                &asset,
+                &accrual_mode,
+                &timestamp,
+            )?;
+        ink::env::emit_event::<DefaultEnvironment, ReserveAccrualModeChanged>(
+            ReserveAccrualModeChanged {
+                asset,
+                accrual_mode,
+            },
+        );
+        Ok(())
+    }
+
+    fn _set_reserve_tw_size(
+        &mut self,
+        asset: AccountId,
+        size: u32,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_tw_ur_size_change(&asset, size)?;
+        ink::env::emit_event::<DefaultEnvironment, ReserveTwSizeChanged>(
+            ReserveTwSizeChanged { asset, size },
+        );
+        Ok(())
+    }
+
+    fn _set_reserve_isolation(
+        &mut self,
+        asset: AccountId,
+        reserve_isolation: ReserveIsolation,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_reserve_isolation_change(&asset, &reserve_isolation)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ReserveIsolationChanged>(
+            ReserveIsolationChanged {
+                asset,
+                reserve_isolation,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn _set_reserve_account_limits(
+        &mut self,
+        asset: AccountId,
+        reserve_account_limits: ReserveAccountLimits,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_reserve_account_limits_change(
+                &asset,
+                &reserve_account_limits,
+            )?;
+
+        ink::env::emit_event::<DefaultEnvironment, ReserveAccountLimitsChanged>(
+            ReserveAccountLimitsChanged {
+                asset,
+                reserve_account_limits,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn _set_reserve_close_factor(
+        &mut self,
+        asset: AccountId,
+        close_factor: Option<CloseFactor>,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_close_factor_change(&asset, &close_factor)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ReserveCloseFactorChanged>(
+            ReserveCloseFactorChanged {
+                asset,
+                close_factor,
+            },
+        );
+        Ok(())
+    }
+
+    fn _set_stable_rate_model(
+        &mut self,
+        asset: AccountId,
+        stable_rate_model: Option<StableRateModel>,
+    ) -> Result<(), LendingPoolError> {
+        if stable_rate_model.is_some()
+            && self
+                .data::<LendingPoolStorage>()
+                .find_reserve_abacus_tokens(&asset)
+                .ok_or(LendingPoolError::AssetNotRegistered)?
+                .s_token_address
+                .is_none()
+        {
+            return Err(LendingPoolError::StableDebtTokenNotRegistered);
+        }
+
+        self.data::<LendingPoolStorage>()
+            .account_for_stable_rate_model_change(&asset, &stable_rate_model)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ReserveStableRateModelChanged>(
+            ReserveStableRateModelChanged {
+                asset,
+                stable_rate_model,
+            },
+        );
+        Ok(())
+    }
+
+    fn _set_stablecoin_debt_rate_e18(
+        &mut self,
+        asset: AccountId,
+        debt_rate_e18: u64,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_stablecoin_debt_rate_e18_change(
+                &asset,
+                &debt_rate_e18,
+            )?;
+        ink::env::emit_event::<DefaultEnvironment, StablecoinDebtRateChanged>(
+            StablecoinDebtRateChanged {
+                asset,
+                debt_rate_e18,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn _add_market_rule(
+        &mut self,
+        market_rule: MarketRule,
+    ) -> Result<(), LendingPoolError> {
+        for asset_rule in market_rule.iter().flatten() {
+            asset_rule.validate_new_rule(&None)?;
+            asset_rule.validate_bounds()?;
+        }
+
+        let market_rule_id = self
+            .data::<LendingPoolStorage>()
+            .account_for_add_market_rule(&market_rule);
+
+        let registerd_assets = self
+            .data::<LendingPoolStorage>()
+            .get_all_registered_assets();
+
+        for asset_id in 0..market_rule.len() {
+            if let Some(asset_rules) = market_rule[asset_id] {
+                ink::env::emit_event::<DefaultEnvironment, AssetRulesChanged>(
+                    AssetRulesChanged {
+                        market_rule_id,
+                        asset: registerd_assets[asset_id],
+                        collateral_coefficient_e6: asset_rules
+                            .collateral_coefficient_e6,
+                        borrow_coefficient_e6: asset_rules
+                            .borrow_coefficient_e6,
+                        penalty_e6: asset_rules.penalty_e6,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    fn _modify_asset_rule(
+        &mut self,
+        market_rule_id: u32,
+        asset: AccountId,
+        asset_rules: AssetRules,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_asset_rule_change(
+                &market_rule_id,
+                &asset,
+                &asset_rules,
+            )?;
+
+        ink::env::emit_event::<DefaultEnvironment, AssetRulesChanged>(
+            AssetRulesChanged {
+                market_rule_id,
+                asset,
+                collateral_coefficient_e6: asset_rules
+                    .collateral_coefficient_e6,
+                borrow_coefficient_e6: asset_rules.borrow_coefficient_e6,
+                penalty_e6: asset_rules.penalty_e6,
+            },
+        );
+
+        Ok(())
+    }
+
+    fn _set_market_rule_liquidation_auction(
+        &mut self,
+        market_rule_id: u32,
+        auction_params: Option<LiquidationAuctionParams>,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .account_for_liquidation_auction_params_change(
+                &market_rule_id,
+                &auction_params,
+            )?;
+        ink::env::emit_event::<
+            DefaultEnvironment,
+            MarketRuleLiquidationAuctionChanged,
+        >(MarketRuleLiquidationAuctionChanged {
+            market_rule_id,
+            auction_params,
+        });
+        Ok(())
+    }
+
+    fn _set_timelock_delay(
+        &mut self,
+        timelock_delay: Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .timelock_delay
+            .set(&timelock_delay);
+        ink::env::emit_event::<DefaultEnvironment, TimelockDelayChanged>(
+            TimelockDelayChanged { timelock_delay },
+        );
+        Ok(())
+    }
+
+    /// applies the parameter change (queued in the timelock) without checking the caller's role.
+    fn _apply_manage_action(
+        &mut self,
+        action: ManageAction,
+    ) -> Result<(), LendingPoolError> {
+        match action {
+            ManageAction::SetPriceFeedProvider {
+                price_feed_provider,
+            } => self._set_price_feed_provider(price_feed_provider),
+            ManageAction::SetFeeReductionProvider {
+                fee_reduction_provider,
+            } => self._set_fee_reduction_provider(fee_reduction_provider),
+            ManageAction::SetFlashLoanFeeE6 { flash_loan_fee_e6 } => {
+                self._set_flash_loan_fee_e6(flash_loan_fee_e6)
+            }
+            ManageAction::SetReserveRestrictions {
+                asset,
+                reserve_restrictions,
+            } => self._set_reserve_restrictions(asset, reserve_restrictions),
+            ManageAction::SetInterestRateModel {
+                asset,
+                interest_rate_model_params,
+            } => {
+                self._set_interest_rate_model(asset, interest_rate_model_params)
+            }
+            ManageAction::SetReserveFees {
+                asset,
+                reserve_fees,
+            } => self._set_reserve_fees(asset, reserve_fees),
+            ManageAction::SetReserveAccrualMode {
+                asset,
+                accrual_mode,
+            } => self._set_reserve_accrual_mode(asset, accrual_mode),
+            ManageAction::SetReserveTwSize { asset, size } => {
+                self._set_reserve_tw_size(asset, size)
+            }
+            ManageAction::SetReserveIsolation {
+                asset,
+                reserve_isolation,
+            } => self._set_reserve_isolation(asset, reserve_isolation),
+            ManageAction::SetReserveAccountLimits {
+                asset,
+                reserve_account_limits,
+            } => {
+                self._set_reserve_account_limits(asset, reserve_account_limits)
+            }
+            ManageAction::SetReserveCloseFactor {
+                asset,
+                close_factor,
+            } => self._set_reserve_close_factor(asset, close_factor),
+            ManageAction::SetStableRateModel {
+                asset,
+                stable_rate_model,
+            } => self._set_stable_rate_model(asset, stable_rate_model),
+            ManageAction::SetStablecoinDebtRateE18 {
+                asset,
+                debt_rate_e18,
+            } => self._set_stablecoin_debt_rate_e18(asset, debt_rate_e18),
+            ManageAction::AddMarketRule { market_rule } => {
+                self._add_market_rule(market_rule)
+            }
+            ManageAction::ModifyAssetRule {
+                market_rule_id,
+                asset,
+                asset_rules,
+            } => self._modify_asset_rule(market_rule_id, asset, asset_rules),
+            ManageAction::SetMarketRuleLiquidationAuction {
+                market_rule_id,
+                auction_params,
+            } => self._set_market_rule_liquidation_auction(
+                market_rule_id,
+                auction_params,
+            ),
+            ManageAction::SetTimelockDelay { timelock_delay } => {
+                self._set_timelock_delay(timelock_delay)
+            }
+        }
+    }
 }
@@ -8,7 +8,9 @@ mod liquidate;
 mod maintain;
 mod manage;
 mod multi_op;
+mod s_token_interface;
 mod storage;
+mod timelock;
 mod v_token_interface;
 mod view;
 
@@ -21,6 +23,8 @@ pub use liquidate::*;
 pub use maintain::*;
 pub use manage::*;
 pub use multi_op::*;
+pub use s_token_interface::*;
 pub use storage::*;
+pub use timelock::*;
 pub use v_token_interface::*;
 pub use view::*;
@@ -1,6 +1,6 @@
 // SPDX-License-Identifier: BUSL-1.1
 use crate::lending_pool::{
-    events::{Borrow, Deposit, Repay, Withdraw},
+    events::{Borrow, BorrowStable, Deposit, Repay, RepayStable, Withdraw},
     LendingPoolError,
 };
 use abax_library::structs::{Action, Operation, ReserveAbacusTokens};
@@ -27,6 +27,8 @@ pub trait LendingPoolMultiOpImpl:
         on_behalf_of: AccountId,
         _data: Vec<u8>,
     ) -> Result<(), LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .ensure_actions_not_paused(&op)?;
         let mut actions = op.clone();
         let act_slice = actions.as_mut_slice();
         let res = self
@@ -53,8 +55,7 @@ pub trait LendingPoolMultiOpImpl:
                 .or_else(|| {
                     let present = self
                         .data::<LendingPoolStorage>()
-                        .reserve_abacus_tokens
-                        .get(asset)
+                        .find_reserve_abacus_tokens(&asset)
                         .unwrap();
                     abacus_tokens.insert(asset, &present);
                     Some(present)
@@ -167,6 +168,59 @@ pub trait LendingPoolMultiOpImpl:
                         amount,
                     });
                 }
+                Operation::BorrowStable => {
+                    self._transfer_out(&asset, &caller, &amount)?;
+                    // STOKEN
+                    _emit_abacus_token_transfer_event_and_decrease_allowance(
+                        &abacus_tokens.s_token_address.ok_or(
+                            LendingPoolError::StableDebtTokenNotRegistered,
+                        )?,
+                        &on_behalf_of,
+                        (account_accumulated_debt_interest
+                            .checked_add(amount)
+                            .ok_or(MathError::Overflow)?)
+                            as i128,
+                        &(caller),
+                        amount,
+                    )?;
+                    let asset_id =
+                        self.data::<LendingPoolStorage>().asset_id(&asset)?;
+                    let (account_reserve_data, _) = self
+                        .data::<LendingPoolStorage>()
+                        .get_account_reserve_data(asset_id, &on_behalf_of);
+                    ink::env::emit_event::<DefaultEnvironment, BorrowStable>(
+                        BorrowStable {
+                            asset,
+                            caller,
+                            on_behalf_of,
+                            amount,
+                            stable_rate_e18: account_reserve_data
+                                .stable_rate_e18,
+                        },
+                    );
+                }
+                Operation::RepayStable => {
+                    self._transfer_in(&asset, &caller, &amount)?;
+                    // STOKEN
+                    _emit_abacus_token_transfer_event(
+                        &abacus_tokens.s_token_address.ok_or(
+                            LendingPoolError::StableDebtTokenNotRegistered,
+                        )?,
+                        &on_behalf_of,
+                        (*account_accumulated_debt_interest as i128)
+                            .overflowing_sub(amount as i128)
+                            .0,
+                    )?;
+
+                    ink::env::emit_event::<DefaultEnvironment, RepayStable>(
+                        RepayStable {
+                            asset,
+                            caller,
+                            on_behalf_of,
+                            amount,
+                        },
+                    );
+                }
             }
         }
 
@@ -0,0 +1,25 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::traits::{AccountId, Balance, StorageFieldGetter};
+
+use super::storage::LendingPoolStorage;
+
+pub trait LendingPoolSTokenInterfaceImpl:
+    StorageFieldGetter<LendingPoolStorage>
+{
+    fn total_stable_debt_of(&self, underlying_asset: AccountId) -> Balance {
+        self.data::<LendingPoolStorage>()
+            .total_stable_debt_of(&underlying_asset)
+            .unwrap()
+    }
+
+    fn account_stable_debt_of(
+        &self,
+        underlying_asset: AccountId,
+        account: AccountId,
+    ) -> Balance {
+        let timestamp = Self::env().block_timestamp();
+        self.data::<LendingPoolStorage>()
+            .account_stable_debt_of(&underlying_asset, &account, &timestamp)
+            .unwrap()
+    }
+}
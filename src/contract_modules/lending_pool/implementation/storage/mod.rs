@@ -1,26 +1,42 @@
 // SPDX-License-Identifier: BUSL-1.1
 use crate::{
     fee_reduction::{FeeReduction, FeeReductionRef},
-    lending_pool::{DecimalMultiplier, LendingPoolError, MarketRule, RuleId},
+    lending_pool::{
+        DecimalMultiplier, LendingPoolError, ManageAction, MarketRule,
+        QueuedManageAction, RuleId, MANAGE_ACTION_GRACE_PERIOD,
+    },
     price_feed::{PriceFeed, PriceFeedRef},
 };
 use abax_library::{
     math::{
         calculate_amount_to_take, calculate_asset_amount_value_e8,
-        e8_mul_e6_to_e6_rdown,
+        e8_mul_e6_to_e6_rdown, E10_U128, E6_U128, E8_U128,
     },
     structs::{
-        AccountConfig, AccountReserveData, Action, AssetId, AssetRules,
-        FeeReductions, InterestRateModel, Operation, ReserveAbacusTokens,
-        ReserveData, ReserveFees, ReserveIndexesAndFees, ReserveRestrictions,
-        TwEntry, TwIndex,
+        legacy::{
+            account_reserve_datas_from_v1, AccountConfigV1,
+            AccountReserveDataV1, InterestRateModelV1, ReserveAbacusTokensV1,
+            ReserveDataV1, ReserveIndexesAndFeesV1, TwIndexV1,
+        },
+        AccountConfig, AccountHealth, AccountReserveData, Action, ActionsPause,
+        AssetId, AssetRules, AssetsBitmap, CloseFactor, DelistingStage,
+        FeeReductions, InterestAccrualMode, InterestRateModel,
+        LiquidationAuction, LiquidationAuctionError, LiquidationAuctionParams,
+        Operation, PausableAction, ReserveAbacusTokens, ReserveAccountLimits,
+        ReserveData, ReserveDelisting, ReserveDelistingError, ReserveFees,
+        ReserveFreeze, ReserveIndexesAndFees, ReserveIsolation,
+        ReserveRestrictions, StableRateModel, TwEntry, TwIndex,
+        DEFAULT_TW_INDEX_SIZE,
     },
 };
 use ink::codegen::TraitCallBuilder;
 use ink::storage::Mapping;
 use ink::{env::DefaultEnvironment, prelude::vec, prelude::vec::Vec};
 use pendzl::{
-    math::errors::MathError,
+    math::{
+        errors::MathError,
+        operations::{mul_div, Rounding},
+    },
     traits::{AccountId, Balance, Timestamp},
 };
 
@@ -30,10 +46,42 @@ pub enum ReserveAction<'a> {
     Withdraw(u32, &'a mut Balance, bool),
     Borrow(u32, &'a Balance),
     Repay(u32, &'a mut Balance),
+    BorrowStable(u32, &'a Balance),
+    RepayStable(u32, &'a mut Balance),
     DepositTransfer(u32, u32, &'a mut Balance, bool),
     DebtTransfer(u32, u32, &'a mut Balance, bool),
 }
 
+/// Result of seizing the deposit of an account with bad debt in one reserve.
+#[derive(Debug)]
+pub struct DepositSeizure {
+    pub asset_id: AssetId,
+    /// seized deposit (with accumulated interest) moved to the reserve's safety fund.
+    pub amount: Balance,
+    /// (accumulated_deposit_interest, accumulated_debt_interest) of the account.
+    pub interests: (Balance, Balance),
+}
+
+/// Result of writing off the debt of an account in one reserve.
+#[derive(Debug)]
+pub struct BadDebtWriteOff {
+    pub asset_id: AssetId,
+    /// written off debt (with accumulated interest).
+    pub amount: Balance,
+    /// part of `amount` covered by the protocol's earned fee.
+    pub covered_by_earned_fee: Balance,
+    /// part of `amount` covered by the reserve's safety fund.
+    pub covered_by_safety_fund: Balance,
+    /// part of `amount` socialized among depositors.
+    pub socialized: Balance,
+    /// part of `amount` that was the stable debt.
+    pub stable_amount: Balance,
+    /// (accumulated_deposit_interest, accumulated_debt_interest) of the account.
+    pub interests: (Balance, Balance),
+    /// accumulated_stable_debt_interest of the account.
+    pub stable_interest: Balance,
+}
+
 #[derive(Default, Debug)]
 #[pendzl::storage_item]
 pub struct LendingPoolStorage {
@@ -50,27 +98,90 @@ pub struct LendingPoolStorage {
     #[lazy]
     pub next_rule_id: RuleId,
     pub market_rules: Mapping<RuleId, MarketRule>,
+    /// If set, accounts using the market rule are liquidated in the Dutch-auction mode.
+    pub market_rule_liquidation_auctions:
+        Mapping<RuleId, LiquidationAuctionParams>,
 
-    pub reserve_abacus_tokens: Mapping<AccountId, ReserveAbacusTokens>,
+    /// `ReserveAbacusTokens` in the layout before the stable borrowing. Read if there is no entry in `reserve_abacus_tokens_v2`. Moved by `migrate_reserve`.
+    pub reserve_abacus_tokens: Mapping<AccountId, ReserveAbacusTokensV1>,
+    pub reserve_abacus_tokens_v2: Mapping<AccountId, ReserveAbacusTokens>,
 
     pub reserve_restrictions: Mapping<AssetId, ReserveRestrictions>,
-    pub reserve_indexes_and_fees: Mapping<AssetId, ReserveIndexesAndFees>,
+    /// `ReserveIndexesAndFees` in the layout before the interest accrual modes. Read if there is no entry in `reserve_indexes_and_fees_v2`. Moved by `migrate_reserve`.
+    pub reserve_indexes_and_fees: Mapping<AssetId, ReserveIndexesAndFeesV1>,
+    pub reserve_indexes_and_fees_v2: Mapping<AssetId, ReserveIndexesAndFees>,
     pub reserve_decimal_multiplier: Mapping<AssetId, DecimalMultiplier>,
-    pub reserve_datas: Mapping<AssetId, ReserveData>,
-
-    pub tw_ur_indexes: Mapping<AssetId, TwIndex>,
+    /// `ReserveData` in the layout before the stable borrowing. Read if there is no entry in `reserve_datas_v2`. Moved by `migrate_reserve`.
+    pub reserve_datas: Mapping<AssetId, ReserveDataV1>,
+    pub reserve_datas_v2: Mapping<AssetId, ReserveData>,
+    /// If set, limits the debt that can be repaid in a single liquidation. The key is the id of the repaid asset.
+    pub reserve_close_factors: Mapping<AssetId, CloseFactor>,
+    /// Underlying tokens dedicated to cover bad debt of a reserve.
+    pub reserve_safety_funds: Mapping<AssetId, Balance>,
+    /// If set, the reserve's isolation mode parameters.
+    pub reserve_isolations: Mapping<AssetId, ReserveIsolation>,
+    /// If set, limits the deposit, the debt and the share in the total debt of a single account in the reserve.
+    pub reserve_account_limits: Mapping<AssetId, ReserveAccountLimits>,
+    /// Total debt (in USD, 10^8 precision, valued at the moment of borrowing or repaying) backed by the isolated collateral. The key is the id of the isolated collateral.
+    pub isolated_debts_e8: Mapping<AssetId, u128>,
+    /// Number of accounts (stored in `account_configs_v2`) that have the asset turned on as collateral.
+    /// Complete only once `accounts_migrated` is set.
+    pub reserve_collateral_users: Mapping<AssetId, u32>,
+    /// If set, the reserve is being delisted (or is closed).
+    pub reserve_delistings: Mapping<AssetId, ReserveDelisting>,
+    /// If set, the reserve is frozen. Records the reason and the time of freezing.
+    pub reserve_freezes: Mapping<AssetId, ReserveFreeze>,
+    /// Actions paused in the reserve (on top of the globally paused ones).
+    pub reserve_actions_pauses: Mapping<AssetId, ActionsPause>,
+
+    /// `TwIndex`es in the layout before the ring of TW entries was resizable. Read if there is no entry in `tw_ur_indexes_v2`. Moved by `migrate_reserve`.
+    pub tw_ur_indexes: Mapping<AssetId, TwIndexV1>,
+    pub tw_ur_indexes_v2: Mapping<AssetId, TwIndex>,
     pub tw_ur_entries: Mapping<(AssetId, u32), TwEntry>,
-    pub interest_rate_model: Mapping<AssetId, InterestRateModel>,
+    /// interest rate models in the layout before the interest rate strategies (adaptive only). Read if there is no entry in `interest_rate_model_v2`. Moved by `migrate_reserve`.
+    pub interest_rate_model: Mapping<AssetId, InterestRateModelV1>,
+    pub interest_rate_model_v2: Mapping<AssetId, InterestRateModel>,
+    /// If set, stable borrowing of the asset is enabled.
+    pub stable_rate_models: Mapping<AssetId, StableRateModel>,
 
     /// The AccountReserveData is stored in a vector, where the index is coresponding to the asset_id.
     #[allow(clippy::type_complexity)]
-    pub account_reserve_datas:
+    pub account_reserve_datas_v2:
         Mapping<AccountId, Vec<Option<AccountReserveData>>>,
-    pub account_configs: Mapping<AccountId, AccountConfig>,
+    pub account_configs_v2: Mapping<AccountId, AccountConfig>,
+    /// account reserve datas in the layout before the stable borrowing. Read if there is no entry in `account_reserve_datas_v2`. Moved by `migrate_account`.
+    #[allow(clippy::type_complexity)]
+    pub account_reserve_datas:
+        Mapping<AccountId, Vec<Option<AccountReserveDataV1>>>,
+    /// `AccountConfig`s in the layout before the stable borrowing and the isolation mode. Read if there is no entry in `account_configs_v2`. Moved by `migrate_account`.
+    pub account_configs: Mapping<AccountId, AccountConfigV1>,
+
+    pub liquidation_auctions: Mapping<AccountId, LiquidationAuction>,
+    /// accounts with active liquidation auctions, indexed by `LiquidationAuction::index`.
+    pub liquidation_auction_accounts: Mapping<u32, AccountId>,
+    #[lazy]
+    pub liquidation_auctions_count: u32,
 
     #[lazy]
     /// fee that must be paid while taking flash loan. 10^6 = 100%.
     pub flash_loan_fee_e6: u128,
+
+    #[lazy]
+    /// actions paused in all reserves.
+    pub global_actions_pause: ActionsPause,
+
+    #[lazy]
+    /// delay between queuing and execution of a parameter change. 0 means that the timelock is disabled.
+    pub timelock_delay: Timestamp,
+    #[lazy]
+    pub next_manage_action_id: u32,
+    /// accounts registered with counters below it were migrated by `migrate_accounts`.
+    #[lazy]
+    pub accounts_migration_counter: u128,
+    /// set once no account is left in the legacy layouts (or the pool was deployed without them).
+    #[lazy]
+    pub accounts_migrated: bool,
+    pub queued_manage_actions: Mapping<u32, QueuedManageAction>,
 }
 
 /// Holds references to reserve related data used during accounting for actions.
@@ -79,6 +190,9 @@ struct ReserveDataContext<'a> {
     reserve_data: &'a mut ReserveData,
     reserve_indexes_and_fees: &'a mut ReserveIndexesAndFees,
     reserve_restrictions: &'a ReserveRestrictions,
+    account_limits: ReserveAccountLimits,
+    stable_rate_model: Option<StableRateModel>,
+    delisting: Option<ReserveDelisting>,
 }
 
 /// Used to accumulate interests for each account.
@@ -128,6 +242,71 @@ impl LendingPoolStorage {
         let mut account_config = self.get_account_config(account);
         let fee_reductions = self.get_fee_reductions_of_account(account);
 
+        let (results, must_check_collateralization) = self
+            .account_for_actions_on_account_data(
+                &mut account_datas,
+                &mut account_config,
+                &fee_reductions,
+                actions,
+            )?;
+
+        // check if there is enought collatera
+        if must_check_collateralization {
+            self.ensure_collateralized_from_raw(
+                &account_datas,
+                &account_config,
+                &fee_reductions,
+            )?;
+        }
+
+        self.account_reserve_datas_v2
+            .insert(account, &account_datas);
+        self.store_account_config(account, &account_config);
+        Ok(results)
+    }
+
+    /// accounts for `actions` of the `account` like `account_for_account_actions` but neither checks collateralization nor stores the account's data.
+    /// Returns the account's data after the actions and its health.
+    ///
+    /// # Note
+    /// The reserves' data is modified - the caller must revert the state.
+    pub fn simulate_account_actions(
+        &mut self,
+        account: &AccountId,
+        actions: &mut [Action],
+    ) -> Result<
+        (Vec<Option<AccountReserveData>>, AccountHealth),
+        LendingPoolError,
+    > {
+        let mut account_datas = self.get_account_datas(account);
+        let mut account_config = self.get_account_config(account);
+        let fee_reductions = self.get_fee_reductions_of_account(account);
+
+        self.account_for_actions_on_account_data(
+            &mut account_datas,
+            &mut account_config,
+            &fee_reductions,
+            actions,
+        )?;
+
+        let account_health = self.calculate_account_health(
+            &account_datas,
+            &account_config,
+            &fee_reductions,
+            &self.get_assets_prices_e18()?,
+        )?;
+        Ok((account_datas, account_health))
+    }
+
+    /// accounts for `actions` on the account's data and config (without storing them).
+    /// Returns the accumulated interests for each action and whether the collateralization must be checked.
+    fn account_for_actions_on_account_data(
+        &mut self,
+        account_datas: &mut [Option<AccountReserveData>],
+        account_config: &mut AccountConfig,
+        fee_reductions: &FeeReductions,
+        actions: &mut [Action],
+    ) -> Result<(Vec<(u128, u128)>, bool), LendingPoolError> {
         let timestamp = ink::env::block_timestamp::<DefaultEnvironment>();
 
         let mut results: Vec<(u128, u128)> = Vec::new();
@@ -137,15 +316,15 @@ impl LendingPoolStorage {
         for action in actions.iter_mut() {
             let asset_id = self.asset_id(&action.args.asset)?;
             let account_data =
-                get_account_data_entry_mut(&mut account_datas, asset_id);
+                get_account_data_entry_mut(account_datas, asset_id);
 
             match action.op {
                 Operation::Deposit => {
                     let res = self.account_for_reserve_action(
                         asset_id,
                         &mut [account_data],
-                        &mut [&mut account_config],
-                        &[&fee_reductions],
+                        &mut [&mut *account_config],
+                        &[fee_reductions],
                         &mut [&mut ReserveAction::Deposit(
                             0,
                             &action.args.amount,
@@ -155,14 +334,14 @@ impl LendingPoolStorage {
                     results.push(*res.first().unwrap());
                 }
                 Operation::Withdraw => {
-                    if (account_config.collaterals >> asset_id) & 1 == 1 {
+                    if account_config.collaterals.contains(asset_id) {
                         must_check_collateralization = true;
                     }
                     let res = self.account_for_reserve_action(
                         asset_id,
                         &mut [account_data],
-                        &mut [&mut account_config],
-                        &[&fee_reductions],
+                        &mut [&mut *account_config],
+                        &[fee_reductions],
                         &mut [&mut ReserveAction::Withdraw(
                             0,
                             &mut action.args.amount,
@@ -177,8 +356,8 @@ impl LendingPoolStorage {
                     let res = self.account_for_reserve_action(
                         asset_id,
                         &mut [account_data],
-                        &mut [&mut account_config],
-                        &[&fee_reductions],
+                        &mut [&mut *account_config],
+                        &[fee_reductions],
                         &mut [&mut ReserveAction::Borrow(
                             0,
                             &action.args.amount,
@@ -186,13 +365,19 @@ impl LendingPoolStorage {
                         &timestamp,
                     )?;
                     results.push(*res.first().unwrap());
+                    self.account_for_isolated_debt_change(
+                        account_config,
+                        asset_id,
+                        &action.args.amount,
+                        true,
+                    )?;
                 }
                 Operation::Repay => {
                     let res = self.account_for_reserve_action(
                         asset_id,
                         &mut [account_data],
-                        &mut [&mut account_config],
-                        &[&fee_reductions],
+                        &mut [&mut *account_config],
+                        &[fee_reductions],
                         &mut [&mut ReserveAction::Repay(
                             0,
                             &mut action.args.amount,
@@ -200,22 +385,58 @@ impl LendingPoolStorage {
                         &timestamp,
                     )?;
                     results.push(*res.first().unwrap());
+                    self.account_for_isolated_debt_change(
+                        account_config,
+                        asset_id,
+                        &action.args.amount,
+                        false,
+                    )?;
+                }
+                Operation::BorrowStable => {
+                    must_check_collateralization = true;
+                    let res = self.account_for_reserve_action(
+                        asset_id,
+                        &mut [account_data],
+                        &mut [&mut *account_config],
+                        &[fee_reductions],
+                        &mut [&mut ReserveAction::BorrowStable(
+                            0,
+                            &action.args.amount,
+                        )],
+                        &timestamp,
+                    )?;
+                    results.push(*res.first().unwrap());
+                    self.account_for_isolated_debt_change(
+                        account_config,
+                        asset_id,
+                        &action.args.amount,
+                        true,
+                    )?;
+                }
+                Operation::RepayStable => {
+                    let res = self.account_for_reserve_action(
+                        asset_id,
+                        &mut [account_data],
+                        &mut [&mut *account_config],
+                        &[fee_reductions],
+                        &mut [&mut ReserveAction::RepayStable(
+                            0,
+                            &mut action.args.amount,
+                        )],
+                        &timestamp,
+                    )?;
+                    results.push(*res.first().unwrap());
+                    self.account_for_isolated_debt_change(
+                        account_config,
+                        asset_id,
+                        &action.args.amount,
+                        false,
+                    )?;
                 }
             }
         }
 
-        // check if there is enought collatera
-        if must_check_collateralization {
-            self.ensure_collateralized_from_raw(
-                &account_datas,
-                &account_config,
-                &fee_reductions,
-            )?;
-        }
-
-        self.account_reserve_datas.insert(account, &account_datas);
-        self.account_configs.insert(account, &account_config);
-        Ok(results)
+        Ok((results, must_check_collateralization))
     }
 
     /// accounts for one list of ReserveActions
@@ -246,16 +467,13 @@ impl LendingPoolStorage {
             self.get_reserve_indexes_and_fees(asset_id);
         let reserve_restrictions = self.get_reserve_restrictions(asset_id);
 
-        self.store_utilization_rate_snapshot(
+        self.update_reserve_indexes(
             asset_id,
             &reserve_data,
+            &mut reserve_indexes_and_fees,
             timestamp,
         )?;
 
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
-
         let mut interests_acc = InterestsAcc::new(accounts_data.len());
 
         let mut reserve_ctx = ReserveDataContext {
@@ -263,11 +481,17 @@ impl LendingPoolStorage {
             reserve_data: &mut reserve_data,
             reserve_indexes_and_fees: &mut reserve_indexes_and_fees,
             reserve_restrictions: &reserve_restrictions,
+            account_limits: self.get_reserve_account_limits(asset_id),
+            stable_rate_model: self.stable_rate_models.get(asset_id),
+            delisting: self.reserve_delistings.get(asset_id),
         };
 
         for action in actions.iter_mut() {
             match action {
                 ReserveAction::Deposit(account_id, amount) => {
+                    if reserve_ctx.delisting.is_some() {
+                        return Err(ReserveDelistingError::Delisting.into());
+                    }
                     let interest_res = self.account_for_deposit(
                         &mut reserve_ctx,
                         accounts_data.get_mut(*account_id as usize).unwrap(),
@@ -321,6 +545,24 @@ impl LendingPoolStorage {
                     )?;
                     interests_acc.update_at(*account_id, interest_res)?;
                 }
+                ReserveAction::BorrowStable(account_id, amount) => {
+                    let interest_res = self.account_for_stable_borrow(
+                        &mut reserve_ctx,
+                        accounts_data.get_mut(*account_id as usize).unwrap(),
+                        accounts_config.get_mut(*account_id as usize).unwrap(),
+                        amount,
+                    )?;
+                    interests_acc.update_at(*account_id, interest_res)?;
+                }
+                ReserveAction::RepayStable(account_id, amount) => {
+                    let interest_res = self.account_for_stable_repay(
+                        &mut reserve_ctx,
+                        accounts_data.get_mut(*account_id as usize).unwrap(),
+                        accounts_config.get_mut(*account_id as usize).unwrap(),
+                        amount,
+                    )?;
+                    interests_acc.update_at(*account_id, interest_res)?;
+                }
 
                 ReserveAction::DepositTransfer(
                     from_id,
@@ -376,12 +618,12 @@ impl LendingPoolStorage {
             }
         }
 
-        if let Some(params) = self.interest_rate_model.get(asset_id) {
+        if let Some(params) = self.find_interest_rate_model(asset_id) {
             reserve_data.recalculate_current_rates(&params)?
         }
 
-        self.reserve_datas.insert(asset_id, &reserve_data);
-        self.reserve_indexes_and_fees
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        self.reserve_indexes_and_fees_v2
             .insert(asset_id, &reserve_indexes_and_fees);
 
         Ok(interests_acc.items)
@@ -418,6 +660,9 @@ impl LendingPoolStorage {
         reserve_ctx
             .reserve_restrictions
             .ensure_max_total_deposit_not_reached(reserve_ctx.reserve_data)?;
+        reserve_ctx
+            .account_limits
+            .ensure_account_deposit_within_limit(account_reserve_data)?;
 
         Ok((
             account_accumulated_deposit_interest,
@@ -480,6 +725,9 @@ impl LendingPoolStorage {
     ) -> Result<(u128, u128), LendingPoolError> {
         reserve_ctx.reserve_data.ensure_activated()?;
         reserve_ctx.reserve_data.ensure_not_frozen()?;
+        if reserve_ctx.delisting.is_some() {
+            return Err(ReserveDelistingError::Delisting.into());
+        }
 
         let (
             account_accumulated_deposit_interest,
@@ -504,6 +752,12 @@ impl LendingPoolStorage {
         reserve_ctx
             .reserve_restrictions
             .ensure_max_total_debt_not_reached(reserve_ctx.reserve_data)?;
+        reserve_ctx
+            .account_limits
+            .ensure_account_debt_within_limits(
+                account_reserve_data,
+                reserve_ctx.reserve_data,
+            )?;
 
         Ok((
             account_accumulated_deposit_interest,
@@ -558,6 +812,160 @@ impl LendingPoolStorage {
         ))
     }
 
+    /// accounts for borrowing with the stable rate currently offered by the reserve.
+    ///
+    /// # Returns
+    /// (0, accumulated_stable_debt_interest)
+    fn account_for_stable_borrow(
+        &mut self,
+        reserve_ctx: &mut ReserveDataContext,
+        account_reserve_data: &mut AccountReserveData,
+        account_config: &mut AccountConfig,
+        amount: &Balance,
+    ) -> Result<(u128, u128), LendingPoolError> {
+        reserve_ctx.reserve_data.ensure_activated()?;
+        reserve_ctx.reserve_data.ensure_not_frozen()?;
+        if reserve_ctx.delisting.is_some() {
+            return Err(ReserveDelistingError::Delisting.into());
+        }
+
+        let stable_rate_model = reserve_ctx
+            .stable_rate_model
+            .ok_or(LendingPoolError::StableBorrowDisabled)?;
+
+        let account_accumulated_stable_debt_interest =
+            reserve_ctx.reserve_data.add_stable_interest(
+                account_reserve_data.accumulate_account_stable_interest(
+                    &reserve_ctx
+                        .reserve_indexes_and_fees
+                        .indexes
+                        .update_timestamp,
+                )?,
+            )?;
+
+        let stable_rate_e18 = stable_rate_model
+            .current_stable_rate_e18(reserve_ctx.reserve_data)?;
+
+        account_reserve_data.increase_account_stable_debt(
+            &reserve_ctx.asset_id,
+            account_config,
+            reserve_ctx.reserve_data,
+            amount,
+            &stable_rate_e18,
+        )?;
+
+        reserve_ctx
+            .reserve_restrictions
+            .ensure_debt_exceeds_minimum(account_reserve_data)?;
+        reserve_ctx
+            .reserve_restrictions
+            .ensure_max_total_debt_not_reached(reserve_ctx.reserve_data)?;
+        reserve_ctx
+            .account_limits
+            .ensure_account_debt_within_limits(
+                account_reserve_data,
+                reserve_ctx.reserve_data,
+            )?;
+
+        Ok((0, account_accumulated_stable_debt_interest))
+    }
+
+    /// accounts for repaying the stable debt.
+    ///
+    /// # Returns
+    /// (0, accumulated_stable_debt_interest)
+    fn account_for_stable_repay(
+        &mut self,
+        reserve_ctx: &mut ReserveDataContext,
+        account_reserve_data: &mut AccountReserveData,
+        account_config: &mut AccountConfig,
+        amount: &mut Balance,
+    ) -> Result<(u128, u128), LendingPoolError> {
+        reserve_ctx.reserve_data.ensure_activated()?;
+
+        let account_accumulated_stable_debt_interest =
+            reserve_ctx.reserve_data.add_stable_interest(
+                account_reserve_data.accumulate_account_stable_interest(
+                    &reserve_ctx
+                        .reserve_indexes_and_fees
+                        .indexes
+                        .update_timestamp,
+                )?,
+            )?;
+
+        if account_reserve_data.stable_debt == 0 {
+            return Err(LendingPoolError::NothingToRepay);
+        }
+
+        if *amount > account_reserve_data.stable_debt {
+            *amount = account_reserve_data.stable_debt;
+        }
+
+        account_reserve_data.decrease_account_stable_debt(
+            &reserve_ctx.asset_id,
+            account_config,
+            reserve_ctx.reserve_data,
+            amount,
+        )?;
+        reserve_ctx
+            .reserve_restrictions
+            .ensure_debt_exceeds_minimum(account_reserve_data)?;
+
+        Ok((0, account_accumulated_stable_debt_interest))
+    }
+
+    /// accounts for the change of the debt backed by the isolated collateral of the account (if the account uses one).
+    ///
+    /// The `amount` of `asset_id` is valued at the current price. On increase the borrowed asset must be isolation borrowable
+    /// and the total debt backed by the isolated collateral can not exceed its debt ceiling.
+    fn account_for_isolated_debt_change(
+        &mut self,
+        account_config: &AccountConfig,
+        asset_id: AssetId,
+        amount: &Balance,
+        is_increase: bool,
+    ) -> Result<(), LendingPoolError> {
+        let isolated_id = match account_config.isolated_collateral {
+            Some(isolated_id) => isolated_id,
+            None => return Ok(()),
+        };
+        if is_increase {
+            self.get_reserve_isolation(asset_id)
+                .ensure_isolation_borrowable()?;
+        }
+
+        let amount_value_e8 = calculate_asset_amount_value_e8(
+            amount,
+            &self.get_asset_price_e18(asset_id)?,
+            &self.get_decimal_multiplier(asset_id),
+        );
+        let isolated_debt_e8 =
+            self.isolated_debts_e8.get(isolated_id).unwrap_or_default();
+
+        let new_isolated_debt_e8 = if is_increase {
+            let new_isolated_debt_e8 = isolated_debt_e8
+                .checked_add(amount_value_e8)
+                .ok_or(MathError::Overflow)?;
+            self.get_reserve_isolation(isolated_id)
+                .ensure_debt_ceiling_not_reached(new_isolated_debt_e8)?;
+            new_isolated_debt_e8
+        } else {
+            // the debt is valued at different prices while borrowing and repaying
+            isolated_debt_e8.saturating_sub(amount_value_e8)
+        };
+
+        self.isolated_debts_e8
+            .insert(isolated_id, &new_isolated_debt_e8);
+        Ok(())
+    }
+
+    /// accounts for liquidation of `liquidated_account` by `caller`.
+    ///
+    /// `amount_to_repay` is capped by the close factor of the `asset_to_repay` reserve (if set).
+    /// The variable debt is repaid. If the `liquidated_account` has no variable debt in `asset_to_repay`, its stable debt is repaid.
+    ///
+    /// # Returns
+    /// (amount_to_take, max_amount_to_repay, is_stable_debt_repaid, liquidated_account_interests_to_repay, liquidated_account_interests_to_take, caller_interests_to_take)
     #[allow(clippy::too_many_arguments)]
     #[allow(clippy::type_complexity)]
     pub fn account_for_liquidate(
@@ -567,9 +975,11 @@ impl LendingPoolStorage {
         asset_to_repay: &AccountId,
         asset_to_take: &AccountId,
         amount_to_repay: &mut Balance,
+        penalty_part_e6: &u128,
+        receive_underlying: bool,
         timestamp: &Timestamp,
     ) -> Result<
-        (u128, (u128, u128), (u128, u128), (u128, u128)),
+        (u128, u128, bool, (u128, u128), (u128, u128), (u128, u128)),
         LendingPoolError,
     > {
         let asset_to_repay_id = self.asset_id(asset_to_repay)?;
@@ -585,13 +995,36 @@ impl LendingPoolStorage {
             self.get_fee_reductions_of_account(liquidated_account);
         let caller_fee_reductions = self.get_fee_reductions_of_account(caller);
 
+        let mut assets_to_price = get_priced_assets(&liquidated_account_config);
+        assets_to_price.insert(asset_to_repay_id);
+        assets_to_price.insert(asset_to_take_id);
+        let prices_e18 = self.get_prices_e18_of(&assets_to_price)?;
+
+        // the capped amount never exceeds the debt, so the repay below doesn't change it
+        let (mut amount_to_take, max_amount_to_repay) = self
+            .calculate_liquidated_amount_and_ensure_collateralized(
+                liquidated_account,
+                &liquidated_account_datas,
+                &liquidated_account_config,
+                &liquidated_account_fee_reductions,
+                &prices_e18,
+                asset_to_repay_id,
+                asset_to_take_id,
+                amount_to_repay,
+                penalty_part_e6,
+                timestamp,
+            )?;
+
         let liquidated_account_data_to_repay = liquidated_account_datas
             .get_mut(asset_to_repay_id as usize)
             .unwrap()
             .as_mut()
             .ok_or(LendingPoolError::NothingToRepay)?;
 
-        if liquidated_account_data_to_repay.debt == 0 {
+        let is_stable_debt_repaid = liquidated_account_data_to_repay.debt == 0;
+        if is_stable_debt_repaid
+            && liquidated_account_data_to_repay.stable_debt == 0
+        {
             return Err(LendingPoolError::NothingToRepay);
         }
 
@@ -600,24 +1033,23 @@ impl LendingPoolStorage {
             &mut [liquidated_account_data_to_repay],
             &mut [&mut liquidated_account_config],
             &[&liquidated_account_fee_reductions],
-            &mut [&mut ReserveAction::Repay(0, amount_to_repay)],
+            &mut [&mut if is_stable_debt_repaid {
+                ReserveAction::RepayStable(0, amount_to_repay)
+            } else {
+                ReserveAction::Repay(0, amount_to_repay)
+            }],
             timestamp,
         )?;
         let (
             liquidated_account_accumulated_deposit_interest_to_repay,
             liquidated_account_accumulated_debt_interest_to_repay,
         ) = res.first().unwrap();
-
-        let prices_e18 = self.get_assets_prices_e18()?;
-        let mut amount_to_take = self
-            .calculate_liquidated_amount_and_ensure_collateralized(
-                liquidated_account,
-                asset_to_repay_id,
-                asset_to_take_id,
-                &prices_e18[asset_to_repay_id as usize],
-                &prices_e18[asset_to_take_id as usize],
-                amount_to_repay,
-            )?;
+        self.account_for_isolated_debt_change(
+            &liquidated_account_config,
+            asset_to_repay_id,
+            amount_to_repay,
+            false,
+        )?;
 
         let liquidated_account_data_to_take = liquidated_account_datas
             .get_mut(asset_to_take_id as usize)
@@ -625,42 +1057,64 @@ impl LendingPoolStorage {
             .as_mut()
             .ok_or(LendingPoolError::NothingToCompensateWith)?;
 
-        let callers_data_to_take = get_account_data_entry_mut(
-            &mut caller_account_datas,
-            asset_to_take_id,
-        );
+        // the taken deposit is either transferred to the caller or withdrawn (the underlying is transferred out by the caller of this function)
+        let res = if receive_underlying {
+            self.account_for_reserve_action(
+                asset_to_take_id,
+                &mut [liquidated_account_data_to_take],
+                &mut [&mut liquidated_account_config],
+                &[&liquidated_account_fee_reductions],
+                &mut [&mut ReserveAction::Withdraw(
+                    0,
+                    &mut amount_to_take,
+                    true,
+                )],
+                timestamp,
+            )?
+        } else {
+            let callers_data_to_take = get_account_data_entry_mut(
+                &mut caller_account_datas,
+                asset_to_take_id,
+            );
 
-        let res = self.account_for_reserve_action(
-            asset_to_take_id,
-            &mut [liquidated_account_data_to_take, callers_data_to_take],
-            &mut [&mut liquidated_account_config, &mut caller_config],
-            &[&liquidated_account_fee_reductions, &caller_fee_reductions],
-            &mut [&mut ReserveAction::DepositTransfer(
-                0,
-                1,
-                &mut amount_to_take,
-                true,
-            )],
-            timestamp,
-        )?;
+            self.account_for_reserve_action(
+                asset_to_take_id,
+                &mut [liquidated_account_data_to_take, callers_data_to_take],
+                &mut [&mut liquidated_account_config, &mut caller_config],
+                &[&liquidated_account_fee_reductions, &caller_fee_reductions],
+                &mut [&mut ReserveAction::DepositTransfer(
+                    0,
+                    1,
+                    &mut amount_to_take,
+                    true,
+                )],
+                timestamp,
+            )?
+        };
 
-        self.account_configs
-            .insert(liquidated_account, &liquidated_account_config);
-        self.account_reserve_datas
+        self.store_account_config(
+            liquidated_account,
+            &liquidated_account_config,
+        );
+        self.account_reserve_datas_v2
             .insert(liquidated_account, &liquidated_account_datas);
 
-        self.account_configs.insert(caller, &caller_config);
-        self.account_reserve_datas
-            .insert(caller, &caller_account_datas);
+        if !receive_underlying {
+            self.store_account_config(caller, &caller_config);
+            self.account_reserve_datas_v2
+                .insert(caller, &caller_account_datas);
+        }
 
         Ok((
             amount_to_take,
+            max_amount_to_repay,
+            is_stable_debt_repaid,
             (
                 *liquidated_account_accumulated_deposit_interest_to_repay,
                 *liquidated_account_accumulated_debt_interest_to_repay,
             ),
             *res.first().unwrap(),
-            *res.get(1).unwrap(),
+            res.get(1).copied().unwrap_or_default(),
         ))
     }
 
@@ -676,12 +1130,10 @@ impl LendingPoolStorage {
     {
         let asset_id = self.asset_id(asset)?;
         let mut from_config = self
-            .account_configs
-            .get(from)
+            .find_account_config(from)
             .ok_or(LendingPoolError::InsufficientDeposit)?;
         let mut from_datas = self
-            .account_reserve_datas
-            .get(from)
+            .find_account_datas(from)
             .ok_or(LendingPoolError::InsufficientDeposit)?;
         let mut to_config = self.get_account_config(to);
         let (mut to_account_reserve_data, to_account_datas) =
@@ -715,7 +1167,7 @@ impl LendingPoolStorage {
             )?;
         }
 
-        if from_config.collaterals & (1 << asset_id) == 1 {
+        if from_config.collaterals.contains(asset_id) {
             self.ensure_collateralized_from_raw(
                 &from_datas,
                 &from_config,
@@ -729,9 +1181,9 @@ impl LendingPoolStorage {
             to_account_reserve_data,
             to,
         );
-        self.account_reserve_datas.insert(from, &from_datas);
-        self.account_configs.insert(from, &from_config);
-        self.account_configs.insert(to, &to_config);
+        self.account_reserve_datas_v2.insert(from, &from_datas);
+        self.store_account_config(from, &from_config);
+        self.store_account_config(to, &to_config);
         Ok((*result.first().unwrap(), *result.get(1).unwrap()))
     }
 
@@ -746,17 +1198,14 @@ impl LendingPoolStorage {
     ) -> Result<((Balance, Balance), (Balance, Balance)), LendingPoolError>
     {
         let asset_id = self.asset_id(asset)?;
-        let mut from_config =
-            self.account_configs.get(from).unwrap_or_default();
+        let mut from_config = self.get_account_config(from);
         let (mut from_account_data, from_account_datas) =
             self.get_account_reserve_data(asset_id, from);
         let mut to_config = self
-            .account_configs
-            .get(to)
+            .find_account_config(to)
             .ok_or(LendingPoolError::InsufficientCollateral)?;
         let mut to_datas = self
-            .account_reserve_datas
-            .get(to)
+            .find_account_datas(to)
             .ok_or(LendingPoolError::InsufficientCollateral)?;
         let from_fee_reductions = self.get_fee_reductions_of_account(from);
         let to_fee_reductions = self.get_fee_reductions_of_account(to);
@@ -781,6 +1230,15 @@ impl LendingPoolStorage {
                 timestamp,
             )?;
         }
+        self.account_for_isolated_debt_change(
+            &from_config,
+            asset_id,
+            amount,
+            false,
+        )?;
+        self.account_for_isolated_debt_change(
+            &to_config, asset_id, amount, true,
+        )?;
         self.ensure_collateralized_from_raw(
             &to_datas,
             &to_config,
@@ -793,9 +1251,9 @@ impl LendingPoolStorage {
             from_account_data,
             from,
         );
-        self.account_reserve_datas.insert(to, &to_datas);
-        self.account_configs.insert(to, &to_config);
-        self.account_configs.insert(from, &from_config);
+        self.account_reserve_datas_v2.insert(to, &to_datas);
+        self.store_account_config(to, &to_config);
+        self.store_account_config(from, &from_config);
         Ok((*result.first().unwrap(), *result.get(1).unwrap()))
     }
 
@@ -811,8 +1269,11 @@ impl LendingPoolStorage {
             self.get_account_reserve_data(asset_id, account);
         let mut account_config = self.get_account_config(account);
         let reserve_restrictions = self.get_reserve_restrictions(asset_id);
+        let reserve_isolation = self.get_reserve_isolation(asset_id);
         let market_rule = self.get_market_rule(&account_config);
 
+        account_config.exit_isolation_if_unused();
+
         let collateral_coefficient_e6 = market_rule
             .get(asset_id as usize)
             .ok_or(LendingPoolError::RuleCollateralDisable)?
@@ -824,48 +1285,394 @@ impl LendingPoolStorage {
             return Err(LendingPoolError::RuleCollateralDisable);
         }
 
+        if use_as_collateral_to_set
+            && self.reserve_delistings.contains(asset_id)
+        {
+            return Err(ReserveDelistingError::Delisting.into());
+        }
+
         if use_as_collateral_to_set {
             reserve_restrictions
-                .ensure_collateral_exceeds_minimum(&account_reserve_data)?
+                .ensure_collateral_exceeds_minimum(&account_reserve_data)?;
+            reserve_isolation
+                .ensure_can_be_collateral(asset_id, &account_config)?;
         };
 
         if use_as_collateral_to_set {
-            account_config.collaterals |= 1_u128 << asset_id;
+            account_config.collaterals.insert(asset_id);
+            if reserve_isolation.is_isolated() {
+                account_config.isolated_collateral = Some(asset_id);
+            }
         } else {
-            account_config.collaterals &= !(1_u128 << asset_id);
+            account_config.collaterals.remove(asset_id);
+            account_config.exit_isolation_if_unused();
         }
 
-        self.account_configs.insert(account, &account_config);
+        self.store_account_config(account, &account_config);
 
         Ok(())
     }
 
-    pub fn account_for_accumulate_interest(
+    /// writes off the whole debt of the `account` that has no collateral left.
+    ///
+    /// First, the debt in each borrowed asset (with accumulated interest) is removed from the `account` and covered by the protocol's earned fee.
+    ///
+    /// Then, the remaining deposits of the `account` (that are not used as collateral, so can't be taken by liquidators)
+    /// are seized - moved to the safety funds of their reserves - up to the value of the written off debt at the oracle prices.
+    /// Deposits in the borrowed assets are seized first. The rest of the deposits is left to the `account`.
+    ///
+    /// Finally, for each borrowed asset the rest of the debt is covered by, in order:
+    /// 1) the reserve's safety fund,
+    /// 2) the socialization among depositors (decrease of deposit index).
+    #[allow(clippy::type_complexity)]
+    pub fn account_for_settle_bad_debt(
+        &mut self,
+        account: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<(Vec<DepositSeizure>, Vec<BadDebtWriteOff>), LendingPoolError>
+    {
+        let mut account_config = self.get_account_config(account);
+        if !account_config.has_unbacked_debt() {
+            return Err(LendingPoolError::NoBadDebt);
+        }
+        let mut account_datas = self.get_account_datas(account);
+        let fee_reductions = self.get_fee_reductions_of_account(account);
+
+        let debts = account_config.debts();
+        let prices_e18 =
+            self.get_prices_e18_of(&account_config.deposits.union(&debts))?;
+
+        let mut seizures: Vec<DepositSeizure> = Vec::new();
+        let mut write_offs: Vec<BadDebtWriteOff> = Vec::new();
+        let mut debt_value_e8: u128 = 0;
+
+        for asset_id in debts.iter() {
+            let mut reserve_data = self.get_reserve_data(asset_id);
+            let mut reserve_indexes_and_fees =
+                self.get_reserve_indexes_and_fees(asset_id);
+
+            self.update_reserve_indexes(
+                asset_id,
+                &reserve_data,
+                &mut reserve_indexes_and_fees,
+                timestamp,
+            )?;
+
+            let account_reserve_data =
+                get_account_data_entry_mut(&mut account_datas, asset_id);
+
+            let interests = reserve_data.add_interests(
+                account_reserve_data.accumulate_account_interest(
+                    &reserve_indexes_and_fees.indexes,
+                    &mut reserve_indexes_and_fees.fees,
+                    &fee_reductions,
+                )?,
+            )?;
+
+            let stable_interest = reserve_data.add_stable_interest(
+                account_reserve_data
+                    .accumulate_account_stable_interest(timestamp)?,
+            )?;
+
+            let variable_amount = account_reserve_data.debt;
+            account_reserve_data.decrease_account_debt(
+                &asset_id,
+                &mut account_config,
+                &mut reserve_data,
+                &variable_amount,
+            )?;
+            let stable_amount = account_reserve_data.stable_debt;
+            account_reserve_data.decrease_account_stable_debt(
+                &asset_id,
+                &mut account_config,
+                &mut reserve_data,
+                &stable_amount,
+            )?;
+            let amount = variable_amount
+                .checked_add(stable_amount)
+                .ok_or(MathError::Overflow)?;
+            self.account_for_isolated_debt_change(
+                &account_config,
+                asset_id,
+                &amount,
+                false,
+            )?;
+
+            debt_value_e8 = debt_value_e8
+                .checked_add(calculate_asset_amount_value_e8(
+                    &amount,
+                    &prices_e18[asset_id as usize],
+                    &self.get_decimal_multiplier(asset_id),
+                ))
+                .ok_or(MathError::Overflow)?;
+
+            let covered_by_earned_fee =
+                reserve_indexes_and_fees.fees.cover_with_earned_fee(&amount);
+
+            self.reserve_datas_v2.insert(asset_id, &reserve_data);
+            self.reserve_indexes_and_fees_v2
+                .insert(asset_id, &reserve_indexes_and_fees);
+
+            write_offs.push(BadDebtWriteOff {
+                asset_id,
+                amount,
+                covered_by_earned_fee,
+                covered_by_safety_fund: 0,
+                socialized: 0,
+                stable_amount,
+                interests,
+                stable_interest,
+            });
+        }
+
+        // the deposits in the borrowed assets go straight to the safety funds covering the debt
+        let deposited_assets = account_config.deposits.clone();
+        let assets_to_seize = deposited_assets.intersection(&debts);
+        let assets_to_seize = assets_to_seize.iter().chain(
+            deposited_assets
+                .iter()
+                .filter(|asset_id| !debts.contains(*asset_id)),
+        );
+        for asset_id in assets_to_seize {
+            if debt_value_e8 == 0 {
+                break;
+            }
+            let price_e18 = prices_e18[asset_id as usize];
+            if price_e18 == 0 {
+                continue;
+            }
+            let mut reserve_data = self.get_reserve_data(asset_id);
+            let mut reserve_indexes_and_fees =
+                self.get_reserve_indexes_and_fees(asset_id);
+            let reserve_restrictions = self.get_reserve_restrictions(asset_id);
+
+            self.update_reserve_indexes(
+                asset_id,
+                &reserve_data,
+                &mut reserve_indexes_and_fees,
+                timestamp,
+            )?;
+
+            let account_reserve_data =
+                get_account_data_entry_mut(&mut account_datas, asset_id);
+
+            let interests = reserve_data.add_interests(
+                account_reserve_data.accumulate_account_interest(
+                    &reserve_indexes_and_fees.indexes,
+                    &mut reserve_indexes_and_fees.fees,
+                    &fee_reductions,
+                )?,
+            )?;
+
+            let decimal_multiplier = self.get_decimal_multiplier(asset_id);
+            let amount = mul_div(
+                debt_value_e8,
+                decimal_multiplier
+                    .checked_mul(E10_U128)
+                    .ok_or(MathError::Overflow)?,
+                price_e18,
+                Rounding::Up,
+            )?
+            .min(account_reserve_data.deposit);
+            debt_value_e8 =
+                debt_value_e8.saturating_sub(calculate_asset_amount_value_e8(
+                    &amount,
+                    &price_e18,
+                    &decimal_multiplier,
+                ));
+            account_reserve_data.decrease_account_deposit(
+                &asset_id,
+                &mut account_config,
+                &mut reserve_data,
+                &reserve_restrictions,
+                &amount,
+            )?;
+
+            let safety_fund = self
+                .reserve_safety_funds
+                .get(asset_id)
+                .unwrap_or_default()
+                .checked_add(amount)
+                .ok_or(MathError::Overflow)?;
+            self.reserve_safety_funds.insert(asset_id, &safety_fund);
+
+            if let Some(params) = self.find_interest_rate_model(asset_id) {
+                reserve_data.recalculate_current_rates(&params)?
+            }
+
+            self.reserve_datas_v2.insert(asset_id, &reserve_data);
+            self.reserve_indexes_and_fees_v2
+                .insert(asset_id, &reserve_indexes_and_fees);
+
+            seizures.push(DepositSeizure {
+                asset_id,
+                amount,
+                interests,
+            });
+        }
+
+        for write_off in write_offs.iter_mut() {
+            let asset_id = write_off.asset_id;
+            let mut reserve_data = self.get_reserve_data(asset_id);
+            let mut reserve_indexes_and_fees =
+                self.get_reserve_indexes_and_fees(asset_id);
+
+            let mut uncovered = write_off
+                .amount
+                .checked_sub(write_off.covered_by_earned_fee)
+                .ok_or(MathError::Underflow)?;
+
+            let safety_fund =
+                self.reserve_safety_funds.get(asset_id).unwrap_or_default();
+            write_off.covered_by_safety_fund = safety_fund.min(uncovered);
+            if write_off.covered_by_safety_fund != 0 {
+                self.reserve_safety_funds.insert(
+                    asset_id,
+                    &(safety_fund
+                        .checked_sub(write_off.covered_by_safety_fund)
+                        .ok_or(MathError::Underflow)?),
+                );
+            }
+            uncovered = uncovered
+                .checked_sub(write_off.covered_by_safety_fund)
+                .ok_or(MathError::Underflow)?;
+
+            if uncovered > reserve_data.total_deposit {
+                return Err(LendingPoolError::BadDebtExceedsDeposits);
+            }
+            write_off.socialized = uncovered;
+            reserve_indexes_and_fees.indexes.socialize_deposit_loss(
+                &reserve_data.total_deposit,
+                &write_off.socialized,
+            )?;
+            reserve_data.decrease_total_deposit(&write_off.socialized)?;
+
+            if let Some(params) = self.find_interest_rate_model(asset_id) {
+                reserve_data.recalculate_current_rates(&params)?
+            }
+
+            self.reserve_datas_v2.insert(asset_id, &reserve_data);
+            self.reserve_indexes_and_fees_v2
+                .insert(asset_id, &reserve_indexes_and_fees);
+        }
+
+        account_config.exit_isolation_if_unused();
+
+        self.account_reserve_datas_v2
+            .insert(account, &account_datas);
+        self.store_account_config(account, &account_config);
+
+        Ok((seizures, write_offs))
+    }
+
+    /// changes the stable rate of the `account` in `asset` reserve to the currently offered stable rate.
+    ///
+    /// # Returns
+    /// (new_stable_rate_e18, accumulated_stable_debt_interest)
+    pub fn account_for_stable_rate_rebalance(
         &mut self,
         asset: &AccountId,
+        account: &AccountId,
         timestamp: &Timestamp,
-    ) -> Result<(), LendingPoolError> {
+    ) -> Result<(u64, Balance), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
+        let stable_rate_model = self
+            .stable_rate_models
+            .get(asset_id)
+            .ok_or(LendingPoolError::StableBorrowDisabled)?;
+
+        let (mut account_reserve_data, account_datas) =
+            self.get_account_reserve_data(asset_id, account);
+        if account_reserve_data.stable_debt == 0 {
+            return Err(LendingPoolError::InsufficientDebt);
+        }
+
         let mut reserve_data = self.get_reserve_data(asset_id);
         let mut reserve_indexes_and_fees =
             self.get_reserve_indexes_and_fees(asset_id);
-        let interest_rate_model = self.interest_rate_model.get(asset_id);
 
-        self.store_utilization_rate_snapshot(
+        self.update_reserve_indexes(
             asset_id,
             &reserve_data,
+            &mut reserve_indexes_and_fees,
             timestamp,
         )?;
 
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
+        let stable_interest = reserve_data.add_stable_interest(
+            account_reserve_data
+                .accumulate_account_stable_interest(timestamp)?,
+        )?;
+
+        let current_stable_rate_e18 =
+            stable_rate_model.current_stable_rate_e18(&reserve_data)?;
+        if !stable_rate_model.can_rebalance(
+            account_reserve_data.stable_rate_e18,
+            current_stable_rate_e18,
+        ) {
+            return Err(LendingPoolError::StableRateRebalanceNotAllowed);
+        }
+
+        account_reserve_data.rebalance_stable_rate(
+            &mut reserve_data,
+            &current_stable_rate_e18,
+        )?;
+
+        if let Some(params) = self.find_interest_rate_model(asset_id) {
+            reserve_data.recalculate_current_rates(&params)?
+        }
+
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        self.reserve_indexes_and_fees_v2
+            .insert(asset_id, &reserve_indexes_and_fees);
+        self.insert_account_data(
+            account_datas,
+            asset_id,
+            account_reserve_data,
+            account,
+        );
+
+        Ok((current_stable_rate_e18, stable_interest))
+    }
+
+    pub fn account_for_safety_fund_increase(
+        &mut self,
+        asset: &AccountId,
+        amount: &Balance,
+    ) -> Result<Balance, LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let safety_fund = self
+            .reserve_safety_funds
+            .get(asset_id)
+            .unwrap_or_default()
+            .checked_add(*amount)
+            .ok_or(MathError::Overflow)?;
+        self.reserve_safety_funds.insert(asset_id, &safety_fund);
+        Ok(safety_fund)
+    }
+
+    pub fn account_for_accumulate_interest(
+        &mut self,
+        asset: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let mut reserve_data = self.get_reserve_data(asset_id);
+        let mut reserve_indexes_and_fees =
+            self.get_reserve_indexes_and_fees(asset_id);
+
+        self.update_reserve_indexes(
+            asset_id,
+            &reserve_data,
+            &mut reserve_indexes_and_fees,
+            timestamp,
+        )?;
+        let interest_rate_model = self.find_interest_rate_model(asset_id);
         if let Some(params) = interest_rate_model {
             reserve_data.recalculate_current_rates(&params)?
         }
 
-        self.reserve_datas.insert(asset_id, &reserve_data);
-        self.reserve_indexes_and_fees
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        self.reserve_indexes_and_fees_v2
             .insert(asset_id, &reserve_indexes_and_fees);
 
         Ok(())
@@ -881,56 +1688,103 @@ impl LendingPoolStorage {
         fee_reductions: &FeeReductions,
         prices_e18: &[u128],
     ) -> Result<(bool, u128), LendingPoolError> {
-        let mut total_collateral_power_e6: u128 = 0;
-        let mut total_debt_power_e6: u128 = 0;
-
-        let market_rule = self.get_market_rule(account_config);
-
-        let collaterals = account_config.deposits & account_config.collaterals;
-        let debts = account_config.borrows;
-        let active_account_assets = collaterals | debts;
-
-        let next_asset_id = self.next_asset_id.get().unwrap_or(0);
+        let (total_collateral_power_e6, total_debt_power_e6) = self
+            .calculate_collateral_and_debt_powers_e6(
+                account_reserve_datas,
+                account_config,
+                fee_reductions,
+                prices_e18,
+            )?;
 
-        for asset_id in 0..next_asset_id {
-            if (active_account_assets >> asset_id) == 0 {
-                break;
-            }
-            if ((active_account_assets >> asset_id) & 1) == 0 {
-                continue;
-            }
+        if total_collateral_power_e6 >= total_debt_power_e6 {
+            Ok((
+                true,
+                total_collateral_power_e6
+                    .checked_sub(total_debt_power_e6)
+                    .ok_or(MathError::Underflow)?,
+            ))
+        } else {
+            Ok((
+                false,
+                total_debt_power_e6
+                    .checked_sub(total_collateral_power_e6)
+                    .ok_or(MathError::Underflow)?,
+            ))
+        }
+    }
 
-            let mut account_reserve_data =
-                match account_reserve_datas[asset_id as usize] {
-                    Some(data) => data,
-                    None => continue,
-                };
+    /// Returns (total_collateral_power_e6, total_debt_power_e6) of an account
+    /// i.e. sums of collaterals and debts values weighted by the coefficients of the account's market rule.
+    pub fn calculate_collateral_and_debt_powers_e6(
+        &self,
+        account_reserve_datas: &[Option<AccountReserveData>],
+        account_config: &AccountConfig,
+        fee_reductions: &FeeReductions,
+        prices_e18: &[u128],
+    ) -> Result<(u128, u128), LendingPoolError> {
+        let account_health = self.calculate_account_health(
+            account_reserve_datas,
+            account_config,
+            fee_reductions,
+            prices_e18,
+        )?;
+        Ok((
+            account_health.collateral_power_e6,
+            account_health.debt_power_e6,
+        ))
+    }
 
-            // Reserve indexes are not updated
-            let mut reserve_indexes_and_fees =
-                self.get_reserve_indexes_and_fees(asset_id);
+    /// Returns the health of an account i.e. the values of its collaterals and debts, the values weighted by the coefficients of the account's market rule and the health factor.
+    pub fn calculate_account_health(
+        &self,
+        account_reserve_datas: &[Option<AccountReserveData>],
+        account_config: &AccountConfig,
+        fee_reductions: &FeeReductions,
+        prices_e18: &[u128],
+    ) -> Result<AccountHealth, LendingPoolError> {
+        let mut account_health = AccountHealth::default();
 
-            account_reserve_data.accumulate_account_interest(
-                &reserve_indexes_and_fees.indexes,
-                &mut reserve_indexes_and_fees.fees,
-                fee_reductions,
-            )?;
+        let market_rule = self.get_market_rule(account_config);
 
-            if ((collaterals >> asset_id) & 1) == 1 {
+        let collaterals = self.get_counted_collaterals(account_config);
+        let debts = account_config.debts();
+        let active_account_assets = collaterals.union(&debts);
+
+        for asset_id in active_account_assets.iter() {
+            let account_reserve_data = match self
+                .get_account_reserve_data_with_interests(
+                    asset_id,
+                    &account_reserve_datas[asset_id as usize],
+                    fee_reductions,
+                )? {
+                Some(data) => data,
+                None => continue,
+            };
+
+            if self.is_counted_as_collateral(account_config, asset_id) {
                 let collateral_value_e8 = calculate_asset_amount_value_e8(
                     &account_reserve_data.deposit,
                     &prices_e18[asset_id as usize],
                     &self.get_decimal_multiplier(asset_id),
                 );
 
-                let collateral_coefficient_e6 = market_rule
-                    .get(asset_id as usize)
-                    .ok_or(LendingPoolError::RuleCollateralDisable)?
-                    .ok_or(LendingPoolError::RuleCollateralDisable)?
-                    .collateral_coefficient_e6
-                    .ok_or(LendingPoolError::RuleCollateralDisable)?;
+                let collateral_coefficient_e6 = self
+                    .ramp_down_collateral_coefficient_e6(
+                        asset_id,
+                        market_rule
+                            .get(asset_id as usize)
+                            .ok_or(LendingPoolError::RuleCollateralDisable)?
+                            .ok_or(LendingPoolError::RuleCollateralDisable)?
+                            .collateral_coefficient_e6
+                            .ok_or(LendingPoolError::RuleCollateralDisable)?,
+                    )?;
 
-                total_collateral_power_e6 = total_collateral_power_e6
+                account_health.total_collateral_value_e8 = account_health
+                    .total_collateral_value_e8
+                    .checked_add(collateral_value_e8)
+                    .ok_or(MathError::Overflow)?;
+                account_health.collateral_power_e6 = account_health
+                    .collateral_power_e6
                     .checked_add(e8_mul_e6_to_e6_rdown(
                         collateral_value_e8,
                         collateral_coefficient_e6,
@@ -938,9 +1792,12 @@ impl LendingPoolStorage {
                     .ok_or(MathError::Overflow)?;
             }
 
-            if ((debts >> asset_id) & 1) == 1 {
+            if debts.contains(asset_id) {
                 let debt_value_e8 = calculate_asset_amount_value_e8(
-                    &account_reserve_data.debt,
+                    &account_reserve_data
+                        .debt
+                        .checked_add(account_reserve_data.stable_debt)
+                        .ok_or(MathError::Overflow)?,
                     &prices_e18[asset_id as usize],
                     &self.get_decimal_multiplier(asset_id),
                 );
@@ -952,7 +1809,12 @@ impl LendingPoolStorage {
                     .borrow_coefficient_e6
                     .ok_or(LendingPoolError::RuleBorrowDisable)?;
 
-                total_debt_power_e6 = total_debt_power_e6
+                account_health.total_debt_value_e8 = account_health
+                    .total_debt_value_e8
+                    .checked_add(debt_value_e8)
+                    .ok_or(MathError::Overflow)?;
+                account_health.debt_power_e6 = account_health
+                    .debt_power_e6
                     .checked_add(e8_mul_e6_to_e6_rdown(
                         debt_value_e8,
                         debt_coefficient_e6,
@@ -961,23 +1823,181 @@ impl LendingPoolStorage {
             }
         }
 
-        if total_collateral_power_e6 >= total_debt_power_e6 {
-            Ok((
-                true,
-                total_collateral_power_e6
-                    .checked_sub(total_debt_power_e6)
-                    .ok_or(MathError::Underflow)?,
-            ))
-        } else {
-            Ok((
-                false,
-                total_debt_power_e6
-                    .checked_sub(total_collateral_power_e6)
-                    .ok_or(MathError::Underflow)?,
-            ))
+        account_health.update_health_factor()?;
+        Ok(account_health)
+    }
+
+    /// Returns the maximal amount of `asset` that the `account` can borrow without becoming undercollateralized.
+    /// Returns 0 if the market rule of the `account` doesn't allow borrowing the `asset`.
+    pub fn calculate_max_borrowable(
+        &self,
+        account: &AccountId,
+        asset: &AccountId,
+    ) -> Result<Balance, LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let account_config = self.get_account_config(account);
+        let prices_e18 = self.get_assets_prices_e18()?;
+        let account_health = self.calculate_account_health(
+            &self.get_account_datas(account),
+            &account_config,
+            &self.get_fee_reductions_of_account(account),
+            &prices_e18,
+        )?;
+
+        let borrow_coefficient_e6 = match self
+            .get_market_rule(&account_config)
+            .get(asset_id as usize)
+            .copied()
+            .flatten()
+            .and_then(|asset_rules| asset_rules.borrow_coefficient_e6)
+        {
+            Some(borrow_coefficient_e6) => borrow_coefficient_e6,
+            None => return Ok(0),
+        };
+
+        self.calculate_amount_of_power_e6(
+            asset_id,
+            account_health.free_power_e6(),
+            borrow_coefficient_e6,
+            &prices_e18,
+        )
+    }
+
+    /// Returns the maximal amount of `asset` that the `account` can withdraw without becoming undercollateralized.
+    pub fn calculate_max_withdrawable(
+        &self,
+        account: &AccountId,
+        asset: &AccountId,
+    ) -> Result<Balance, LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let account_datas = self.get_account_datas(account);
+        let account_config = self.get_account_config(account);
+        let fee_reductions = self.get_fee_reductions_of_account(account);
+
+        let deposit = match self.get_account_reserve_data_with_interests(
+            asset_id,
+            &account_datas[asset_id as usize],
+            &fee_reductions,
+        )? {
+            Some(account_reserve_data) => account_reserve_data.deposit,
+            None => return Ok(0),
+        };
+        if account_config.debts().is_empty()
+            || !self.is_counted_as_collateral(&account_config, asset_id)
+        {
+            return Ok(deposit);
+        }
+
+        let prices_e18 = self.get_assets_prices_e18()?;
+        let account_health = self.calculate_account_health(
+            &account_datas,
+            &account_config,
+            &fee_reductions,
+            &prices_e18,
+        )?;
+        let collateral_coefficient_e6 = self
+            .ramp_down_collateral_coefficient_e6(
+                asset_id,
+                self.get_market_rule(&account_config)
+                    .get(asset_id as usize)
+                    .copied()
+                    .flatten()
+                    .and_then(|asset_rules| {
+                        asset_rules.collateral_coefficient_e6
+                    })
+                    .ok_or(LendingPoolError::RuleCollateralDisable)?,
+            )?;
+        if collateral_coefficient_e6 == 0 {
+            return Ok(deposit);
+        }
+
+        Ok(deposit.min(self.calculate_amount_of_power_e6(
+            asset_id,
+            account_health.free_power_e6(),
+            collateral_coefficient_e6,
+            &prices_e18,
+        )?))
+    }
+
+    /// Returns the amount of `asset_id` which value weighted by `coefficient_e6` equals `power_e6`.
+    fn calculate_amount_of_power_e6(
+        &self,
+        asset_id: AssetId,
+        power_e6: u128,
+        coefficient_e6: u128,
+        prices_e18: &[u128],
+    ) -> Result<Balance, LendingPoolError> {
+        let value_e8 =
+            mul_div(power_e6, E8_U128, coefficient_e6, Rounding::Down)?;
+        Ok(mul_div(
+            value_e8,
+            self.get_decimal_multiplier(asset_id)
+                .checked_mul(E10_U128)
+                .ok_or(MathError::Overflow)?,
+            prices_e18[asset_id as usize],
+            Rounding::Down,
+        )?)
+    }
+
+    /// Returns the account reserve data with accumulated interests. Reserve indexes are not updated.
+    fn get_account_reserve_data_with_interests(
+        &self,
+        asset_id: AssetId,
+        account_reserve_data: &Option<AccountReserveData>,
+        fee_reductions: &FeeReductions,
+    ) -> Result<Option<AccountReserveData>, LendingPoolError> {
+        let mut account_reserve_data = match account_reserve_data {
+            Some(data) => *data,
+            None => return Ok(None),
+        };
+        let mut reserve_indexes_and_fees =
+            self.get_reserve_indexes_and_fees(asset_id);
+
+        account_reserve_data.accumulate_account_interest(
+            &reserve_indexes_and_fees.indexes,
+            &mut reserve_indexes_and_fees.fees,
+            fee_reductions,
+        )?;
+        account_reserve_data.accumulate_account_stable_interest(
+            &reserve_indexes_and_fees.indexes.update_timestamp,
+        )?;
+        Ok(Some(account_reserve_data))
+    }
+
+    /// Returns the bitmap of the account's deposits that are used as collateral.
+    /// The isolated collateral is the only collateral that backs the debt.
+    fn get_counted_collaterals(
+        &self,
+        account_config: &AccountConfig,
+    ) -> AssetsBitmap {
+        let collaterals = account_config
+            .deposits
+            .intersection(&account_config.collaterals);
+        match account_config.isolated_collateral {
+            Some(isolated_id) => {
+                let mut isolated = AssetsBitmap::default();
+                if collaterals.contains(isolated_id) {
+                    isolated.insert(isolated_id);
+                }
+                isolated
+            }
+            None => collaterals,
         }
     }
 
+    /// Returns true if the account's deposit of `asset_id` backs the account's debt.
+    /// An isolated asset backs only the debt of accounts isolated with it.
+    fn is_counted_as_collateral(
+        &self,
+        account_config: &AccountConfig,
+        asset_id: AssetId,
+    ) -> bool {
+        self.get_counted_collaterals(account_config)
+            .contains(asset_id)
+            && (account_config.isolated_collateral == Some(asset_id)
+                || !self.get_reserve_isolation(asset_id).is_isolated())
+    }
+
     pub fn calculate_lending_power_of_an_account_e6(
         &self,
         account: &AccountId,
@@ -986,7 +2006,67 @@ impl LendingPoolStorage {
             &self.get_account_datas(account),
             &self.get_account_config(account),
             &self.get_fee_reductions_of_account(account),
-            &self.get_assets_prices_e18()?,
+            &self.get_account_prices_e18(&self.get_account_config(account))?,
+        )
+    }
+
+    /// Returns the ids and amounts of the assets in which the account has the largest (by value) collateral and debt.
+    /// Not accumulated interests are not included in the amounts.
+    #[allow(clippy::type_complexity)]
+    pub fn get_largest_collateral_and_debt(
+        &self,
+        account_reserve_datas: &[Option<AccountReserveData>],
+        account_config: &AccountConfig,
+        prices_e18: &[u128],
+    ) -> (Option<(AssetId, Balance)>, Option<(AssetId, Balance)>) {
+        let collaterals = account_config
+            .deposits
+            .intersection(&account_config.collaterals);
+        let debts = account_config.debts();
+
+        let mut largest_collateral: Option<(AssetId, Balance, u128)> = None;
+        let mut largest_debt: Option<(AssetId, Balance, u128)> = None;
+        for (asset_id, account_reserve_data) in
+            account_reserve_datas.iter().enumerate()
+        {
+            let account_reserve_data = match account_reserve_data {
+                Some(data) => data,
+                None => continue,
+            };
+            let asset_id = asset_id as AssetId;
+            let decimal_multiplier = self.get_decimal_multiplier(asset_id);
+
+            if collaterals.contains(asset_id) {
+                let value_e8 = calculate_asset_amount_value_e8(
+                    &account_reserve_data.deposit,
+                    &prices_e18[asset_id as usize],
+                    &decimal_multiplier,
+                );
+                if !largest_collateral.is_some_and(|(_, _, v)| v >= value_e8) {
+                    largest_collateral = Some((
+                        asset_id,
+                        account_reserve_data.deposit,
+                        value_e8,
+                    ));
+                }
+            }
+            if debts.contains(asset_id) {
+                let amount = account_reserve_data
+                    .debt
+                    .saturating_add(account_reserve_data.stable_debt);
+                let value_e8 = calculate_asset_amount_value_e8(
+                    &amount,
+                    &prices_e18[asset_id as usize],
+                    &decimal_multiplier,
+                );
+                if !largest_debt.is_some_and(|(_, _, v)| v >= value_e8) {
+                    largest_debt = Some((asset_id, amount, value_e8));
+                }
+            }
+        }
+        (
+            largest_collateral.map(|(asset_id, amount, _)| (asset_id, amount)),
+            largest_debt.map(|(asset_id, amount, _)| (asset_id, amount)),
         )
     }
 
@@ -1011,7 +2091,7 @@ impl LendingPoolStorage {
             account_datas,
             account_config,
             fee_reductions,
-            &self.get_assets_prices_e18()?,
+            &self.get_account_prices_e18(account_config)?,
         )?;
         Ok(())
     }
@@ -1034,23 +2114,135 @@ impl LendingPoolStorage {
         }
     }
 
-    pub fn calculate_liquidated_amount_and_ensure_collateralized(
+    /// Returns the maximal amount of `asset_to_repay_id` debt of the `account` that can be repaid in one liquidation.
+    /// If the reserve has no close factor set, the whole debt can be repaid.
+    /// The variable debt is considered unless the account has only the stable debt in `asset_to_repay_id`.
+    /// The target collateralization cap accounts only for the `penalty_part_e6` of the penalties that is actually paid.
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate_max_amount_to_repay(
         &self,
         account: &AccountId,
-        asset_to_repay_id: u32,
-        asset_to_take_id: u32,
-        asset_to_repay_price_e18: &u128,
-        asset_to_take_price_e18: &u128,
-        amount_to_repay: &Balance,
+        account_reserve_datas: &[Option<AccountReserveData>],
+        account_config: &AccountConfig,
+        fee_reductions: &FeeReductions,
+        prices_e18: &[u128],
+        asset_to_repay_id: AssetId,
+        asset_to_take_id: AssetId,
+        penalty_part_e6: &u128,
+        timestamp: &Timestamp,
     ) -> Result<Balance, LendingPoolError> {
-        let account_config = &self
-            .account_configs
-            .get(account)
-            .ok_or(LendingPoolError::NothingToRepay)?;
-        if (account_config.collaterals >> asset_to_take_id) & 1_u128 != 1 {
+        let asset_to_repay = self.id_to_asset.get(asset_to_repay_id).unwrap(); // asset_id exists => id_to_asset exists
+        let debt =
+            match self.account_debt_of(&asset_to_repay, account, timestamp)? {
+                0 => self.account_stable_debt_of(
+                    &asset_to_repay,
+                    account,
+                    timestamp,
+                )?,
+                debt => debt,
+            };
+
+        let close_factor =
+            match self.reserve_close_factors.get(asset_to_repay_id) {
+                Some(close_factor) => close_factor,
+                None => return Ok(debt),
+            };
+
+        let (collateral_power_e6, debt_power_e6) = self
+            .calculate_collateral_and_debt_powers_e6(
+                account_reserve_datas,
+                account_config,
+                fee_reductions,
+                prices_e18,
+            )?;
+
+        let market_rule = self.get_market_rule(account_config);
+        let repay_borrow_coefficient_e6 = market_rule
+            .get(asset_to_repay_id as usize)
+            .copied()
+            .flatten()
+            .and_then(|rule| rule.borrow_coefficient_e6)
+            .unwrap_or_default();
+        let take_collateral_coefficient_e6 = self
+            .ramp_down_collateral_coefficient_e6(
+                asset_to_take_id,
+                market_rule
+                    .get(asset_to_take_id as usize)
+                    .copied()
+                    .flatten()
+                    .and_then(|rule| rule.collateral_coefficient_e6)
+                    .unwrap_or_default(),
+            )?;
+        let mut total_penalty_e6: u128 = 0;
+        for asset_id in [asset_to_repay_id, asset_to_take_id] {
+            let penalty_e6 = market_rule
+                .get(asset_id as usize)
+                .copied()
+                .flatten()
+                .and_then(|rule| rule.penalty_e6)
+                .unwrap_or_default();
+            total_penalty_e6 = total_penalty_e6
+                .checked_add(mul_div(
+                    penalty_e6,
+                    *penalty_part_e6,
+                    E6_U128,
+                    Rounding::Down,
+                )?)
+                .ok_or(MathError::Overflow)?;
+        }
+
+        Ok(close_factor.max_amount_to_repay(
+            debt,
+            collateral_power_e6,
+            debt_power_e6,
+            prices_e18[asset_to_repay_id as usize],
+            self.get_decimal_multiplier(asset_to_repay_id),
+            repay_borrow_coefficient_e6,
+            take_collateral_coefficient_e6,
+            total_penalty_e6,
+        )?)
+    }
+
+    /// Caps `amount_to_repay` by the close factor of the `asset_to_repay_id` reserve (see `calculate_max_amount_to_repay`)
+    /// and calculates the amount of `asset_to_take_id` to be taken from the `account` for it.
+    /// It is the only place liquidations are priced at, so all the liquidation paths (plain, auction and flash) are capped.
+    /// Must be called with the `account`'s data from before the repay.
+    ///
+    /// # Returns
+    /// (amount_to_take, max_amount_to_repay)
+    #[allow(clippy::too_many_arguments)]
+    pub fn calculate_liquidated_amount_and_ensure_collateralized(
+        &self,
+        account: &AccountId,
+        account_reserve_datas: &[Option<AccountReserveData>],
+        account_config: &AccountConfig,
+        fee_reductions: &FeeReductions,
+        prices_e18: &[u128],
+        asset_to_repay_id: AssetId,
+        asset_to_take_id: AssetId,
+        amount_to_repay: &mut Balance,
+        penalty_part_e6: &u128,
+        timestamp: &Timestamp,
+    ) -> Result<(Balance, Balance), LendingPoolError> {
+        if !account_config.collaterals.contains(asset_to_take_id) {
             return Err(LendingPoolError::TakingNotACollateral);
         }
 
+        let max_amount_to_repay = self.calculate_max_amount_to_repay(
+            account,
+            account_reserve_datas,
+            account_config,
+            fee_reductions,
+            prices_e18,
+            asset_to_repay_id,
+            asset_to_take_id,
+            penalty_part_e6,
+            timestamp,
+        )?;
+        if *amount_to_repay > max_amount_to_repay {
+            *amount_to_repay = max_amount_to_repay;
+        }
+
         let reserve_to_repay_decimal_multiplier =
             self.get_decimal_multiplier(asset_to_repay_id);
 
@@ -1058,24 +2250,84 @@ impl LendingPoolStorage {
             self.get_decimal_multiplier(asset_to_take_id);
 
         let market_rule = self.get_market_rule(account_config);
-        let penalty_to_repay_e6 = get_penalty(&market_rule, asset_to_repay_id);
-        let penalty_to_take_e6 = get_penalty(&market_rule, asset_to_take_id);
+        // in the auction mode only a part of the penalties is paid
+        let penalty_to_repay_e6 = mul_div(
+            get_penalty(&market_rule, asset_to_repay_id),
+            *penalty_part_e6,
+            E6_U128,
+            Rounding::Down,
+        )?;
+        let penalty_to_take_e6 = mul_div(
+            get_penalty(&market_rule, asset_to_take_id),
+            *penalty_part_e6,
+            E6_U128,
+            Rounding::Down,
+        )?;
 
         let amount_to_take = calculate_amount_to_take(
             amount_to_repay,
-            asset_to_repay_price_e18,
-            asset_to_take_price_e18,
+            &prices_e18[asset_to_repay_id as usize],
+            &prices_e18[asset_to_take_id as usize],
             &reserve_to_repay_decimal_multiplier,
             &reserve_to_take_decimal_multiplier,
             &penalty_to_repay_e6,
             &penalty_to_take_e6,
         )?;
-        Ok(amount_to_take)
+        Ok((amount_to_take, max_amount_to_repay))
     }
 
     /*
     TIME-WEIGHTED UTILIZATION RATE CALCULATIONS
     */
+    /// Brings the reserve up to `timestamp` before its data is modified: snaps the utilization rate,
+    /// drifts the rate at target of the continuous interest rate model and updates the indexes.
+    /// Every index update of an action goes through this method, so the continuous model never misses the time the utilization held.
+    fn update_reserve_indexes(
+        &mut self,
+        asset_id: AssetId,
+        reserve_data: &ReserveData,
+        reserve_indexes_and_fees: &mut ReserveIndexesAndFees,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        self.store_utilization_rate_snapshot(
+            asset_id,
+            reserve_data,
+            timestamp,
+        )?;
+        self.drift_rate_at_target(asset_id, reserve_data, timestamp)?;
+        reserve_indexes_and_fees.update_indexes(reserve_data, timestamp)?;
+        Ok(())
+    }
+
+    /// Drifts the rate at target of the continuous interest rate model of the given asset up to the given timestamp.
+    /// Must be called before the reserve data is modified - with the utilization rate that held since the last update.
+    fn drift_rate_at_target(
+        &mut self,
+        asset_id: AssetId,
+        reserve_data: &ReserveData,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        if let Some(mut interest_rate_model) =
+            self.find_interest_rate_model(asset_id)
+        {
+            // with no debt the utilization is 0 (even if there is no deposit)
+            let utilization_rate_e6 = if reserve_data.total_debt == 0
+                && reserve_data.total_stable_debt == 0
+            {
+                0
+            } else {
+                reserve_data.current_utilization_rate_e6()?
+            };
+            if interest_rate_model
+                .drift_rate_at_target(utilization_rate_e6, *timestamp)?
+            {
+                self.interest_rate_model_v2
+                    .insert(asset_id, &interest_rate_model);
+            }
+        }
+        Ok(())
+    }
+
     /// Snap the utilization rate for the given asset at the given timestamp.
     /// To snap utilization a new time weighted entry is created.
     fn store_utilization_rate_snapshot(
@@ -1084,7 +2336,7 @@ impl LendingPoolStorage {
         reserve_data: &ReserveData,
         timestamp: &Timestamp,
     ) -> Result<(), LendingPoolError> {
-        let tw_index = self.tw_ur_indexes.get(asset_id).unwrap();
+        let tw_index = self.find_tw_ur_index(asset_id).unwrap();
 
         let last_tw_entry = self
             .tw_ur_entries
@@ -1124,57 +2376,45 @@ impl LendingPoolStorage {
             },
         );
 
-        self.tw_ur_indexes.insert(asset_id, &new_tw_index);
+        self.tw_ur_indexes_v2.insert(asset_id, &new_tw_index);
 
         Ok(())
     }
 
     /// Returns the time-weighted utilization rate for the given asset.
     /// The rate is calculated for the latest shortest period longer than the given period.
-    /// arg guessed_index is adjusted to the nearest index that would return a tw_entry
+    /// If `guessed_index` is given, it is adjusted to the nearest index that would return a tw_entry
     /// with smallest time diff between latest_tw_entry & tw_entry and is distant enough from the latest_tw_entry at the same time.
-    /// If timestamp entry at guessed_index is not distant enough (at least period) from the last entry, the function returns WrongIndex error.
+    /// If timestamp entry at guessed_index is not distant enough (at least period) from the last entry, the function returns `TwError::EntryTooRecent`
+    /// or `TwError::EntryOverwritten` if no entry in the ring is distant enough.
+    /// Otherwise the tw_entry is binary searched over the whole ring - a correct `guessed_index` only saves the gas.
     pub fn get_tw_ur_from_shortest_period_longer_than(
         &self,
         period: u64,
         asset_id: AssetId,
-        guessed_index: u32,
+        guessed_index: Option<u32>,
     ) -> Result<u32, LendingPoolError> {
-        let tw_index = self.tw_ur_indexes.get(asset_id).unwrap();
-        let last_tw_entry =
-            self.get_tw_ur_entry(asset_id, tw_index.value).unwrap();
-
-        let mut curr_apr_tw_index = TwIndex {
-            value: guessed_index,
+        let tw_index = self.find_tw_ur_index(asset_id).unwrap();
+        let get_entry = |index: u32| self.get_tw_ur_entry(asset_id, index);
+        let last_tw_entry = tw_index.valid_entry(get_entry(tw_index.value))?;
+
+        let appropriate_tw_entry = match guessed_index {
+            Some(guessed_index) => tw_index.walk_to_distant_entry(
+                period,
+                guessed_index,
+                &last_tw_entry,
+                &get_entry,
+            )?,
+            None => tw_index.search_distant_entry(
+                period,
+                &last_tw_entry,
+                &get_entry,
+            )?,
         };
 
-        let mut after_appropriate_tw_entry = self
-            .get_tw_ur_entry(asset_id, curr_apr_tw_index.next().value)
-            .ok_or(LendingPoolError::TwEntryInvalidIndex(0))?;
-
-        while last_tw_entry
-            .timestamp
-            .saturating_sub(after_appropriate_tw_entry.timestamp)
-            >= period
-        {
-            curr_apr_tw_index = curr_apr_tw_index.next();
-            after_appropriate_tw_entry = self
-                .get_tw_ur_entry(asset_id, curr_apr_tw_index.next().value)
-                .ok_or(LendingPoolError::TwEntryInvalidIndex(1))?;
-        }
-
-        let appropriate_tw_entry = self
-            .get_tw_ur_entry(asset_id, curr_apr_tw_index.value)
-            .ok_or(LendingPoolError::TwEntryInvalidIndex(2))?;
-
-        // if the entry is too recent
-        // may occur when while body has not executed even once
         let delta_timestamp = last_tw_entry
             .timestamp
             .saturating_sub(appropriate_tw_entry.timestamp);
-        if delta_timestamp < period {
-            return Err(LendingPoolError::TwEntryInvalidIndex(3));
-        }
 
         let delta_accumulator = last_tw_entry
             .accumulator
@@ -1197,6 +2437,71 @@ impl LendingPoolStorage {
     MANAGEMENT SECTION - methods responsible for changing parameters of the lending pool.
     Registering new assets, chaning the parameters, freezing, activating.
      */
+    pub fn is_timelock_enabled(&self) -> bool {
+        self.timelock_delay.get().unwrap_or_default() > 0
+    }
+
+    /// # Returns
+    /// (action_id, eta)
+    pub fn account_for_queue_manage_action(
+        &mut self,
+        action: &ManageAction,
+        proposer: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<(u32, Timestamp), LendingPoolError> {
+        if !self.is_timelock_enabled() {
+            return Err(LendingPoolError::TimelockDisabled);
+        }
+        let eta = timestamp
+            .checked_add(self.timelock_delay.get().unwrap_or_default())
+            .ok_or(MathError::Overflow)?;
+        let action_id = self.next_manage_action_id.get_or_default();
+        self.queued_manage_actions.insert(
+            action_id,
+            &QueuedManageAction {
+                action: action.clone(),
+                proposer: *proposer,
+                eta,
+            },
+        );
+        self.next_manage_action_id
+            .set(&(action_id.checked_add(1).ok_or(MathError::Overflow)?));
+        Ok((action_id, eta))
+    }
+
+    /// removes the queued action that is ready to be executed and has not expired.
+    pub fn account_for_execute_manage_action(
+        &mut self,
+        action_id: &u32,
+        timestamp: &Timestamp,
+    ) -> Result<QueuedManageAction, LendingPoolError> {
+        let queued_action = self
+            .queued_manage_actions
+            .get(action_id)
+            .ok_or(LendingPoolError::ManageActionNotQueued)?;
+        if *timestamp < queued_action.eta {
+            return Err(LendingPoolError::ManageActionNotReady);
+        }
+        if timestamp.saturating_sub(queued_action.eta)
+            > MANAGE_ACTION_GRACE_PERIOD
+        {
+            return Err(LendingPoolError::ManageActionExpired);
+        }
+        self.queued_manage_actions.remove(action_id);
+        Ok(queued_action)
+    }
+
+    pub fn account_for_cancel_manage_action(
+        &mut self,
+        action_id: &u32,
+    ) -> Result<(), LendingPoolError> {
+        if !self.queued_manage_actions.contains(action_id) {
+            return Err(LendingPoolError::ManageActionNotQueued);
+        }
+        self.queued_manage_actions.remove(action_id);
+        Ok(())
+    }
+
     pub fn account_for_price_feed_provider_change(
         &mut self,
         price_feed_provider: &AccountId,
@@ -1225,90 +2530,439 @@ impl LendingPoolStorage {
             return Err(LendingPoolError::AlreadyRegistered);
         }
         let id = self.next_asset_id.get_or_default();
+        reserve_fees.validate()?;
 
         self.asset_to_id.insert(asset, &id);
         self.id_to_asset.insert(id, asset);
-        self.reserve_datas.insert(id, reserve_data);
+        self.reserve_datas_v2.insert(id, reserve_data);
         self.reserve_restrictions.insert(id, reserve_restrictions);
         self.reserve_decimal_multiplier
             .insert(id, decimal_multiplier);
-        self.reserve_indexes_and_fees
+        self.reserve_indexes_and_fees_v2
             .insert(id, &ReserveIndexesAndFees::new(timestamp, reserve_fees));
 
-        self.tw_ur_indexes.insert(id, &TwIndex::new());
+        self.tw_ur_indexes_v2
+            .insert(id, &TwIndex::new(DEFAULT_TW_INDEX_SIZE));
+
+        if let Some(model) = interest_rate_model {
+            self.interest_rate_model_v2.insert(id, model);
+        };
+
+        self.next_asset_id
+            .set(&(id.checked_add(1).ok_or(MathError::Overflow)?));
+        Ok(())
+    }
+
+    pub fn account_for_set_abacus_tokens(
+        &mut self,
+        asset: &AccountId,
+        reserve_abacus_tokens: &ReserveAbacusTokens,
+    ) -> Result<(), LendingPoolError> {
+        if !self.asset_to_id.contains(asset) {
+            return Err(LendingPoolError::AssetNotRegistered);
+        }
+
+        self.reserve_abacus_tokens_v2
+            .insert(asset, reserve_abacus_tokens);
+
+        Ok(())
+    }
+
+    pub fn account_for_market_rule_change(
+        &mut self,
+        account: &AccountId,
+        market_rule_id: RuleId,
+    ) -> Result<(), LendingPoolError> {
+        self.ensure_market_rule_in_bounds(market_rule_id)?;
+
+        let mut account_config = self.get_account_config(account);
+
+        account_config.market_rule_id = market_rule_id;
+
+        self.store_account_config(account, &account_config);
+
+        Ok(())
+    }
+
+    pub fn account_for_changing_activity(
+        &mut self,
+        asset: &AccountId,
+        active: bool,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        if active && self.is_reserve_closed(asset_id) {
+            return Err(ReserveDelistingError::Closed.into());
+        }
+        let mut reserve_data = self.get_reserve_data(asset_id);
+        reserve_data.set_is_active(active)?;
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        Ok(())
+    }
+
+    pub fn account_for_changing_is_frozen(
+        &mut self,
+        asset: &AccountId,
+        new_is_frozen: bool,
+        reason_code: u32,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let mut reserve_data = self.get_reserve_data(asset_id);
+        reserve_data.set_is_frozen(new_is_frozen)?;
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        if new_is_frozen {
+            self.reserve_freezes.insert(
+                asset_id,
+                &ReserveFreeze {
+                    reason_code,
+                    since: *timestamp,
+                },
+            );
+        } else {
+            self.reserve_freezes.remove(asset_id);
+        }
+        Ok(())
+    }
+
+    pub fn account_for_reserve_restricitions_change(
+        &mut self,
+        asset: &AccountId,
+        reserve_restrictions: &ReserveRestrictions,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        self.reserve_restrictions
+            .insert(asset_id, reserve_restrictions);
+        Ok(())
+    }
+
+    pub fn account_for_reserve_isolation_change(
+        &mut self,
+        asset: &AccountId,
+        reserve_isolation: &ReserveIsolation,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let current_isolation = self.get_reserve_isolation(asset_id);
+        // accounts in the legacy layouts are not counted in `reserve_collateral_users`
+        if !current_isolation.is_isolated()
+            && reserve_isolation.is_isolated()
+            && !self.accounts_migrated.get().unwrap_or(false)
+        {
+            return Err(LendingPoolError::AccountsNotMigrated);
+        }
+        current_isolation.ensure_can_be_changed_to(
+            reserve_isolation,
+            self.reserve_collateral_users
+                .get(asset_id)
+                .unwrap_or_default(),
+        )?;
+        self.reserve_isolations.insert(asset_id, reserve_isolation);
+        Ok(())
+    }
+
+    pub fn account_for_reserve_account_limits_change(
+        &mut self,
+        asset: &AccountId,
+        reserve_account_limits: &ReserveAccountLimits,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        reserve_account_limits.validate()?;
+        self.reserve_account_limits
+            .insert(asset_id, reserve_account_limits);
+        Ok(())
+    }
+
+    pub fn account_for_start_delisting(
+        &mut self,
+        asset: &AccountId,
+        ramp_duration: &Timestamp,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        if self.reserve_delistings.contains(asset_id) {
+            return Err(ReserveDelistingError::AlreadyDelisting.into());
+        }
+        self.reserve_delistings.insert(
+            asset_id,
+            &ReserveDelisting::new(*timestamp, *ramp_duration),
+        );
+        Ok(())
+    }
+
+    /// closes the delisted reserve that has no deposits and no debts left and deactivates it.
+    pub fn account_for_close_reserve(
+        &mut self,
+        asset: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let mut delisting = self
+            .reserve_delistings
+            .get(asset_id)
+            .ok_or(ReserveDelistingError::NotInSettlement)?;
+        delisting.ensure_in_settlement(timestamp)?;
 
-        if let Some(model) = interest_rate_model {
-            self.interest_rate_model.insert(id, model);
-        };
+        let mut reserve_data = self.get_reserve_data(asset_id);
+        if reserve_data.total_deposit != 0
+            || reserve_data.total_debt != 0
+            || reserve_data.total_stable_debt != 0
+        {
+            return Err(ReserveDelistingError::ReserveNotEmpty.into());
+        }
+        if reserve_data.activated {
+            reserve_data.set_is_active(false)?;
+            self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        }
 
-        self.next_asset_id
-            .set(&(id.checked_add(1).ok_or(MathError::Overflow)?));
+        delisting.is_closed = true;
+        self.reserve_delistings.insert(asset_id, &delisting);
         Ok(())
     }
 
-    pub fn account_for_set_abacus_tokens(
+    pub fn account_for_liquidation_auction_params_change(
         &mut self,
-        asset: &AccountId,
-        reserve_abacus_tokens: &ReserveAbacusTokens,
+        market_rule_id: &RuleId,
+        auction_params: &Option<LiquidationAuctionParams>,
     ) -> Result<(), LendingPoolError> {
-        if !self.asset_to_id.contains(asset) {
-            return Err(LendingPoolError::AssetNotRegistered);
+        self.ensure_market_rule_in_bounds(*market_rule_id)?;
+        match auction_params {
+            Some(params) => {
+                params.validate()?;
+                self.market_rule_liquidation_auctions
+                    .insert(market_rule_id, params);
+            }
+            None => {
+                self.market_rule_liquidation_auctions.remove(market_rule_id)
+            }
         }
+        Ok(())
+    }
 
-        self.reserve_abacus_tokens
-            .insert(asset, reserve_abacus_tokens);
-
+    pub fn account_for_start_liquidation_auction(
+        &mut self,
+        account: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let account_config = self.get_account_config(account);
+        if !self
+            .market_rule_liquidation_auctions
+            .contains(account_config.market_rule_id)
+        {
+            return Err(LiquidationAuctionError::AuctionsDisabled.into());
+        }
+        if self.liquidation_auctions.contains(account) {
+            return Err(LiquidationAuctionError::AuctionAlreadyStarted.into());
+        }
+        let index = self.liquidation_auctions_count.get_or_default();
+        self.liquidation_auction_accounts.insert(index, account);
+        self.liquidation_auctions.insert(
+            account,
+            &LiquidationAuction {
+                start_timestamp: *timestamp,
+                index,
+            },
+        );
+        self.liquidation_auctions_count
+            .set(&(index.checked_add(1).ok_or(MathError::Overflow)?));
         Ok(())
     }
 
-    pub fn account_for_market_rule_change(
+    pub fn account_for_close_liquidation_auction(
         &mut self,
         account: &AccountId,
-        market_rule_id: RuleId,
     ) -> Result<(), LendingPoolError> {
-        self.ensure_market_rule_in_bounds(market_rule_id)?;
+        let auction = self
+            .liquidation_auctions
+            .get(account)
+            .ok_or(LiquidationAuctionError::AuctionNotStarted)?;
+        // the last auction is moved to the place of the closed one
+        let last_index = self
+            .liquidation_auctions_count
+            .get_or_default()
+            .checked_sub(1)
+            .ok_or(MathError::Underflow)?;
+        if auction.index != last_index {
+            let last_account =
+                self.liquidation_auction_accounts.get(last_index).unwrap();
+            let mut last_auction =
+                self.liquidation_auctions.get(last_account).unwrap();
+            last_auction.index = auction.index;
+            self.liquidation_auctions
+                .insert(last_account, &last_auction);
+            self.liquidation_auction_accounts
+                .insert(auction.index, &last_account);
+        }
+        self.liquidation_auction_accounts.remove(last_index);
+        self.liquidation_auctions.remove(account);
+        self.liquidation_auctions_count.set(&last_index);
+        Ok(())
+    }
 
-        let mut account_config = self.get_account_config(account);
+    /// Returns the part of the penalties (10^6 = 100%) paid while liquidating the `account` or None if the full penalties are paid (the auction mode is disabled).
+    /// The debt in a delisted asset (in the settlement stage) is always liquidated with the full penalties.
+    pub fn get_liquidation_penalty_part_e6(
+        &self,
+        account: &AccountId,
+        asset_to_repay: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<Option<u128>, LendingPoolError> {
+        let account_config = self.get_account_config(account);
+        let auction_params = match self
+            .market_rule_liquidation_auctions
+            .get(account_config.market_rule_id)
+        {
+            Some(auction_params) => auction_params,
+            None => return Ok(None),
+        };
+        if self
+            .ensure_delisting_settlement(asset_to_repay, timestamp)
+            .is_ok()
+        {
+            return Ok(None);
+        }
+        let auction = self
+            .liquidation_auctions
+            .get(account)
+            .ok_or(LiquidationAuctionError::AuctionNotStarted)?;
+        Ok(Some(
+            auction_params
+                .penalty_part_e6(&auction.start_timestamp, timestamp)?,
+        ))
+    }
 
-        account_config.market_rule_id = market_rule_id;
+    /// ensures that the `action` is paused neither globally nor in the reserve of the `asset`.
+    pub fn ensure_action_not_paused(
+        &self,
+        asset: &AccountId,
+        action: PausableAction,
+    ) -> Result<(), LendingPoolError> {
+        self.global_actions_pause
+            .get_or_default()
+            .ensure_not_paused(action)?;
+        let asset_id = self.asset_id(asset)?;
+        self.reserve_actions_pauses
+            .get(asset_id)
+            .unwrap_or_default()
+            .ensure_not_paused(action)?;
+        Ok(())
+    }
 
-        self.account_configs.insert(account, &account_config);
+    pub fn ensure_actions_not_paused(
+        &self,
+        actions: &[Action],
+    ) -> Result<(), LendingPoolError> {
+        for action in actions {
+            self.ensure_action_not_paused(
+                &action.args.asset,
+                action.op.into(),
+            )?;
+        }
+        Ok(())
+    }
 
+    pub fn account_for_global_action_pause_change(
+        &mut self,
+        action: PausableAction,
+        paused: bool,
+    ) -> Result<(), LendingPoolError> {
+        let mut actions_pause = self.global_actions_pause.get_or_default();
+        actions_pause.set_paused(action, paused)?;
+        self.global_actions_pause.set(&actions_pause);
         Ok(())
     }
 
-    pub fn account_for_changing_activity(
+    pub fn account_for_reserve_action_pause_change(
         &mut self,
         asset: &AccountId,
-        active: bool,
+        action: PausableAction,
+        paused: bool,
     ) -> Result<(), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
-        let mut reserve_data = self.get_reserve_data(asset_id);
-        reserve_data.set_is_active(active)?;
-        self.reserve_datas.insert(asset_id, &reserve_data);
+        let mut actions_pause = self
+            .reserve_actions_pauses
+            .get(asset_id)
+            .unwrap_or_default();
+        actions_pause.set_paused(action, paused)?;
+        self.reserve_actions_pauses.insert(asset_id, &actions_pause);
         Ok(())
     }
 
-    pub fn account_for_changing_is_frozen(
+    pub fn ensure_delisting_settlement(
+        &self,
+        asset: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        self.reserve_delistings
+            .get(asset_id)
+            .ok_or(ReserveDelistingError::NotInSettlement)?
+            .ensure_in_settlement(timestamp)?;
+        Ok(())
+    }
+
+    pub fn get_delisting_stage(
+        &self,
+        asset_id: AssetId,
+        timestamp: &Timestamp,
+    ) -> DelistingStage {
+        match self.reserve_delistings.get(asset_id) {
+            Some(delisting) => delisting.stage(timestamp),
+            None => DelistingStage::Listed,
+        }
+    }
+
+    fn is_reserve_closed(&self, asset_id: AssetId) -> bool {
+        self.reserve_delistings
+            .get(asset_id)
+            .is_some_and(|delisting| delisting.is_closed)
+    }
+
+    /// returns the `collateral_coefficient_e6` of the asset ramped down if the asset is being delisted.
+    fn ramp_down_collateral_coefficient_e6(
+        &self,
+        asset_id: AssetId,
+        collateral_coefficient_e6: u128,
+    ) -> Result<u128, LendingPoolError> {
+        match self.reserve_delistings.get(asset_id) {
+            Some(delisting) => Ok(delisting.ramped_collateral_coefficient_e6(
+                collateral_coefficient_e6,
+                &ink::env::block_timestamp::<DefaultEnvironment>(),
+            )?),
+            None => Ok(collateral_coefficient_e6),
+        }
+    }
+
+    pub fn account_for_close_factor_change(
         &mut self,
         asset: &AccountId,
-        new_is_frozen: bool,
+        close_factor: &Option<CloseFactor>,
     ) -> Result<(), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
-        let mut reserve_data = self.get_reserve_data(asset_id);
-        reserve_data.set_is_frozen(new_is_frozen)?;
-        self.reserve_datas.insert(asset_id, &reserve_data);
+        match close_factor {
+            Some(close_factor) => {
+                close_factor.validate()?;
+                self.reserve_close_factors.insert(asset_id, close_factor);
+            }
+            None => self.reserve_close_factors.remove(asset_id),
+        }
         Ok(())
     }
 
-    pub fn account_for_reserve_restricitions_change(
+    pub fn account_for_stable_rate_model_change(
         &mut self,
         asset: &AccountId,
-        reserve_restrictions: &ReserveRestrictions,
+        stable_rate_model: &Option<StableRateModel>,
     ) -> Result<(), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
-        self.reserve_restrictions
-            .insert(asset_id, reserve_restrictions);
+        if self.find_interest_rate_model(asset_id).is_none() {
+            return Err(LendingPoolError::AssetIsProtocolStablecoin);
+        }
+        match stable_rate_model {
+            Some(model) => self.stable_rate_models.insert(asset_id, model),
+            None => {
+                self.stable_rate_models.remove(asset_id);
+                None
+            }
+        };
         Ok(())
     }
 
@@ -1319,38 +2973,90 @@ impl LendingPoolStorage {
         timestamp: &Timestamp,
     ) -> Result<(), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
-        if !self.interest_rate_model.contains(asset_id) {
+        if self.find_interest_rate_model(asset_id).is_none() {
             return Err(LendingPoolError::AssetIsProtocolStablecoin);
         }
         let mut reserve_data = self.get_reserve_data(asset_id);
         let mut reserve_indexes_and_fees =
             self.get_reserve_indexes_and_fees(asset_id);
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
+        self.update_reserve_indexes(
+            asset_id,
+            &reserve_data,
+            &mut reserve_indexes_and_fees,
+            timestamp,
+        )?;
         reserve_data.recalculate_current_rates(interest_rate_model)?;
 
-        self.reserve_datas.insert(asset_id, &reserve_data);
-        self.interest_rate_model
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
+        self.interest_rate_model_v2
             .insert(asset_id, interest_rate_model);
-        self.reserve_indexes_and_fees
+        self.reserve_indexes_and_fees_v2
+            .insert(asset_id, &reserve_indexes_and_fees);
+        Ok(())
+    }
+
+    /// accrues the interest with the current mode up to `timestamp` and sets the new mode.
+    pub fn account_for_accrual_mode_change(
+        &mut self,
+        asset: &AccountId,
+        accrual_mode: &InterestAccrualMode,
+        timestamp: &Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        if self.find_interest_rate_model(asset_id).is_none() {
+            return Err(LendingPoolError::AssetIsProtocolStablecoin);
+        }
+        let reserve_data = self.get_reserve_data(asset_id);
+        let mut reserve_indexes_and_fees =
+            self.get_reserve_indexes_and_fees(asset_id);
+        self.update_reserve_indexes(
+            asset_id,
+            &reserve_data,
+            &mut reserve_indexes_and_fees,
+            timestamp,
+        )?;
+        reserve_indexes_and_fees.accrual_mode = *accrual_mode;
+        self.reserve_indexes_and_fees_v2
             .insert(asset_id, &reserve_indexes_and_fees);
         Ok(())
     }
 
+    /// Resizes the ring of the TW utilization rate entries of the reserve.
+    /// The ring is restarted from the latest entry (moved to the index 0) so no entry is overwritten out of order.
+    pub fn account_for_tw_ur_size_change(
+        &mut self,
+        asset: &AccountId,
+        size: u32,
+    ) -> Result<(), LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let tw_index = self.find_tw_ur_index(asset_id).unwrap();
+        let latest_tw_entry = self.get_tw_ur_entry(asset_id, tw_index.value);
+        let resized_tw_index =
+            tw_index.resized(size, latest_tw_entry.as_ref())?;
+        if let Some(latest_tw_entry) = latest_tw_entry {
+            // the previous copy would be taken for a valid entry in the resized ring
+            self.tw_ur_entries.remove((asset_id, tw_index.value));
+            self.tw_ur_entries
+                .insert((asset_id, resized_tw_index.value), &latest_tw_entry);
+        }
+        self.tw_ur_indexes_v2.insert(asset_id, &resized_tw_index);
+        Ok(())
+    }
+
     pub fn account_for_reserve_fees_change(
         &mut self,
         asset: &AccountId,
         reserve_fees: &ReserveFees,
     ) -> Result<(), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
-        if !self.interest_rate_model.contains(asset_id) {
+        if self.find_interest_rate_model(asset_id).is_none() {
             return Err(LendingPoolError::AssetIsProtocolStablecoin);
         }
+        reserve_fees.validate()?;
         let mut reserve_indexes_and_fees =
             self.get_reserve_indexes_and_fees(asset_id);
         reserve_indexes_and_fees.fees = *reserve_fees;
-        self.reserve_indexes_and_fees
+        self.reserve_indexes_and_fees_v2
             .insert(asset_id, &reserve_indexes_and_fees);
         Ok(())
     }
@@ -1377,9 +3083,10 @@ impl LendingPoolStorage {
         while (market_rule.len() as u32) <= asset_id {
             market_rule.push(None);
         }
-        market_rule[asset_id as usize] = Some(*asset_rules);
         let old_asset_rule = market_rule[asset_id as usize];
         asset_rules.validate_new_rule(&old_asset_rule)?;
+        asset_rules.validate_bounds()?;
+        market_rule[asset_id as usize] = Some(*asset_rules);
         self.market_rules.insert(market_rule_id, &market_rule);
         Ok(())
     }
@@ -1390,15 +3097,111 @@ impl LendingPoolStorage {
         debt_rate_e18: &u64,
     ) -> Result<(), LendingPoolError> {
         let asset_id = self.asset_id(asset)?;
-        if self.interest_rate_model.contains(asset_id) {
+        if self.find_interest_rate_model(asset_id).is_some() {
             return Err(LendingPoolError::AssetIsNotProtocolStablecoin);
         }
         let mut reserve_data = self.get_reserve_data(asset_id);
         reserve_data.current_debt_rate_e18 = *debt_rate_e18;
-        self.reserve_datas.insert(asset_id, &reserve_data);
+        self.reserve_datas_v2.insert(asset_id, &reserve_data);
         Ok(())
     }
 
+    /*
+    MIGRATION SECTION - methods moving the data stored in the legacy layouts to the versioned storage keys.
+    */
+
+    /// moves the data of the reserve stored in the legacy layouts to the current storage.
+    /// The data that is already stored in the current layout is kept.
+    ///
+    /// # Returns
+    /// true if there was any data in the legacy layouts.
+    pub fn migrate_reserve(&mut self, asset_id: AssetId) -> bool {
+        let mut migrated = false;
+        if let Some(legacy) = self.reserve_datas.take(asset_id) {
+            if !self.reserve_datas_v2.contains(asset_id) {
+                self.reserve_datas_v2
+                    .insert(asset_id, &ReserveData::from(legacy));
+            }
+            migrated = true;
+        }
+        if let Some(legacy) = self.reserve_indexes_and_fees.take(asset_id) {
+            if !self.reserve_indexes_and_fees_v2.contains(asset_id) {
+                self.reserve_indexes_and_fees_v2
+                    .insert(asset_id, &ReserveIndexesAndFees::from(legacy));
+            }
+            migrated = true;
+        }
+        if let Some(legacy) = self.tw_ur_indexes.take(asset_id) {
+            if !self.tw_ur_indexes_v2.contains(asset_id) {
+                self.tw_ur_indexes_v2
+                    .insert(asset_id, &TwIndex::from(legacy));
+            }
+            migrated = true;
+        }
+        if let Some(legacy) = self.interest_rate_model.take(asset_id) {
+            if !self.interest_rate_model_v2.contains(asset_id) {
+                self.interest_rate_model_v2
+                    .insert(asset_id, &InterestRateModel::from(legacy));
+            }
+            migrated = true;
+        }
+        if let Some(asset) = self.id_to_asset.get(asset_id) {
+            if let Some(legacy) = self.reserve_abacus_tokens.take(asset) {
+                if !self.reserve_abacus_tokens_v2.contains(asset) {
+                    self.reserve_abacus_tokens_v2
+                        .insert(asset, &ReserveAbacusTokens::from(legacy));
+                }
+                migrated = true;
+            }
+        }
+        migrated
+    }
+
+    /// moves the data of the `account` stored in the legacy layouts to the current storage.
+    /// The data that is already stored in the current layout is kept.
+    ///
+    /// # Returns
+    /// true if there was any data in the legacy layouts.
+    pub fn migrate_account(&mut self, account: &AccountId) -> bool {
+        let mut migrated = false;
+        if let Some(legacy) = self.account_configs.take(account) {
+            if !self.account_configs_v2.contains(account) {
+                self.store_account_config(
+                    account,
+                    &AccountConfig::from(legacy),
+                );
+            }
+            migrated = true;
+        }
+        if let Some(legacy) = self.account_reserve_datas.take(account) {
+            if !self.account_reserve_datas_v2.contains(account) {
+                self.account_reserve_datas_v2
+                    .insert(account, &account_reserve_datas_from_v1(legacy));
+            }
+            migrated = true;
+        }
+        migrated
+    }
+
+    /// advances the `accounts_migration_counter` if accounts with counters in range [`from_counter`, `to_counter`) were migrated
+    /// and sets `accounts_migrated` once it reaches the `next_counter` of the account registrar.
+    pub fn account_for_accounts_migration(
+        &mut self,
+        from_counter: u128,
+        to_counter: u128,
+        next_counter: u128,
+    ) {
+        let mut migration_counter =
+            self.accounts_migration_counter.get().unwrap_or(0);
+        if from_counter <= migration_counter && migration_counter < to_counter {
+            migration_counter = to_counter.min(next_counter);
+            self.accounts_migration_counter.set(&migration_counter);
+        }
+        if migration_counter >= next_counter {
+            self.accounts_migrated.set(&true);
+        }
+    }
+
     /*
         SECTION REST - getters, setters, ensure methods, etc.
     */
@@ -1413,9 +3216,7 @@ impl LendingPoolStorage {
         let mut reserve_indexes_and_fees =
             self.get_reserve_indexes_and_fees(asset_id);
 
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
+        reserve_indexes_and_fees.update_indexes(&reserve_data, timestamp)?;
 
         Ok(reserve_data.total_deposit)
     }
@@ -1433,9 +3234,7 @@ impl LendingPoolStorage {
         let (mut account_reserve_data, _) =
             self.get_account_reserve_data(asset_id, account);
 
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
+        reserve_indexes_and_fees.update_indexes(&reserve_data, timestamp)?;
         account_reserve_data.accumulate_account_interest(
             &reserve_indexes_and_fees.indexes,
             &mut reserve_indexes_and_fees.fees,
@@ -1454,9 +3253,7 @@ impl LendingPoolStorage {
         let reserve_data = self.get_reserve_data(asset_id);
         let mut reserve_indexes_and_fees =
             self.get_reserve_indexes_and_fees(asset_id);
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
+        reserve_indexes_and_fees.update_indexes(&reserve_data, timestamp)?;
 
         Ok(reserve_data.total_debt)
     }
@@ -1473,9 +3270,7 @@ impl LendingPoolStorage {
             self.get_reserve_indexes_and_fees(asset_id);
         let (mut account_reserve_data, _) =
             self.get_account_reserve_data(asset_id, account);
-        reserve_indexes_and_fees
-            .indexes
-            .update(&reserve_data, timestamp)?;
+        reserve_indexes_and_fees.update_indexes(&reserve_data, timestamp)?;
         account_reserve_data.accumulate_account_interest(
             &reserve_indexes_and_fees.indexes,
             &mut reserve_indexes_and_fees.fees,
@@ -1485,6 +3280,28 @@ impl LendingPoolStorage {
         Ok(account_reserve_data.debt)
     }
 
+    pub fn total_stable_debt_of(
+        &self,
+        asset: &AccountId,
+    ) -> Result<Balance, LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        Ok(self.get_reserve_data(asset_id).total_stable_debt)
+    }
+
+    pub fn account_stable_debt_of(
+        &self,
+        asset: &AccountId,
+        account: &AccountId,
+        timestamp: &Timestamp,
+    ) -> Result<Balance, LendingPoolError> {
+        let asset_id = self.asset_id(asset)?;
+        let (mut account_reserve_data, _) =
+            self.get_account_reserve_data(asset_id, account);
+        account_reserve_data.accumulate_account_stable_interest(timestamp)?;
+
+        Ok(account_reserve_data.stable_debt)
+    }
+
     fn insert_account_data(
         &mut self,
         mut account_datas: Vec<Option<AccountReserveData>>,
@@ -1492,8 +3309,9 @@ impl LendingPoolStorage {
         updated_account_data: AccountReserveData,
         of: &AccountId,
     ) {
-        account_datas.insert(asset_id as usize, Some(updated_account_data));
-        self.account_reserve_datas.insert(of, &account_datas);
+        // `account_datas` were resized to fit all registered assets
+        account_datas[asset_id as usize] = Some(updated_account_data);
+        self.account_reserve_datas_v2.insert(of, &account_datas);
     }
 
     pub fn get_all_registered_assets(&self) -> Vec<AccountId> {
@@ -1519,13 +3337,12 @@ impl LendingPoolStorage {
             .collect()
     }
 
-    fn get_account_datas(
+    pub fn get_account_datas(
         &self,
         account: &AccountId,
     ) -> Vec<Option<AccountReserveData>> {
         let mut account_datas = self
-            .account_reserve_datas
-            .get(account)
+            .find_account_datas(account)
             .unwrap_or(self.get_account_reserve_datas_defaults());
 
         let next_id = self.next_asset_id.get().unwrap_or(0) as usize;
@@ -1575,35 +3392,208 @@ impl LendingPoolStorage {
         self.reserve_restrictions.get(asset_id).unwrap() // asset_id exists => reserve_restrictions exists
     }
 
+    fn get_reserve_isolation(&self, asset_id: u32) -> ReserveIsolation {
+        self.reserve_isolations.get(asset_id).unwrap_or_default()
+    }
+
+    fn get_reserve_account_limits(
+        &self,
+        asset_id: u32,
+    ) -> ReserveAccountLimits {
+        self.reserve_account_limits
+            .get(asset_id)
+            .unwrap_or_default()
+    }
+
     fn get_reserve_indexes_and_fees(
         &self,
         asset_id: u32,
     ) -> ReserveIndexesAndFees {
-        self.reserve_indexes_and_fees.get(asset_id).unwrap() // asset_id exists => reserve_indexes_and_fees exists
+        self.find_reserve_indexes_and_fees(asset_id).unwrap() // asset_id exists => reserve_indexes_and_fees exists
     }
 
     fn get_reserve_data(&self, asset_id: u32) -> ReserveData {
-        self.reserve_datas.get(asset_id).unwrap() // asset_id exists => reserve_data exists
+        self.find_reserve_data(asset_id).unwrap() // asset_id exists => reserve_data exists
+    }
+
+    /// returns the reserve data of `asset_id`, converted from the legacy layout if the reserve was not migrated yet.
+    pub fn find_reserve_data(&self, asset_id: AssetId) -> Option<ReserveData> {
+        self.reserve_datas_v2
+            .get(asset_id)
+            .or_else(|| self.reserve_datas.get(asset_id).map(ReserveData::from))
+    }
+
+    /// returns the indexes and fees of `asset_id`, converted from the legacy layout if the reserve was not migrated yet.
+    pub fn find_reserve_indexes_and_fees(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<ReserveIndexesAndFees> {
+        self.reserve_indexes_and_fees_v2.get(asset_id).or_else(|| {
+            self.reserve_indexes_and_fees
+                .get(asset_id)
+                .map(ReserveIndexesAndFees::from)
+        })
+    }
+
+    /// returns the TW index of `asset_id`, converted from the legacy layout if the reserve was not migrated yet.
+    pub fn find_tw_ur_index(&self, asset_id: AssetId) -> Option<TwIndex> {
+        self.tw_ur_indexes_v2
+            .get(asset_id)
+            .or_else(|| self.tw_ur_indexes.get(asset_id).map(TwIndex::from))
+    }
+
+    /// returns the interest rate model of `asset_id`, converted from the legacy layout if the reserve was not migrated yet.
+    pub fn find_interest_rate_model(
+        &self,
+        asset_id: AssetId,
+    ) -> Option<InterestRateModel> {
+        self.interest_rate_model_v2.get(asset_id).or_else(|| {
+            self.interest_rate_model
+                .get(asset_id)
+                .map(InterestRateModel::from)
+        })
+    }
+
+    /// returns the abacus tokens of the `asset` reserve, converted from the legacy layout if the reserve was not migrated yet.
+    pub fn find_reserve_abacus_tokens(
+        &self,
+        asset: &AccountId,
+    ) -> Option<ReserveAbacusTokens> {
+        self.reserve_abacus_tokens_v2.get(asset).or_else(|| {
+            self.reserve_abacus_tokens
+                .get(asset)
+                .map(ReserveAbacusTokens::from)
+        })
     }
 
     fn get_decimal_multiplier(&self, asset_id: u32) -> u128 {
         self.reserve_decimal_multiplier.get(asset_id).unwrap() // asset_id exists => reserve_decimal_multiplier exists
     }
 
-    fn get_account_config(&self, account: &AccountId) -> AccountConfig {
-        self.account_configs.get(account).unwrap_or_default()
+    pub fn get_account_config(&self, account: &AccountId) -> AccountConfig {
+        self.find_account_config(account).unwrap_or_default()
+    }
+
+    /// stores the `account`'s config and updates `reserve_collateral_users` of the assets turned on or off as collateral.
+    fn store_account_config(
+        &mut self,
+        account: &AccountId,
+        account_config: &AccountConfig,
+    ) {
+        let stored_collaterals = self
+            .account_configs_v2
+            .get(account)
+            .unwrap_or_default()
+            .collaterals;
+        for asset_id in account_config.collaterals.iter() {
+            if !stored_collaterals.contains(asset_id) {
+                let users = self
+                    .reserve_collateral_users
+                    .get(asset_id)
+                    .unwrap_or_default();
+                self.reserve_collateral_users
+                    .insert(asset_id, &users.saturating_add(1));
+            }
+        }
+        for asset_id in stored_collaterals.iter() {
+            if !account_config.collaterals.contains(asset_id) {
+                let users = self
+                    .reserve_collateral_users
+                    .get(asset_id)
+                    .unwrap_or_default();
+                self.reserve_collateral_users
+                    .insert(asset_id, &users.saturating_sub(1));
+            }
+        }
+        self.account_configs_v2.insert(account, account_config);
+    }
+
+    /// returns the `account`'s config, converted from the legacy layout if the account was not migrated yet.
+    fn find_account_config(
+        &self,
+        account: &AccountId,
+    ) -> Option<AccountConfig> {
+        self.account_configs_v2.get(account).or_else(|| {
+            self.account_configs.get(account).map(AccountConfig::from)
+        })
+    }
+
+    /// returns the `account`'s reserve datas, converted from the legacy layout if the account was not migrated yet.
+    fn find_account_datas(
+        &self,
+        account: &AccountId,
+    ) -> Option<Vec<Option<AccountReserveData>>> {
+        self.account_reserve_datas_v2.get(account).or_else(|| {
+            self.account_reserve_datas
+                .get(account)
+                .map(account_reserve_datas_from_v1)
+        })
+    }
+
+    /// returns prices of all registered assets ordered by `asset_id`. The price of a closed reserve is 0 (it is not queried).
+    pub fn get_assets_prices_e18(&self) -> Result<Vec<u128>, LendingPoolError> {
+        let mut all_assets = AssetsBitmap::default();
+        for asset_id in 0..self.next_asset_id.get().unwrap_or(0) {
+            all_assets.insert(asset_id);
+        }
+        self.get_prices_e18_of(&all_assets)
+    }
+
+    /// returns prices of the assets the `account_config` uses as collateral or has debt in, ordered by `asset_id`.
+    /// Prices of the other assets are 0 (they are not queried), so a failing feed of an unrelated asset doesn't affect the account.
+    pub fn get_account_prices_e18(
+        &self,
+        account_config: &AccountConfig,
+    ) -> Result<Vec<u128>, LendingPoolError> {
+        self.get_prices_e18_of(&get_priced_assets(account_config))
+    }
+
+    /// returns prices of `assets` ordered by `asset_id`. The price of an asset not in `assets` or of a closed reserve is 0 (it is not queried).
+    fn get_prices_e18_of(
+        &self,
+        assets: &AssetsBitmap,
+    ) -> Result<Vec<u128>, LendingPoolError> {
+        let assets_ids: Vec<AssetId> = assets
+            .iter()
+            .filter(|asset_id| !self.is_reserve_closed(*asset_id))
+            .collect();
+        let mut prices_e18 =
+            vec![0; self.next_asset_id.get().unwrap_or(0) as usize];
+        if assets_ids.is_empty() {
+            return Ok(prices_e18);
+        }
+        let assets_to_price: Vec<AccountId> = assets_ids
+            .iter()
+            .map(|asset_id| self.id_to_asset.get(asset_id).unwrap()) // asset_id exists => asset exists
+            .collect();
+        let price_feeder: PriceFeedRef =
+            self.price_feed_provider.get().unwrap().into();
+        let fetched_prices_e18 = price_feeder
+            .call()
+            .get_latest_prices(assets_to_price)
+            .call_v1()
+            .invoke()?;
+        for (asset_id, price_e18) in assets_ids.iter().zip(fetched_prices_e18) {
+            prices_e18[*asset_id as usize] = price_e18;
+        }
+        Ok(prices_e18)
     }
 
-    fn get_assets_prices_e18(&self) -> Result<Vec<u128>, LendingPoolError> {
-        let all_assets = self.get_all_registered_assets();
+    fn get_asset_price_e18(
+        &self,
+        asset_id: u32,
+    ) -> Result<u128, LendingPoolError> {
+        let asset = self.id_to_asset.get(asset_id).unwrap(); // asset_id exists => asset exists
         let price_feeder: PriceFeedRef =
             self.price_feed_provider.get().unwrap().into();
-        Ok(price_feeder
+        let prices_e18 = price_feeder
             .call()
-            .get_latest_prices(all_assets)
+            .get_latest_prices(vec![asset])
             .call_v1()
-            .invoke()?)
+            .invoke()?;
+        Ok(prices_e18[0])
     }
+
     pub fn get_fee_reductions_of_account(
         &self,
         account: &AccountId,
@@ -1640,3 +3630,10 @@ fn get_account_data_entry_mut(
 fn get_penalty(market_rule: &[Option<AssetRules>], asset_id: u32) -> u128 {
     market_rule[asset_id as usize].unwrap().penalty_e6.unwrap() //  asset_id exists => market_rule exists, borrow_coefficient_e6 exists => penalty_e6 exists
 }
+/// returns the assets whose prices are needed to evaluate the account's health - its collaterals and debts.
+fn get_priced_assets(account_config: &AccountConfig) -> AssetsBitmap {
+    account_config
+        .deposits
+        .intersection(&account_config.collaterals)
+        .union(&account_config.debts())
+}
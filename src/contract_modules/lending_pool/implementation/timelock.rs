@@ -0,0 +1,84 @@
+// SPDX-License-Identifier: BUSL-1.1
+use crate::lending_pool::{
+    events::{ManageActionCancelled, ManageActionExecuted, ManageActionQueued},
+    LendingPoolError, ManageAction, EMERGENCY_ADMIN, ROLE_ADMIN,
+};
+use ink::env::DefaultEnvironment;
+use pendzl::traits::Timestamp;
+
+use super::{manage::LendingPoolManageImpl, storage::LendingPoolStorage};
+
+pub trait LendingPoolTimelockImpl: LendingPoolManageImpl {
+    fn set_timelock_delay(
+        &mut self,
+        timelock_delay: Timestamp,
+    ) -> Result<(), LendingPoolError> {
+        self._ensure_can_manage_directly(ROLE_ADMIN)?;
+        self._set_timelock_delay(timelock_delay)
+    }
+
+    fn queue_manage_action(
+        &mut self,
+        action: ManageAction,
+    ) -> Result<u32, LendingPoolError> {
+        let caller = Self::env().caller();
+        self._ensure_has_role(action.required_role(), Some(caller))?;
+        action.validate()?;
+
+        let timestamp = Self::env().block_timestamp();
+        let (action_id, eta) = self
+            .data::<LendingPoolStorage>()
+            .account_for_queue_manage_action(&action, &caller, &timestamp)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ManageActionQueued>(
+            ManageActionQueued {
+                action_id,
+                proposer: caller,
+                eta,
+                action,
+            },
+        );
+        Ok(action_id)
+    }
+
+    fn execute_manage_action(
+        &mut self,
+        action_id: u32,
+    ) -> Result<(), LendingPoolError> {
+        let timestamp = Self::env().block_timestamp();
+        let queued_action = self
+            .data::<LendingPoolStorage>()
+            .account_for_execute_manage_action(&action_id, &timestamp)?;
+        self._ensure_has_role(
+            queued_action.action.required_role(),
+            Some(queued_action.proposer),
+        )
+        .map_err(|_| LendingPoolError::ManageActionProposerLostRole)?;
+
+        self._apply_manage_action(queued_action.action)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ManageActionExecuted>(
+            ManageActionExecuted {
+                action_id,
+                caller: Self::env().caller(),
+            },
+        );
+        Ok(())
+    }
+
+    fn cancel_manage_action(
+        &mut self,
+        action_id: u32,
+    ) -> Result<(), LendingPoolError> {
+        let caller = Self::env().caller();
+        self._ensure_has_role(EMERGENCY_ADMIN, Some(caller))?;
+
+        self.data::<LendingPoolStorage>()
+            .account_for_cancel_manage_action(&action_id)?;
+
+        ink::env::emit_event::<DefaultEnvironment, ManageActionCancelled>(
+            ManageActionCancelled { action_id, caller },
+        );
+        Ok(())
+    }
+}
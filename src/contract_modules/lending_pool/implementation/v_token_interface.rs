@@ -6,6 +6,7 @@ use crate::{
         LendingPoolError,
     },
 };
+use abax_library::structs::PausableAction;
 use pendzl::{
     contracts::access_control::AccessControlError,
     traits::{AccountId, Balance, StorageFieldGetter},
@@ -48,13 +49,16 @@ pub trait LendingPoolVTokenInterfaceImpl:
 
         let reserve_abacus_tokens_tokens = self
             .data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(underlying_asset)
+            .find_reserve_abacus_tokens(&underlying_asset)
             .ok_or(LendingPoolError::AssetNotRegistered)?;
         if Self::env().caller() != reserve_abacus_tokens_tokens.v_token_address
         {
             return Err((AccessControlError::MissingRole).into());
         }
+        self.data::<LendingPoolStorage>().ensure_action_not_paused(
+            &underlying_asset,
+            PausableAction::Transfer,
+        )?;
 
         let timestamp = Self::env().block_timestamp();
 
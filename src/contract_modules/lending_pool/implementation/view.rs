@@ -1,18 +1,24 @@
 // SPDX-License-Identifier: BUSL-1.1
 use crate::lending_pool::{
-    DecimalMultiplier, LendingPoolError, MarketRule, RuleId,
+    ActionsSimulation, DecimalMultiplier, LendingPoolError,
+    LiquidationCandidate, MarketRule, QueuedManageAction,
+    ReserveOperationalState, RuleId,
 };
 use abax_library::{
     math::E18_U128,
     structs::{
-        AccountConfig, AccountReserveData, InterestRateModel,
-        ReserveAbacusTokens, ReserveData, ReserveFees, ReserveIndexes,
-        ReserveRestrictions, TwEntry, TwIndex,
+        AccountConfig, AccountHealth, AccountReserveData, Action, ActionsPause,
+        CloseFactor, DelistingStage, InterestAccrualMode, InterestRateModel,
+        LiquidationAuction, LiquidationAuctionParams, ReserveAbacusTokens,
+        ReserveAccountLimits, ReserveData, ReserveDelisting, ReserveFees,
+        ReserveIndexes, ReserveIsolation, ReserveRestrictions, StableRateModel,
+        TwEntry, TwIndex,
     },
 };
-use pendzl::traits::{AccountId, Balance, StorageFieldGetter};
+use pendzl::traits::{AccountId, Balance, StorageFieldGetter, Timestamp};
 
-use ink::prelude::vec::Vec;
+use core::cmp::Ordering;
+use ink::{env::ReturnFlags, prelude::vec::Vec};
 
 use super::{internal::InternalIncome, storage::LendingPoolStorage};
 
@@ -26,17 +32,40 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
     fn view_asset_id(&self, asset: AccountId) -> Option<RuleId> {
         self.data::<LendingPoolStorage>().asset_to_id.get(asset)
     }
+    fn view_timelock_delay(&self) -> Timestamp {
+        self.data::<LendingPoolStorage>()
+            .timelock_delay
+            .get()
+            .unwrap_or_default()
+    }
+    fn view_queued_manage_action(
+        &self,
+        action_id: u32,
+    ) -> Option<QueuedManageAction> {
+        self.data::<LendingPoolStorage>()
+            .queued_manage_actions
+            .get(action_id)
+    }
     fn view_registered_assets(&self) -> Vec<AccountId> {
+        let timestamp = Self::env().block_timestamp();
         self.data::<LendingPoolStorage>()
             .get_all_registered_assets()
+            .into_iter()
+            .enumerate()
+            .filter(|(asset_id, _)| {
+                self.data::<LendingPoolStorage>()
+                    .get_delisting_stage(*asset_id as u32, &timestamp)
+                    != DelistingStage::Closed
+            })
+            .map(|(_, asset)| asset)
+            .collect()
     }
 
     fn view_reserve_data(&self, asset: AccountId) -> Option<ReserveData> {
         match self.data::<LendingPoolStorage>().asset_to_id.get(asset) {
             Some(asset_id) => self
                 .data::<LendingPoolStorage>()
-                .reserve_datas
-                .get(asset_id),
+                .find_reserve_data(asset_id),
             None => None,
         }
     }
@@ -50,8 +79,7 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
             .get(asset)
             .map(|asset_id| {
                 self.data::<LendingPoolStorage>()
-                    .reserve_indexes_and_fees
-                    .get(asset_id)
+                    .find_reserve_indexes_and_fees(asset_id)
                     .unwrap()
                     .indexes
             })
@@ -62,18 +90,18 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
             Some(asset_id) => {
                 let reserve_data = self
                     .data::<LendingPoolStorage>()
-                    .reserve_datas
-                    .get(asset_id)
+                    .find_reserve_data(asset_id)
                     .unwrap();
                 let mut reserve_indexes_and_fees = self
                     .data::<LendingPoolStorage>()
-                    .reserve_indexes_and_fees
-                    .get(asset_id)
+                    .find_reserve_indexes_and_fees(asset_id)
                     .unwrap();
 
                 reserve_indexes_and_fees
-                    .indexes
-                    .update(&reserve_data, &Self::env().block_timestamp())
+                    .update_indexes(
+                        &reserve_data,
+                        &Self::env().block_timestamp(),
+                    )
                     .unwrap();
                 Some(reserve_indexes_and_fees.indexes)
             }
@@ -87,13 +115,27 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
             .get(asset)
             .map(|asset_id| {
                 self.data::<LendingPoolStorage>()
-                    .reserve_indexes_and_fees
-                    .get(asset_id)
+                    .find_reserve_indexes_and_fees(asset_id)
                     .unwrap()
                     .fees
             })
     }
 
+    fn view_reserve_accrual_mode(
+        &self,
+        asset: AccountId,
+    ) -> Option<InterestAccrualMode> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>()
+                    .find_reserve_indexes_and_fees(asset_id)
+                    .unwrap()
+                    .accrual_mode
+            })
+    }
+
     fn view_interest_rate_model(
         &self,
         asset: AccountId,
@@ -101,8 +143,7 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
         match self.data::<LendingPoolStorage>().asset_to_id.get(asset) {
             Some(asset_id) => self
                 .data::<LendingPoolStorage>()
-                .interest_rate_model
-                .get(asset_id),
+                .find_interest_rate_model(asset_id),
             None => None,
         }
     }
@@ -119,13 +160,152 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
             None => None,
         }
     }
+    fn view_reserve_isolation(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveIsolation> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>()
+                    .reserve_isolations
+                    .get(asset_id)
+                    .unwrap_or_default()
+            })
+    }
+    fn view_reserve_account_limits(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveAccountLimits> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>()
+                    .reserve_account_limits
+                    .get(asset_id)
+                    .unwrap_or_default()
+            })
+    }
+    fn view_reserve_operational_state(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveOperationalState> {
+        let asset_id =
+            self.data::<LendingPoolStorage>().asset_to_id.get(asset)?;
+        let reserve_data = self
+            .data::<LendingPoolStorage>()
+            .find_reserve_data(asset_id)?;
+        Some(ReserveOperationalState {
+            activated: reserve_data.activated,
+            frozen: reserve_data.frozen,
+            freeze: self
+                .data::<LendingPoolStorage>()
+                .reserve_freezes
+                .get(asset_id),
+            paused_actions: self
+                .data::<LendingPoolStorage>()
+                .global_actions_pause
+                .get_or_default()
+                .union(
+                    &self
+                        .data::<LendingPoolStorage>()
+                        .reserve_actions_pauses
+                        .get(asset_id)
+                        .unwrap_or_default(),
+                ),
+            delisting_stage: self
+                .data::<LendingPoolStorage>()
+                .get_delisting_stage(asset_id, &Self::env().block_timestamp()),
+        })
+    }
+    fn view_global_actions_pause(&self) -> ActionsPause {
+        self.data::<LendingPoolStorage>()
+            .global_actions_pause
+            .get_or_default()
+    }
+    fn view_reserve_actions_pause(
+        &self,
+        asset: AccountId,
+    ) -> Option<ActionsPause> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>()
+                    .reserve_actions_pauses
+                    .get(asset_id)
+                    .unwrap_or_default()
+            })
+    }
+    fn view_isolated_debt_e8(&self, asset: AccountId) -> Option<u128> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>()
+                    .isolated_debts_e8
+                    .get(asset_id)
+                    .unwrap_or_default()
+            })
+    }
+    fn view_reserve_delisting(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveDelisting> {
+        match self.data::<LendingPoolStorage>().asset_to_id.get(asset) {
+            Some(asset_id) => self
+                .data::<LendingPoolStorage>()
+                .reserve_delistings
+                .get(asset_id),
+            None => None,
+        }
+    }
+    fn view_reserve_delisting_stage(
+        &self,
+        asset: AccountId,
+    ) -> Option<DelistingStage> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>().get_delisting_stage(
+                    asset_id,
+                    &Self::env().block_timestamp(),
+                )
+            })
+    }
+    fn view_reserve_close_factor(
+        &self,
+        asset: AccountId,
+    ) -> Option<CloseFactor> {
+        match self.data::<LendingPoolStorage>().asset_to_id.get(asset) {
+            Some(asset_id) => self
+                .data::<LendingPoolStorage>()
+                .reserve_close_factors
+                .get(asset_id),
+            None => None,
+        }
+    }
+    fn view_stable_rate_model(
+        &self,
+        asset: AccountId,
+    ) -> Option<StableRateModel> {
+        match self.data::<LendingPoolStorage>().asset_to_id.get(asset) {
+            Some(asset_id) => self
+                .data::<LendingPoolStorage>()
+                .stable_rate_models
+                .get(asset_id),
+            None => None,
+        }
+    }
     fn view_reserve_tokens(
         &self,
         asset: AccountId,
     ) -> Option<ReserveAbacusTokens> {
         self.data::<LendingPoolStorage>()
-            .reserve_abacus_tokens
-            .get(asset)
+            .find_reserve_abacus_tokens(&asset)
     }
     fn view_reserve_decimal_multiplier(
         &self,
@@ -157,6 +337,9 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
                 debt: 0,
                 applied_deposit_index_e18: E18_U128,
                 applied_debt_index_e18: E18_U128,
+                stable_debt: 0,
+                stable_rate_e18: 0,
+                applied_stable_timestamp: 0,
             },
         }
     }
@@ -174,21 +357,21 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
                     .0;
                 let reserve_data = self
                     .data::<LendingPoolStorage>()
-                    .reserve_datas
-                    .get(asset_id)
+                    .find_reserve_data(asset_id)
                     .unwrap();
                 let mut reserve_indexes_and_fees = self
                     .data::<LendingPoolStorage>()
-                    .reserve_indexes_and_fees
-                    .get(asset_id)
+                    .find_reserve_indexes_and_fees(asset_id)
                     .unwrap();
                 let fee_reductions = self
                     .data::<LendingPoolStorage>()
                     .get_fee_reductions_of_account(&account);
 
                 reserve_indexes_and_fees
-                    .indexes
-                    .update(&reserve_data, &Self::env().block_timestamp())
+                    .update_indexes(
+                        &reserve_data,
+                        &Self::env().block_timestamp(),
+                    )
                     .unwrap();
                 account_reserve_data
                     .accumulate_account_interest(
@@ -198,21 +381,27 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
                     )
                     .unwrap();
                 account_reserve_data
+                    .accumulate_account_stable_interest(
+                        &Self::env().block_timestamp(),
+                    )
+                    .unwrap();
+                account_reserve_data
             }
             None => AccountReserveData {
                 deposit: 0,
                 debt: 0,
                 applied_deposit_index_e18: E18_U128,
                 applied_debt_index_e18: E18_U128,
+                stable_debt: 0,
+                stable_rate_e18: 0,
+                applied_stable_timestamp: 0,
             },
         }
     }
 
     fn view_account_config(&self, account: AccountId) -> AccountConfig {
         self.data::<LendingPoolStorage>()
-            .account_configs
-            .get(account)
-            .unwrap_or_default()
+            .get_account_config(&account)
     }
 
     fn view_market_rule(&self, market_rule_id: RuleId) -> Option<MarketRule> {
@@ -221,6 +410,47 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
             .get(market_rule_id)
     }
 
+    fn view_market_rule_liquidation_auction(
+        &self,
+        market_rule_id: RuleId,
+    ) -> Option<LiquidationAuctionParams> {
+        self.data::<LendingPoolStorage>()
+            .market_rule_liquidation_auctions
+            .get(market_rule_id)
+    }
+
+    fn view_liquidation_auction(
+        &self,
+        account: AccountId,
+    ) -> Option<LiquidationAuction> {
+        self.data::<LendingPoolStorage>()
+            .liquidation_auctions
+            .get(account)
+    }
+
+    fn view_active_liquidation_auctions(
+        &self,
+        from: u32,
+        count: u32,
+    ) -> Vec<(AccountId, LiquidationAuction)> {
+        let storage = self.data::<LendingPoolStorage>();
+        let auctions_count =
+            storage.liquidation_auctions_count.get_or_default();
+        let to = from.saturating_add(count).min(auctions_count);
+        let mut result = Vec::new();
+        for index in from..to {
+            if let Some(account) =
+                storage.liquidation_auction_accounts.get(index)
+            {
+                if let Some(auction) = storage.liquidation_auctions.get(account)
+                {
+                    result.push((account, auction));
+                }
+            }
+        }
+        result
+    }
+
     fn get_account_free_collateral_coefficient(
         &self,
         account: AccountId,
@@ -230,6 +460,94 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
             .unwrap()
     }
 
+    fn view_account_health(
+        &self,
+        account: AccountId,
+    ) -> Result<AccountHealth, LendingPoolError> {
+        let storage = self.data::<LendingPoolStorage>();
+        storage.calculate_account_health(
+            &storage.get_account_datas(&account),
+            &storage.get_account_config(&account),
+            &storage.get_fee_reductions_of_account(&account),
+            &storage.get_assets_prices_e18()?,
+        )
+    }
+
+    fn view_max_borrowable(
+        &self,
+        account: AccountId,
+        asset: AccountId,
+    ) -> Result<Balance, LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .calculate_max_borrowable(&account, &asset)
+    }
+
+    fn view_max_withdrawable(
+        &self,
+        account: AccountId,
+        asset: AccountId,
+    ) -> Result<Balance, LendingPoolError> {
+        self.data::<LendingPoolStorage>()
+            .calculate_max_withdrawable(&account, &asset)
+    }
+
+    fn simulate_actions(
+        &mut self,
+        account: AccountId,
+        actions: Vec<Action>,
+    ) -> Result<ActionsSimulation, LendingPoolError> {
+        let result = self._simulate_actions(account, actions);
+        // the reserves' data modified by the simulation must not be committed
+        ink::env::return_value::<
+            ink::MessageResult<Result<ActionsSimulation, LendingPoolError>>,
+        >(ReturnFlags::REVERT, &Ok(result))
+    }
+
+    fn _simulate_actions(
+        &mut self,
+        account: AccountId,
+        mut actions: Vec<Action>,
+    ) -> Result<ActionsSimulation, LendingPoolError> {
+        let (account_datas, account_health) = self
+            .data::<LendingPoolStorage>()
+            .simulate_account_actions(&account, &mut actions)?;
+
+        let mut assets: Vec<AccountId> = Vec::new();
+        for action in actions.iter() {
+            if !assets.contains(&action.args.asset) {
+                assets.push(action.args.asset);
+            }
+        }
+
+        let mut account_reserve_datas = Vec::new();
+        let mut reserve_datas = Vec::new();
+        for asset in assets {
+            // the assets were validated by the simulation
+            let asset_id = self
+                .data::<LendingPoolStorage>()
+                .asset_to_id
+                .get(asset)
+                .unwrap();
+            account_reserve_datas.push((
+                asset,
+                account_datas[asset_id as usize].unwrap_or_default(),
+            ));
+            reserve_datas.push((
+                asset,
+                self.data::<LendingPoolStorage>()
+                    .find_reserve_data(asset_id)
+                    .unwrap(),
+            ));
+        }
+
+        Ok(ActionsSimulation {
+            collateralized: account_health.is_collateralized(),
+            account_health,
+            account_reserve_datas,
+            reserve_datas,
+        })
+    }
+
     fn view_protocol_income(
         &self,
         assets: Option<Vec<AccountId>>,
@@ -247,13 +565,85 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
         }
     }
 
+    fn view_reserve_safety_fund(&self, asset: AccountId) -> Option<Balance> {
+        self.data::<LendingPoolStorage>()
+            .asset_to_id
+            .get(asset)
+            .map(|asset_id| {
+                self.data::<LendingPoolStorage>()
+                    .reserve_safety_funds
+                    .get(asset_id)
+                    .unwrap_or_default()
+            })
+    }
+
+    fn view_accounts_with_unbacked_debt(
+        &self,
+        accounts: Vec<AccountId>,
+    ) -> Vec<AccountId> {
+        accounts
+            .into_iter()
+            .filter(|account| {
+                self.data::<LendingPoolStorage>()
+                    .get_account_config(account)
+                    .has_unbacked_debt()
+            })
+            .collect()
+    }
+
+    fn view_liquidation_candidates(
+        &self,
+        accounts: Vec<AccountId>,
+    ) -> Result<Vec<LiquidationCandidate>, LendingPoolError> {
+        let storage = self.data::<LendingPoolStorage>();
+        let prices_e18 = storage.get_assets_prices_e18()?;
+
+        let mut candidates = Vec::new();
+        for account in accounts {
+            let account_config = storage.get_account_config(&account);
+            if account_config.debts().is_empty() {
+                continue;
+            }
+            let account_datas = storage.get_account_datas(&account);
+            let (collateralized, power_e6) = storage
+                .calculate_lending_power_e6(
+                    &account_datas,
+                    &account_config,
+                    &storage.get_fee_reductions_of_account(&account),
+                    &prices_e18,
+                )?;
+            let (largest_collateral, largest_debt) = storage
+                .get_largest_collateral_and_debt(
+                    &account_datas,
+                    &account_config,
+                    &prices_e18,
+                );
+            let to_asset = |(asset_id, amount): (u32, Balance)| {
+                (storage.id_to_asset.get(asset_id).unwrap(), amount)
+            };
+            candidates.push(LiquidationCandidate {
+                account,
+                collateralized,
+                power_e6,
+                largest_debt: largest_debt.map(to_asset),
+                largest_collateral: largest_collateral.map(to_asset),
+            });
+        }
+
+        candidates.sort_by(|a, b| match (a.collateralized, b.collateralized) {
+            (false, true) => Ordering::Less,
+            (true, false) => Ordering::Greater,
+            (false, false) => b.power_e6.cmp(&a.power_e6),
+            (true, true) => a.power_e6.cmp(&b.power_e6),
+        });
+        Ok(candidates)
+    }
+
     fn view_asset_tw_index(&self, asset: AccountId) -> Option<TwIndex> {
         if let Some(asset_id) =
             self.data::<LendingPoolStorage>().asset_to_id.get(asset)
         {
-            self.data::<LendingPoolStorage>()
-                .tw_ur_indexes
-                .get(asset_id)
+            self.data::<LendingPoolStorage>().find_tw_ur_index(asset_id)
         } else {
             None
         }
@@ -286,7 +676,7 @@ pub trait LendingPoolViewImpl: StorageFieldGetter<LendingPoolStorage> {
         &self,
         period: u64,
         asset: AccountId,
-        guessed_index: u32,
+        guessed_index: Option<u32>,
     ) -> Result<u32, LendingPoolError> {
         let asset_id = self
             .data::<LendingPoolStorage>()
@@ -56,6 +56,7 @@ pub trait LendingPoolActions {
     /// * `AssetNotRegistered` returned if the `asset` is not registered in the `LendingPool` returned.
     /// * `Inactive` returned if the reserve coresponding to the `asset` is inactive.
     /// * `Frozen` returned if the reserve coresponding to the `asset' is frozen.
+    /// * `Delisting` returned if the reserve coresponding to the `asset` is being delisted.
     /// * `MaxDepositReached` returned if the total deposit after this deposit is higher than maximal_deposit.
     /// * `PSP22Error` returned if transfer of `asset`fails.
     #[ink(message)]
@@ -105,7 +106,7 @@ pub trait LendingPoolActions {
     /// * `Frozen` returned if the reserve coresponding to the `asset` is frozen.
     /// * `MinimalDebt` returned if after taking the loan the debt of `on_bahalf_of` is smaller than minimal_debt.
     /// * `MaxDebtReached` returned if after borrowig the total_debt is greated than maximal_total_debt.
-    /// * `PriceFeedError` returned if there is a problem with Price Oracle.
+    /// * `PriceFeedError` returned if there is a problem with Price Oracle (including `StalePrice` and `PriceDeviationTooLarge`).
     /// * `InsufficientCollateral` returned if `use_as_collateral` ==false and insufficient collateralafter disabling `asset` as a collateral.
     /// * `PSP22Error` returned if transfer of `asset` fails (also in the case if the `caller` != `on_behalf_of` and the caller has not enough allowance).
     #[ink(message)]
@@ -138,6 +139,45 @@ pub trait LendingPoolActions {
         data: Vec<u8>,
     ) -> Result<Balance, LendingPoolError>;
 
+    /// Caller takes `amount` of an `asset` as a stable rate debt that is accounted `on_bahalf_of`.
+    /// The debt accrues interest with the stable rate offered at the moment of borrowing (weighted with the already existing stable debt of `on_behalf_of`).
+    /// Tokens are transferred to the `caller`.
+    ///
+    /// * `asset` - AccountId (aka address) of PSP22 that is borrowed.
+    /// * `on_behalf_of` - AccountId (aka address) on behalf of who caller is taking debt. If `caller` != `on_behalf_of` then the allowance of appropriate SToken will be decerased.
+    /// * `amount` - the number of tokens to be borrowed in absolute value (1 USDT = 1_000_000, 1 AZERO = 1_000_000_000_000).
+    /// * `data` - additional data that is unused.
+    ///
+    /// # Errors
+    /// * `StableBorrowDisabled` returned if the reserve coresponding to the `asset` has no stable rate model set.
+    /// * check `borrow` for other possible errors.
+    #[ink(message)]
+    fn borrow_stable(
+        &mut self,
+        asset: AccountId,
+        on_behalf_of: AccountId,
+        amount: Balance,
+        data: Vec<u8>,
+    ) -> Result<(), LendingPoolError>;
+
+    /// Caller repays `amount` of `asset` `on_behalf_of`'s stable debt. Tokens are transferred from the `caller`.
+    ///
+    /// * `asset` - AccountId (aka address) of PSP22 that is repayed.
+    /// * `on_behalf_of` - AccountId (aka address) on behalf of who caller is repaying the stable debt.
+    /// * `amount` - the number of tokens to be repaid. If it is greater then stable debt only stable debt will be repaid.
+    /// * `data` - additional data currently unused.
+    ///
+    /// # Errors
+    /// * check `repay` for possible errors.
+    #[ink(message)]
+    fn repay_stable(
+        &mut self,
+        asset: AccountId,
+        on_behalf_of: AccountId,
+        amount: Balance,
+        data: Vec<u8>,
+    ) -> Result<Balance, LendingPoolError>;
+
     /// Caller perform `actions` on behalf of `on_behalf_of`.
     ///
     /// * `actions` - a vector of actions that should be performed.
@@ -159,17 +199,21 @@ pub trait LendingPoolActions {
     /// * `liquidated_account` - AccountId (aka address) whose position should be liquidated. liquidated_account must be undercollateralized.
     /// * `asset_to_repay` - AccountId (aka address) of PSP22 that liquidated_account has debt in.
     /// * `asset_to_take` - AccountId (aka address) of PSP22 that liquidated_account has supplied and is using as collateral. This asset will be a liquidator reward i.e. liquidator will receive aTokens corresponding to this asset.
-    /// * `amount_to_repay` - the number of tokens to be repaid in absolute value (1USDT = 1_000_000, 1AZERO = 1_000_000_000_000). The minimum of amount_to_repay and `liquidated_account` debt will be repaid.
+    /// * `amount_to_repay` - the number of tokens to be repaid in absolute value (1USDT = 1_000_000, 1AZERO = 1_000_000_000_000). The minimum of amount_to_repay, `liquidated_account` debt and the cap resulting from the close factor of `asset_to_repay` will be repaid. The variable debt is repaid first - the stable debt is repaid only if `liquidated_account` has no variable debt in `asset_to_repay`.
     /// * `minimum_recieved_for_one_repaid_token_e12` - minimum amount of asset_to_take to be received by liquidator per 1 repaid token multiplied by 10^12. !Notice! In the case of AZERO 1 token is 10^-12 of AZERO and in the case of USDT 1 token is 10^-6 of USDT. The liquidator must be conscious and use absolute values.
-    /// * `data` - additional data, currently unused.
+    /// * `data` - additional data. Empty or SCALE-encoded `false` - liquidator receives the deposit of `asset_to_take`. SCALE-encoded `true` (`[1]`) - liquidator receives the underlying `asset_to_take`.
     ///
     /// # Errors
+    /// * `InvalidLiquidationData` returned if the `data` can't be decoded.
+    /// * `InsufficientLiquidity` returned if the underlying `asset_to_take` is to be received and the pool doesn't hold enough of it.
     /// * `NoPriceFeed` returned if there is problem wirg oracle.
-    /// * `Collaterized` returned if the `liaudiated_account` is collaterized.
+    /// * `PriceFeedError` returned if the price of any asset is stale (`StalePrice`) or deviates too much from its anchor price (`PriceDeviationTooLarge`).
+    /// * `Collaterized` returned if the `liaudiated_account` is collaterized - unless `asset_to_repay` is delisted and in the settlement stage. Then the liquidation carries no penalty.
     /// * `AssetNotRegistered` returned if the `asset_to_take` or `asset_to_repay` were not registered.
     /// * `NothingToRepay` returned if the `liquidated_account` has no `asset_to_repay` debt.
     /// * `NothingToCompensateWith` returned if the `liquidated_account` has no `asset_to_take` deposit.
     /// * `TakingNotACollateral` returned if the `asset_to_take` is not used as collateral by `liqudiated_account`.
+    /// * `AuctionNotStarted` returned if the market rule of `liquidated_account` uses the auction mode and its auction was not started.
     #[ink(message)]
     fn liquidate(
         &mut self,
@@ -180,4 +224,32 @@ pub trait LendingPoolActions {
         minimum_recieved_for_one_repaid_token_e12: u128,
         data: Vec<u8>,
     ) -> Result<(Balance, Balance), LendingPoolError>;
+
+    /// is used by anyone to start the liquidation auction of the undercollateralized `account` whose market rule uses the auction mode.
+    /// From the start the part of the liquidation penalties paid to liquidators grows over time up to the full penalties.
+    ///
+    /// * `account` - AccountId (aka address) of the undercollateralized account.
+    ///
+    /// # Errors
+    /// * `Collaterized` returned if the `account` is collaterized.
+    /// * `AuctionsDisabled` returned if the market rule of the `account` doesn't use the auction mode.
+    /// * `AuctionAlreadyStarted` returned if the `account` already has an active auction.
+    #[ink(message)]
+    fn start_liquidation_auction(
+        &mut self,
+        account: AccountId,
+    ) -> Result<(), LendingPoolError>;
+
+    /// is used by anyone to close the liquidation auction of the `account` that is collateralized again.
+    ///
+    /// * `account` - AccountId (aka address) of the account.
+    ///
+    /// # Errors
+    /// * `InsufficientCollateral` returned if the `account` is still undercollateralized.
+    /// * `AuctionNotStarted` returned if the `account` has no active auction.
+    #[ink(message)]
+    fn close_liquidation_auction(
+        &mut self,
+        account: AccountId,
+    ) -> Result<(), LendingPoolError>;
 }
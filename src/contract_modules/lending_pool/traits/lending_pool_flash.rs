@@ -10,7 +10,7 @@ use crate::lending_pool::LendingPoolError;
 pub type LendingPoolFlashRef =
     contract_ref!(LendingPoolFlash, DefaultEnvironment);
 
-/// Trait containing flash_loan message. Used by **flash loaners**.
+/// Trait containing flash_loan and flash_liquidate messages. Used by **flash loaners** and **liquidators**.
 #[ink::trait_definition]
 pub trait LendingPoolFlash {
     /// is used to perform a flash loan. 1) take a loan. 2) perform actions. 3) repay loan + fee. All in one tx.
@@ -32,4 +32,34 @@ pub trait LendingPoolFlash {
         amounts: Vec<Balance>,
         receiver_params: Vec<u8>,
     ) -> Result<(), LendingPoolError>;
+
+    /// is used to liquidate without holding `asset_to_repay` up front. 1) the debt is repaid with flash loaned `asset_to_repay`. 2) the seized collateral is withdrawn to `receiver`.
+    /// 3) `receiver` swaps the collateral and repays loan + fee. All in one tx.
+    ///
+    ///  * `liquidated_account` - AccountId (aka address) of an account that is undercollateralized and should be liquidated.
+    ///  * `asset_to_repay` - AccountId (aka address) of PSP22 that is flash loaned to repay the debt.
+    ///  * `asset_to_take` - AccountId (aka address) of PSP22 that is seized and withdrawn to `receiver`.
+    ///  * `amount_to_repay` - amount of `asset_to_repay` to be repaid.
+    ///  * `minimum_recieved_for_one_repaid_token_e18` - minimum amount of `asset_to_take` to be received per one repaid token (multiplied by 10^18).
+    ///  * `receiver` - AccountId (aka address) of a contract that receives the seized collateral. Must implement `FlashLoanReceiver` - `execute_operation` is called with `asset_to_repay`, the repaid amount and the fee.
+    ///  * `receiver_params` -  additional data passed to receiver.
+    ///
+    /// Returns the repaid and the taken amounts.
+    /// # Errors
+    /// * `AmountNotGreaterThanZero` returned if `amount_to_repay` == 0.
+    /// * all errors of `LendingPoolActions::liquidate` (with the underlying `asset_to_take` received).
+    /// * `PSP22Error` if transfer fails
+    /// * `FlashLoanReceiverError` if call to `receiver` fails.
+    #[ink(message)]
+    #[allow(clippy::too_many_arguments)]
+    fn flash_liquidate(
+        &mut self,
+        liquidated_account: AccountId,
+        asset_to_repay: AccountId,
+        asset_to_take: AccountId,
+        amount_to_repay: Balance,
+        minimum_recieved_for_one_repaid_token_e18: u128,
+        receiver: AccountId,
+        receiver_params: Vec<u8>,
+    ) -> Result<(Balance, Balance), LendingPoolError>;
 }
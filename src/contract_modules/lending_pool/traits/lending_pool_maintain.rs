@@ -1,5 +1,6 @@
 // SPDX-License-Identifier: BUSL-1.1
 use ink::{contract_ref, env::DefaultEnvironment, primitives::AccountId};
+use pendzl::traits::Balance;
 
 use crate::lending_pool::LendingPoolError;
 
@@ -20,20 +21,116 @@ pub trait LendingPoolMaintain {
         asset: AccountId,
     ) -> Result<(), LendingPoolError>;
 
-    /// is used by anyone to adjust interest's rate at the target utilization rate
+    /// is used by anyone to adjust interest's rate at the target utilization rate of the adaptive interest rate model
     ///
     /// * `asset` - AccountId (aka address) of asset of which rate should be adjusted
-    /// * `guessed_index` - u32 of an index in the accumulated time-weighted utilization rate storage
+    /// * `guessed_index` - optional u32 of an index in the accumulated time-weighted utilization rate storage
     /// that is guessed to be the one that should be used to adjust the rate (or is the closest one to the one supposed to be used)
     ///  It must be index of entry that timestamp is at least smaller by interest_rate_model.minimal_time_between_adjustments from the last entry.
+    ///  If `None`, the entry is binary searched - the guess only saves the gas.
     ///
     /// # Errors
-    /// * `LendingPoolError::TwEntryInvalidIndex` returned if the index is invalid - points to a non existing entry or the entry's value is too recent.
+    /// * `TwError::InvalidIndex` / `TwError::EntryNotFound` returned if the index is outside of the ring or points to a non existing entry.
+    /// * `TwError::EntryTooRecent` returned if the entry at the index is not distant enough from the latest entry.
+    /// * `TwError::EntryOverwritten` returned if all the entries distant enough from the latest entry have been overwritten.
     /// * `LendingPoolError::TooEarlyToAdjustRate` returned if the attempt to adjust the rate is made earlier then the minimal time between adjustments.
+    /// * `LendingPoolError::InterestRateModelNotAdaptive` returned if the interest rate model of the reserve is not the adaptive one.
     #[ink(message)]
     fn adjust_rate_at_target(
         &mut self,
         asset: AccountId,
-        guessed_index: u32,
+        guessed_index: Option<u32>,
     ) -> Result<u64, LendingPoolError>;
+
+    /// is used by anyone to write off the debt of an account that has no collateral left (bad debt).
+    ///
+    /// * `account` - AccountId (aka address) of account which debt should be written off
+    ///
+    /// The debt in each asset is covered by the protocol's earned fee first.
+    /// The remaining deposits of the account (not used as collateral) are moved to the safety funds of their reserves
+    /// up to the value of the debt at the oracle prices - the deposits in the borrowed assets first. The rest of the deposits stays with the account.
+    /// Then the debt in each asset is covered by the reserve's safety fund and only the rest is socialized among depositors by decreasing the deposit index.
+    ///
+    /// # Errors
+    /// * `LendingPoolError::NoBadDebt` returned if the account has collateral or has no debt.
+    /// * `LendingPoolError::BadDebtExceedsDeposits` returned if the debt left to socialize exceeds the total deposit of its reserve.
+    #[ink(message)]
+    fn settle_bad_debt(
+        &mut self,
+        account: AccountId,
+    ) -> Result<(), LendingPoolError>;
+
+    /// is used by anyone to withdraw the whole deposit of an account in the delisted asset to the account.
+    ///
+    /// * `asset` - AccountId (aka address) of the delisted asset
+    /// * `account` - AccountId (aka address) of account which deposit should be settled
+    ///
+    /// # Returns
+    /// The withdrawn amount.
+    ///
+    /// # Errors
+    /// * `LendingPoolError::NotInSettlement` returned if the delisting of the reserve is not in the settlement stage.
+    /// * `LendingPoolError::InsufficientDeposit` returned if the `account` has no deposit in `asset`.
+    #[ink(message)]
+    fn settle_delisted_deposit(
+        &mut self,
+        asset: AccountId,
+        account: AccountId,
+    ) -> Result<Balance, LendingPoolError>;
+
+    /// is used by anyone to rebalance the stable rate of an account to the stable rate currently offered by the reserve.
+    ///
+    /// * `asset` - AccountId (aka address) of asset in which the stable debt is taken
+    /// * `account` - AccountId (aka address) of account which stable rate should be rebalanced
+    ///
+    /// # Returns
+    /// The new stable rate of the `account`.
+    ///
+    /// # Errors
+    /// * `LendingPoolError::StableBorrowDisabled` returned if the reserve has no stable rate model set.
+    /// * `LendingPoolError::InsufficientDebt` returned if the `account` has no stable debt in `asset`.
+    /// * `LendingPoolError::StableRateRebalanceNotAllowed` returned if the `account`'s stable rate doesn't diverge from the currently offered stable rate by more than the rebalance threshold.
+    #[ink(message)]
+    fn rebalance_stable_rate(
+        &mut self,
+        asset: AccountId,
+        account: AccountId,
+    ) -> Result<u64, LendingPoolError>;
+
+    /// is used by anyone to add underlying tokens to the safety fund of the reserve.
+    ///
+    /// * `asset` - AccountId (aka address) of asset to which safety fund tokens should be added
+    /// * `amount` - amount of `asset` to be transferred from the caller to the safety fund
+    ///
+    /// # Errors
+    /// * `LendingPoolError::AmountNotGreaterThanZero` returned if `amount` is zero.
+    #[ink(message)]
+    fn increase_safety_fund(
+        &mut self,
+        asset: AccountId,
+        amount: Balance,
+    ) -> Result<(), LendingPoolError>;
+
+    /// is used by anyone to move the data of all reserves stored in the legacy layouts (from before the fields were added to the stored structs)
+    /// to the current storage. The reserves that were not migrated are read from the legacy layouts, so the migration only frees the legacy storage.
+    ///
+    /// # Returns
+    /// The number of reserves that had any data in the legacy layouts.
+    #[ink(message)]
+    fn migrate_reserves(&mut self) -> Result<u32, LendingPoolError>;
+
+    /// is used by anyone to move the data of accounts stored in the legacy layouts to the current storage.
+    /// Accounts registered with counters in range [`from_counter`, `to_counter`) are migrated.
+    ///
+    /// The accounts that were not migrated are read from the legacy layouts, so the migration mostly frees the legacy storage.
+    /// Once all registered accounts are migrated (in consecutive ranges starting from 0), reserves can be isolated again.
+    ///
+    /// # Returns
+    /// The number of accounts that had any data in the legacy layouts.
+    #[ink(message)]
+    fn migrate_accounts(
+        &mut self,
+        from_counter: u128,
+        to_counter: u128,
+    ) -> Result<u32, LendingPoolError>;
 }
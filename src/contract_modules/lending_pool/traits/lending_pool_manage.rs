@@ -1,12 +1,14 @@
 // SPDX-License-Identifier: BUSL-1.1
 use abax_library::structs::{
-    AssetRules, InterestRateModelParams, ReserveRestrictions,
+    AssetRules, CloseFactor, InterestAccrualMode, InterestRateModelParams,
+    LiquidationAuctionParams, PausableAction, ReserveAccountLimits,
+    ReserveIsolation, ReserveRestrictions, StableRateModel,
 };
 use ink::{
     contract_ref, env::DefaultEnvironment, prelude::string::String,
     prelude::vec::Vec, primitives::AccountId,
 };
-use pendzl::traits::Balance;
+use pendzl::traits::{Balance, Timestamp};
 
 use crate::lending_pool::{LendingPoolError, MarketRule, RuleId};
 pub type LendingPoolManageRef =
@@ -25,6 +27,8 @@ pub struct SetReserveFeesArgs {
 }
 
 /// Trait containing `AccessControl` messages used to manage 'LendingPool' parameters. Used by **managers**.
+///
+/// If the timelock is enabled (see `LendingPoolTimelock`), the messages corresponding to `ManageAction` variants return `TimelockActive` and must be queued instead.
 #[ink::trait_definition]
 pub trait LendingPoolManage {
     /// Sets `price_feed_provider` - a contract that implements PriceFeed and will be used to get prices from.
@@ -57,6 +61,7 @@ pub trait LendingPoolManage {
     ///
     /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `FlashLoanFeeTooHigh` returned if the fee is greater than 100%.
     #[ink(message)]
     fn set_flash_loan_fee_e6(
         &mut self,
@@ -72,17 +77,16 @@ pub trait LendingPoolManage {
     /// * `symbol` - symbol of the `asset`. It will be used to create sumbol for `AToken` and `VToken`.     
     /// * `decimals` - a decimal denominator of an asset (number already multiplied by 10^N where N is number of decimals)
     /// * `asset_rules' - `asset`'s AssetRules that will be used in default market rule (id = 0).
-    /// * `maximal_total_deposit` - maximal allowed total deposit. None for uncapped.
-    /// * `maximal_total_debt` - maximal allowed total debt. None for uncapped.
-    /// * `minimal_collateral` - the required minimal deposit of the asset by account to turn asset to be collateral.
-    /// * `minimal_debt` - the minimal possible debt that can be taken by account.
-    /// * `interest_rate_model` - check InterestRateModelParams
-    /// * `income_for_suppliers_part_e6` - indicates which part of an income should suppliers be paid - in E6 notation (multiplied by 10^6)
+    /// * `reserve_restrictions` - maximal total deposit and debt (None for uncapped), minimal collateral and minimal debt. Check ReserveRestrictions.
+    /// * `fees` - the deposit and the debt fees of the reserve. Check SetReserveFeesArgs.
+    /// * `interest_rate_model_params` - the interest rate model (strategy) to be used - `Adaptive`, `TwoSlope`, `MultiKink`, `Fixed` or `Continuous`. Check InterestRateModelParams.
+    ///    None registers the reserve without a model - its rates are not recalculated and the debt rate is set with `set_stablecoin_debt_rate_e18`. Used for the protocol stablecoin.
     ///
     /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a ASSET_LISTING_ADMIN.
     /// * `AlreadyRegistered` returned if asset was already registered.
     /// * `InvalidAssetRule` returned if asset rule is invalid.
+    /// * `ParameterBoundsError` returned if the asset rules, the fees or the interest rate model are out of bounds.
     #[ink(message)]
     #[allow(clippy::too_many_arguments)]
     fn register_asset(
@@ -99,6 +103,29 @@ pub trait LendingPoolManage {
         interest_rate_model_params: Option<InterestRateModelParams>,
     ) -> Result<(), LendingPoolError>;
 
+    /// Instantiates 'SToken' - the token wrapping the stable debt of the already registered `asset`.
+    ///
+    /// * `asset` - `AccountId` of the registered asset
+    /// * `s_token_code_hash` - code hash that will be used to initialize `SToken`
+    /// * `name` - name of the `asset`. It will be used to create name for `SToken`.
+    /// * `symbol` - symbol of the `asset`. It will be used to create sumbol for `SToken`.
+    /// * `decimals` - a decimal denominator of an asset (number already multiplied by 10^N where N is number of decimals)
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a ASSET_LISTING_ADMIN.
+    /// * `AssetNotRegistered` returned if `asset` is not registered.
+    /// * `AssetIsProtocolStablecoin` returned if `asset` is abax native stablecoin.
+    /// * `AlreadyRegistered` returned if the `SToken` of the `asset` was already registered.
+    #[ink(message)]
+    fn register_stable_debt_token(
+        &mut self,
+        asset: AccountId,
+        s_token_code_hash: [u8; 32],
+        name: String,
+        symbol: String,
+        decimals: u8,
+    ) -> Result<(), LendingPoolError>;
+
     ///  activates or disactivates reserve
     ///
     ///  * `active` - true if reserve should be activated. False if reserve should be disactivated.
@@ -106,6 +133,7 @@ pub trait LendingPoolManage {
     /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a EMERGENCY_ADMIN.
     /// * `AlreadySet` returned if trying to set already set state.
+    /// * `Closed` returned if trying to activate a closed reserve.
     #[ink(message)]
     fn set_reserve_is_active(
         &mut self,
@@ -113,11 +141,44 @@ pub trait LendingPoolManage {
         active: bool,
     ) -> Result<(), LendingPoolError>;
 
-    ///  Freezes or unfreezes reserve
+    /// starts the delisting of a reserve. Deposits and borrows of the `asset` and turning it on as collateral are blocked immediately.
+    /// Then the collateral coefficients of the `asset` are linearly ramped down to 0 in `ramp_duration`.
+    /// After that the delisting is in the settlement stage - deposits can be withdrawn to their owners by anyone (`settle_delisted_deposit`)
+    /// and the debts in the `asset` can be liquidated regardless of the account's collateralization. The liquidation of a collateralized account carries no penalty.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `ramp_duration` - duration (in milliseconds) of the ramp down of the collateral coefficients.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a ASSET_LISTING_ADMIN.
+    /// * `AlreadyDelisting` returned if the reserve is already being delisted.
+    #[ink(message)]
+    fn start_reserve_delisting(
+        &mut self,
+        asset: AccountId,
+        ramp_duration: Timestamp,
+    ) -> Result<(), LendingPoolError>;
+
+    /// closes the delisted reserve and deactivates it. The closed reserve can't be activated again.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a ASSET_LISTING_ADMIN.
+    /// * `NotInSettlement` returned if the delisting of the reserve is not in the settlement stage.
+    /// * `ReserveNotEmpty` returned if the reserve has deposits or debts.
+    #[ink(message)]
+    fn close_reserve(
+        &mut self,
+        asset: AccountId,
+    ) -> Result<(), LendingPoolError>;
+
+    ///  Freezes or unfreezes reserve. Frozen reserve doesn't accept deposits and borrows. Withdraws, repays and liquidations are still possible.
     ///
     ///  * `freeze` - true if reserve should be frozen. False if reserve should be unfrozen.
+    ///  * `reason_code` - code of the reason of the change, defined off-chain. 0 means unspecified. It is recorded together with the timestamp while freezing.
     ///
-    /// # Errorsfrozenfrozen
+    /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a EMERGENCY_ADMIN.
     /// * `AlreadySet` returned if trying to set already set state.
     #[ink(message)]
@@ -125,16 +186,51 @@ pub trait LendingPoolManage {
         &mut self,
         asset: AccountId,
         freeze: bool,
+        reason_code: u32,
+    ) -> Result<(), LendingPoolError>;
+
+    ///  Pauses or unpauses the `action` in all reserves. Unlike freezing, it allows to pause only selected actions (e.g. borrows) and leave the others (e.g. repays) working.
+    ///
+    ///  * `action` - action to be paused or unpaused.
+    ///  * `paused` - true if the action should be paused. False if it should be unpaused.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a EMERGENCY_ADMIN.
+    /// * `AlreadySet` returned if trying to set already set state.
+    #[ink(message)]
+    fn set_global_action_paused(
+        &mut self,
+        action: PausableAction,
+        paused: bool,
+    ) -> Result<(), LendingPoolError>;
+
+    ///  Pauses or unpauses the `action` in the reserve of the `asset`. The action is paused if it is paused either globally or in the reserve.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `action` - action to be paused or unpaused.
+    ///  * `paused` - true if the action should be paused. False if it should be unpaused.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a EMERGENCY_ADMIN.
+    /// * `AssetNotRegistered` returned if the `asset` is not registered.
+    /// * `AlreadySet` returned if trying to set already set state.
+    #[ink(message)]
+    fn set_reserve_action_paused(
+        &mut self,
+        asset: AccountId,
+        action: PausableAction,
+        paused: bool,
     ) -> Result<(), LendingPoolError>;
 
     /// modifies ReserveParameters in the `LendingPool`'s storage
     ///
     ///  * `asset` - `AccountId` of the registered asset
-    ///  * `interest_rate_model` - targetted debt rates at utilization of 50%, 60%, 70%, 80%, 90%, 95% 100%
+    ///  * `interest_rate_model` - the interest rate model to be used - adaptive, two-slope, multi-kink (targetted debt rates at utilization of 50%, 60%, 70%, 80%, 90%, 95% 100%), fixed or continuous (the rate at target drifts on each reserve update, without calls to `adjust_rate_at_target`).
     ///  * `income_for_suppliers_part_e6` - indicates which part of an income should suppliers be paid - in E6 notation (multiplied by 10^6)
     ///
     /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `ParameterBoundsError` returned if the target (optimal) utilization rate is not in (0, 100%) or the rates are not ordered (min <= max <= rate at max utilization for the adaptive and continuous models, non-decreasing with the utilization for the two-slope and multi-kink models) or the full adjustment duration of the continuous model is 0.
     #[ink(message)]
     fn set_interest_rate_model(
         &mut self,
@@ -142,6 +238,14 @@ pub trait LendingPoolManage {
         interest_rate_model: InterestRateModelParams,
     ) -> Result<(), LendingPoolError>;
 
+    /// modifies ReserveFees in the `LendingPool`'s storage
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `reserve_fees` - deposit and debt fees. 10^6 = 100%
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `DepositFeeTooHigh` / `DebtFeeTooHigh` returned if a fee is greater than 100%.
     #[ink(message)]
     fn set_reserve_fees(
         &mut self,
@@ -149,6 +253,37 @@ pub trait LendingPoolManage {
         reserve_fees: SetReserveFeesArgs,
     ) -> Result<(), LendingPoolError>;
 
+    /// modifies the interest accrual mode of a reserve. The interest accrued so far is accounted for with the previous mode.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `accrual_mode` - `Simple` (1 + rate * dt) or `Compound` (exp(rate * dt)) accrual of the interest between the updates of the reserve.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `AssetIsProtocolStablecoin` returned if the `asset` is the protocol stablecoin.
+    #[ink(message)]
+    fn set_reserve_accrual_mode(
+        &mut self,
+        asset: AccountId,
+        accrual_mode: InterestAccrualMode,
+    ) -> Result<(), LendingPoolError>;
+
+    /// modifies the size of the ring of the time-weighted utilization rate entries of a reserve.
+    /// The ring is restarted from the latest entry, so the entries older than it are discarded.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `size` - the new number of the entries in the ring.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `TwError::InvalidSize` returned if the `size` is out of bounds.
+    #[ink(message)]
+    fn set_reserve_tw_size(
+        &mut self,
+        asset: AccountId,
+        size: u32,
+    ) -> Result<(), LendingPoolError>;
+
     /// modifies ReserveRestricion in the `LendingPool`'s storage
     ///
     ///  * `asset` - `AccountId` of the registered asset
@@ -166,6 +301,71 @@ pub trait LendingPoolManage {
         reserve_restrictions: ReserveRestrictions,
     ) -> Result<(), LendingPoolError>;
 
+    /// modifies the isolation mode parameters of a reserve.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `reserve_isolation` - `debt_ceiling_e8` - if set, the asset becomes an isolated collateral (it can be a collateral only on its own) and the total debt (in USD, 10^8 precision) backed by it is limited.
+    ///     `is_isolation_borrowable` - whether the asset can be borrowed by accounts using an isolated collateral.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `ReserveIsolationError::AssetUsedAsCollateral` returned if the isolation is turned on while some accounts use the asset as collateral.
+    /// * `AccountsNotMigrated` returned if the isolation is turned on before all accounts are migrated by `migrate_accounts`.
+    #[ink(message)]
+    fn set_reserve_isolation(
+        &mut self,
+        asset: AccountId,
+        reserve_isolation: ReserveIsolation,
+    ) -> Result<(), LendingPoolError>;
+
+    /// modifies the limits on a single account's position in a reserve.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `reserve_account_limits` - `maximal_account_deposit` - if set, limits the deposit of a single account.
+    ///     `maximal_account_debt` - if set, limits the debt (variable and stable) of a single account.
+    ///     `debt_concentration_limit` - if set, limits the share of a single account in the total debt once the total debt reaches `minimal_total_debt`.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `DebtShareOutOfBounds` returned if the maximal debt share is not in (0, 100%].
+    #[ink(message)]
+    fn set_reserve_account_limits(
+        &mut self,
+        asset: AccountId,
+        reserve_account_limits: ReserveAccountLimits,
+    ) -> Result<(), LendingPoolError>;
+
+    /// modifies the close factor of a reserve - a limit on the debt that can be repaid in a single liquidation.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `close_factor` - close factor to be used while repaying `asset` during liquidation. None for no limit.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `InvalidCloseFactor` returned if the `close_factor` is invalid.
+    #[ink(message)]
+    fn set_reserve_close_factor(
+        &mut self,
+        asset: AccountId,
+        close_factor: Option<CloseFactor>,
+    ) -> Result<(), LendingPoolError>;
+
+    /// modifies the stable rate model of a reserve. Setting the model enables stable rate borrowing of `asset`.
+    ///
+    ///  * `asset` - `AccountId` of the registered asset
+    ///  * `stable_rate_model` - stable rate model to be used. None disables new stable rate borrows (the existing stable debts still accrue interest).
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `StableDebtTokenNotRegistered` returned if the `SToken` of the `asset` is not registered.
+    /// * `AssetIsProtocolStablecoin` returned if `asset` is abax native stablecoin.
+    #[ink(message)]
+    fn set_stable_rate_model(
+        &mut self,
+        asset: AccountId,
+        stable_rate_model: Option<StableRateModel>,
+    ) -> Result<(), LendingPoolError>;
+
     /// modifies the stablecoin debt rate
     ///
     ///  * `asset` - `AccountId` of the registered stable asset
@@ -188,6 +388,7 @@ pub trait LendingPoolManage {
     /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a STABLECOIN_RATE_ADMIN.
     /// * `InvalidAssetRule` returned if the `market_rule` contains invalid AssetRule.
+    /// * `ParameterBoundsError` returned if the `market_rule` contains AssetRule out of bounds.
     #[ink(message)]
     fn add_market_rule(
         &mut self,
@@ -202,6 +403,7 @@ pub trait LendingPoolManage {
     /// # Errors
     /// * `AccessControl::MisingRole` returned if the caller is not a STABLECOIN_RATE_ADMIN.
     /// * `InvalidAssetRule` returned if the asset_rules is invalid.
+    /// * `ParameterBoundsError` returned if the asset_rules is out of bounds.
     #[ink(message)]
     fn modify_asset_rule(
         &mut self,
@@ -210,6 +412,21 @@ pub trait LendingPoolManage {
         asset_rules: AssetRules,
     ) -> Result<(), LendingPoolError>;
 
+    /// enables, modifies or disables (None) the Dutch-auction liquidation mode of the market rule identified by market_rule_id.
+    ///
+    /// * `market_rule_id` - id of market rule which should be modified
+    /// * `auction_params` - params of the auction mode. None disables the auction mode.
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a PARAMETERS_ADMIN.
+    /// * `MarketRuleInvalidId` returned if the market rule with `market_rule_id` doesn't exist.
+    /// * `InvalidAuctionParams` returned if the `auction_params` are invalid.
+    #[ink(message)]
+    fn set_market_rule_liquidation_auction(
+        &mut self,
+        market_rule_id: RuleId,
+        auction_params: Option<LiquidationAuctionParams>,
+    ) -> Result<(), LendingPoolError>;
+
     /// collects income generated by the protocol
     ///
     /// * `assets` - vector of assets that income should be taken. If None takes income from all registered assets.
@@ -0,0 +1,227 @@
+// SPDX-License-Identifier: BUSL-1.1
+use abax_library::structs::{
+    validate_flash_loan_fee_e6, AssetRules, CloseFactor, InterestAccrualMode,
+    InterestRateModelParams, LiquidationAuctionParams, ReserveAccountLimits,
+    ReserveFees, ReserveIsolation, ReserveRestrictions, StableRateModel,
+};
+use ink::{contract_ref, env::DefaultEnvironment, primitives::AccountId};
+use pendzl::{contracts::access_control::RoleType, traits::Timestamp};
+
+use crate::lending_pool::{
+    LendingPoolError, MarketRule, RuleId, SetReserveFeesArgs, PARAMETERS_ADMIN,
+    ROLE_ADMIN, STABLECOIN_RATE_ADMIN,
+};
+
+pub type LendingPoolTimelockRef =
+    contract_ref!(LendingPoolTimelock, DefaultEnvironment);
+
+/// Parameter change that can be queued in the timelock. Each variant corresponds to the `LendingPoolManage` message with the same name.
+#[derive(Debug, scale::Encode, scale::Decode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum ManageAction {
+    SetPriceFeedProvider {
+        price_feed_provider: AccountId,
+    },
+    SetFeeReductionProvider {
+        fee_reduction_provider: AccountId,
+    },
+    SetFlashLoanFeeE6 {
+        flash_loan_fee_e6: u128,
+    },
+    SetReserveRestrictions {
+        asset: AccountId,
+        reserve_restrictions: ReserveRestrictions,
+    },
+    SetInterestRateModel {
+        asset: AccountId,
+        interest_rate_model_params: InterestRateModelParams,
+    },
+    SetReserveFees {
+        asset: AccountId,
+        reserve_fees: SetReserveFeesArgs,
+    },
+    SetReserveAccrualMode {
+        asset: AccountId,
+        accrual_mode: InterestAccrualMode,
+    },
+    SetReserveTwSize {
+        asset: AccountId,
+        size: u32,
+    },
+    SetReserveIsolation {
+        asset: AccountId,
+        reserve_isolation: ReserveIsolation,
+    },
+    SetReserveAccountLimits {
+        asset: AccountId,
+        reserve_account_limits: ReserveAccountLimits,
+    },
+    SetReserveCloseFactor {
+        asset: AccountId,
+        close_factor: Option<CloseFactor>,
+    },
+    SetStableRateModel {
+        asset: AccountId,
+        stable_rate_model: Option<StableRateModel>,
+    },
+    SetStablecoinDebtRateE18 {
+        asset: AccountId,
+        debt_rate_e18: u64,
+    },
+    AddMarketRule {
+        market_rule: MarketRule,
+    },
+    ModifyAssetRule {
+        market_rule_id: RuleId,
+        asset: AccountId,
+        asset_rules: AssetRules,
+    },
+    SetMarketRuleLiquidationAuction {
+        market_rule_id: RuleId,
+        auction_params: Option<LiquidationAuctionParams>,
+    },
+    SetTimelockDelay {
+        timelock_delay: Timestamp,
+    },
+}
+
+impl ManageAction {
+    /// Returns the role required to queue the action - the same role that is required to call the corresponding message directly.
+    pub fn required_role(&self) -> RoleType {
+        match self {
+            ManageAction::SetStablecoinDebtRateE18 { .. } => {
+                STABLECOIN_RATE_ADMIN
+            }
+            ManageAction::SetTimelockDelay { .. } => ROLE_ADMIN,
+            _ => PARAMETERS_ADMIN,
+        }
+    }
+
+    /// Verifies the bounds of the parameters that don't depend on the state of the pool - the same bounds that are verified on execution.
+    pub fn validate(&self) -> Result<(), LendingPoolError> {
+        match self {
+            ManageAction::SetFlashLoanFeeE6 { flash_loan_fee_e6 } => {
+                validate_flash_loan_fee_e6(*flash_loan_fee_e6)?
+            }
+            ManageAction::SetInterestRateModel {
+                interest_rate_model_params,
+                ..
+            } => interest_rate_model_params.validate()?,
+            ManageAction::SetReserveFees { reserve_fees, .. } => {
+                ReserveFees::new(
+                    reserve_fees.deposit_fee_e6,
+                    reserve_fees.debt_fee_e6,
+                )
+                .validate()?
+            }
+            ManageAction::SetReserveAccountLimits {
+                reserve_account_limits,
+                ..
+            } => reserve_account_limits.validate()?,
+            ManageAction::SetReserveCloseFactor {
+                close_factor: Some(close_factor),
+                ..
+            } => close_factor.validate()?,
+            ManageAction::AddMarketRule { market_rule } => {
+                for asset_rules in market_rule.iter().flatten() {
+                    asset_rules.validate_new_rule(&None)?;
+                    asset_rules.validate_bounds()?;
+                }
+            }
+            ManageAction::ModifyAssetRule { asset_rules, .. } => {
+                asset_rules.validate_bounds()?
+            }
+            ManageAction::SetMarketRuleLiquidationAuction {
+                auction_params: Some(auction_params),
+                ..
+            } => auction_params.validate()?,
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Parameter change waiting in the timelock.
+#[derive(Debug, scale::Encode, scale::Decode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct QueuedManageAction {
+    pub action: ManageAction,
+    /// account that has queued the action.
+    pub proposer: AccountId,
+    /// timestamp from which the action can be executed.
+    pub eta: Timestamp,
+}
+
+/// Trait containing messages of the optional timelock layer over the `LendingPoolManage` parameter changes.
+///
+/// If the timelock delay is greater than 0, the parameter changes listed in `ManageAction` can't be made directly.
+/// They must be queued by the holder of the required role and can be executed by anyone after the delay.
+/// Registering assets, delisting, activating, freezing and taking protocol income are not timelocked.
+#[ink::trait_definition]
+pub trait LendingPoolTimelock {
+    /// sets the timelock delay. Setting the delay greater than 0 enables the timelock.
+    /// While the timelock is enabled, the delay can be changed (or the timelock disabled) only by queued `SetTimelockDelay` action.
+    ///
+    ///  * `timelock_delay` - delay (in milliseconds) between queuing and execution of a parameter change.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a ROLE_ADMIN.
+    /// * `TimelockActive` returned if the timelock is enabled.
+    #[ink(message)]
+    fn set_timelock_delay(
+        &mut self,
+        timelock_delay: Timestamp,
+    ) -> Result<(), LendingPoolError>;
+
+    /// queues the parameter change. It can be executed after the timelock delay.
+    ///
+    ///  * `action` - parameter change to be queued.
+    ///
+    /// # Returns
+    /// The id of the queued action.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller doesn't have the role required by the `action`.
+    /// * `TimelockDisabled` returned if the timelock is disabled - the parameter change should be made directly.
+    /// * `ParameterBoundsError`, `CloseFactorError`, `LiquidationAuctionError` or `AssetRulesError` returned if the parameters of the `action` are out of their bounds.
+    #[ink(message)]
+    fn queue_manage_action(
+        &mut self,
+        action: ManageAction,
+    ) -> Result<u32, LendingPoolError>;
+
+    /// is used by anyone to execute the queued parameter change after its eta and before the `MANAGE_ACTION_GRACE_PERIOD` after it passes.
+    ///
+    ///  * `action_id` - id of the queued action.
+    ///
+    /// # Errors
+    /// * `ManageActionNotQueued` returned if there is no queued action with `action_id`.
+    /// * `ManageActionNotReady` returned if the eta of the action has not passed yet.
+    /// * `ManageActionExpired` returned if the grace period after the eta has passed. The action should be cancelled.
+    /// * `ManageActionProposerLostRole` returned if the proposer no longer has the role required by the action.
+    /// * Any error returned by the corresponding `LendingPoolManage` message.
+    #[ink(message)]
+    fn execute_manage_action(
+        &mut self,
+        action_id: u32,
+    ) -> Result<(), LendingPoolError>;
+
+    /// cancels the queued parameter change.
+    ///
+    ///  * `action_id` - id of the queued action.
+    ///
+    /// # Errors
+    /// * `AccessControl::MisingRole` returned if the caller is not a EMERGENCY_ADMIN.
+    /// * `ManageActionNotQueued` returned if there is no queued action with `action_id`.
+    #[ink(message)]
+    fn cancel_manage_action(
+        &mut self,
+        action_id: u32,
+    ) -> Result<(), LendingPoolError>;
+}
@@ -1,22 +1,66 @@
 // SPDX-License-Identifier: BUSL-1.1
 use abax_library::structs::{
-    AccountConfig, AccountReserveData, InterestRateModel, ReserveAbacusTokens,
-    ReserveData, ReserveFees, ReserveIndexes, ReserveRestrictions, TwEntry,
-    TwIndex,
+    AccountConfig, AccountHealth, AccountReserveData, Action, ActionsPause,
+    CloseFactor, DelistingStage, InterestAccrualMode, InterestRateModel,
+    LiquidationAuction, LiquidationAuctionParams, ReserveAbacusTokens,
+    ReserveAccountLimits, ReserveData, ReserveDelisting, ReserveFees,
+    ReserveFreeze, ReserveIndexes, ReserveIsolation, ReserveRestrictions,
+    StableRateModel, TwEntry, TwIndex,
 };
 use ink::{
     contract_ref, env::DefaultEnvironment, prelude::vec::Vec,
     primitives::AccountId,
 };
-use pendzl::traits::Balance;
+use pendzl::traits::{Balance, Timestamp};
 
 use crate::lending_pool::{
-    DecimalMultiplier, LendingPoolError, MarketRule, RuleId,
+    DecimalMultiplier, LendingPoolError, MarketRule, QueuedManageAction, RuleId,
 };
 
 pub type LendingPoolViewRef =
     contract_ref!(LendingPoolView, DefaultEnvironment);
 
+/// Operational state of a reserve - used by the off-chain monitoring.
+#[derive(Debug, scale::Encode, scale::Decode, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ReserveOperationalState {
+    pub activated: bool,
+    pub frozen: bool,
+    /// reason code and timestamp of freezing. None if the reserve is not frozen.
+    pub freeze: Option<ReserveFreeze>,
+    /// actions paused globally or in the reserve.
+    pub paused_actions: ActionsPause,
+    pub delisting_stage: DelistingStage,
+}
+
+/// Health of an account with debt - used by the off-chain liquidators.
+#[derive(Debug, scale::Encode, scale::Decode, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct LiquidationCandidate {
+    pub account: AccountId,
+    /// same as returned by `get_account_free_collateral_coefficient`. If false the account can be liquidated.
+    pub collateralized: bool,
+    /// surplus (if collateralized) or deficit (if not) of the collateral power over the debt power. E6 notation.
+    pub power_e6: u128,
+    /// asset and amount of the largest (by value) debt.
+    pub largest_debt: Option<(AccountId, Balance)>,
+    /// asset and amount of the largest (by value) collateral.
+    pub largest_collateral: Option<(AccountId, Balance)>,
+}
+
+/// Result of simulating actions of an account - used by the front ends and the off-chain risk monitoring.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct ActionsSimulation {
+    /// true if the account would be collateralized after the actions.
+    pub collateralized: bool,
+    pub account_health: AccountHealth,
+    /// the account's data in the reserves of the actions' assets after the actions.
+    pub account_reserve_datas: Vec<(AccountId, AccountReserveData)>,
+    /// the data (including the rates) of the reserves of the actions' assets after the actions.
+    pub reserve_datas: Vec<(AccountId, ReserveData)>,
+}
+
 /// Trait containing non-mutable messages - this trait should not be called by other smart contracts.
 /// The main use of this trait is to read data from the `LendingPool`'s storage to the off-chain world.
 #[ink::trait_definition]
@@ -25,6 +69,15 @@ pub trait LendingPoolView {
     fn view_flash_loan_fee_e6(&self) -> u128;
     #[ink(message)]
     fn view_asset_id(&self, asset: AccountId) -> Option<RuleId>;
+    /// Returns the timelock delay. 0 means that the timelock is disabled.
+    #[ink(message)]
+    fn view_timelock_delay(&self) -> Timestamp;
+    #[ink(message)]
+    fn view_queued_manage_action(
+        &self,
+        action_id: u32,
+    ) -> Option<QueuedManageAction>;
+    /// Returns registered assets ordered by their ids. Closed reserves are omitted.
     #[ink(message)]
     fn view_registered_assets(&self) -> Vec<AccountId>;
     #[ink(message)]
@@ -39,6 +92,11 @@ pub trait LendingPoolView {
     #[ink(message)]
     fn view_reserve_fees(&self, asset: AccountId) -> Option<ReserveFees>;
     #[ink(message)]
+    fn view_reserve_accrual_mode(
+        &self,
+        asset: AccountId,
+    ) -> Option<InterestAccrualMode>;
+    #[ink(message)]
     fn view_interest_rate_model(
         &self,
         asset: AccountId,
@@ -49,6 +107,53 @@ pub trait LendingPoolView {
         asset: AccountId,
     ) -> Option<ReserveRestrictions>;
     #[ink(message)]
+    fn view_reserve_isolation(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveIsolation>;
+    #[ink(message)]
+    fn view_reserve_account_limits(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveAccountLimits>;
+    /// Returns the operational state of the reserve (activation, freeze with its reason and time, paused actions and delisting stage).
+    #[ink(message)]
+    fn view_reserve_operational_state(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveOperationalState>;
+    #[ink(message)]
+    fn view_global_actions_pause(&self) -> ActionsPause;
+    /// Returns the actions paused in the reserve. The actions paused globally are not included.
+    #[ink(message)]
+    fn view_reserve_actions_pause(
+        &self,
+        asset: AccountId,
+    ) -> Option<ActionsPause>;
+    /// Returns the total debt (in USD, 10^8 precision) backed by the isolated collateral `asset`.
+    #[ink(message)]
+    fn view_isolated_debt_e8(&self, asset: AccountId) -> Option<u128>;
+    #[ink(message)]
+    fn view_reserve_delisting(
+        &self,
+        asset: AccountId,
+    ) -> Option<ReserveDelisting>;
+    #[ink(message)]
+    fn view_reserve_delisting_stage(
+        &self,
+        asset: AccountId,
+    ) -> Option<DelistingStage>;
+    #[ink(message)]
+    fn view_reserve_close_factor(
+        &self,
+        asset: AccountId,
+    ) -> Option<CloseFactor>;
+    #[ink(message)]
+    fn view_stable_rate_model(
+        &self,
+        asset: AccountId,
+    ) -> Option<StableRateModel>;
+    #[ink(message)]
     fn view_reserve_tokens(
         &self,
         asset: AccountId,
@@ -74,11 +179,60 @@ pub trait LendingPoolView {
     fn view_account_config(&self, account: AccountId) -> AccountConfig;
     #[ink(message)]
     fn view_market_rule(&self, market_rule_id: RuleId) -> Option<MarketRule>;
+    /// Returns the liquidation auction params of the market rule. None if the auction mode is disabled.
+    #[ink(message)]
+    fn view_market_rule_liquidation_auction(
+        &self,
+        market_rule_id: RuleId,
+    ) -> Option<LiquidationAuctionParams>;
+    #[ink(message)]
+    fn view_liquidation_auction(
+        &self,
+        account: AccountId,
+    ) -> Option<LiquidationAuction>;
+    /// Returns up to `count` active liquidation auctions starting from the `from` position.
+    #[ink(message)]
+    fn view_active_liquidation_auctions(
+        &self,
+        from: u32,
+        count: u32,
+    ) -> Vec<(AccountId, LiquidationAuction)>;
     #[ink(message)]
     fn get_account_free_collateral_coefficient(
         &self,
         account_address: AccountId,
     ) -> (bool, u128);
+    /// Returns the values of the account's collaterals and debts, the values weighted by the coefficients of the account's market rule and the health factor.
+    #[ink(message)]
+    fn view_account_health(
+        &self,
+        account: AccountId,
+    ) -> Result<AccountHealth, LendingPoolError>;
+    /// Returns the maximal amount of `asset` that the `account` can borrow without becoming undercollateralized.
+    #[ink(message)]
+    fn view_max_borrowable(
+        &self,
+        account: AccountId,
+        asset: AccountId,
+    ) -> Result<Balance, LendingPoolError>;
+    /// Returns the maximal amount of `asset` that the `account` can withdraw without becoming undercollateralized.
+    #[ink(message)]
+    fn view_max_withdrawable(
+        &self,
+        account: AccountId,
+        asset: AccountId,
+    ) -> Result<Balance, LendingPoolError>;
+    /// Simulates `actions` (as in `multi_op`) performed on behalf of `account` and returns the resulting balances, rates and health.
+    /// The collateralization is not required - the result tells whether the account would be collateralized.
+    ///
+    /// The message always reverts the state (the result is returned with the revert flag) so nothing is committed.
+    /// Pauses and token transfers are not checked.
+    #[ink(message)]
+    fn simulate_actions(
+        &mut self,
+        account: AccountId,
+        actions: Vec<Action>,
+    ) -> Result<ActionsSimulation, LendingPoolError>;
 
     #[ink(message)]
     fn view_protocol_income(
@@ -86,6 +240,29 @@ pub trait LendingPoolView {
         assets: Option<Vec<AccountId>>,
     ) -> Vec<(AccountId, Balance)>;
 
+    /// Returns the amount of underlying tokens in the safety fund of the reserve.
+    #[ink(message)]
+    fn view_reserve_safety_fund(&self, asset: AccountId) -> Option<Balance>;
+
+    /// Returns accounts that have debt but no collateral left (bad debt).
+    /// Accounts registered with counters in range [`from_counter`, `to_counter`) are checked.
+    #[ink(message)]
+    fn view_accounts_with_unbacked_debt(
+        &self,
+        from_counter: u128,
+        to_counter: u128,
+    ) -> Vec<AccountId>;
+
+    /// Returns the health of accounts that have debt, sorted from the least healthy
+    /// i.e. undercollateralized accounts by the descending deficit, then collateralized accounts by the ascending surplus.
+    /// Accounts registered with counters in range [`from_counter`, `to_counter`) are checked.
+    #[ink(message)]
+    fn view_liquidation_candidates(
+        &self,
+        from_counter: u128,
+        to_counter: u128,
+    ) -> Result<Vec<LiquidationCandidate>, LendingPoolError>;
+
     #[ink(message)]
     fn view_asset_tw_index(&self, asset: AccountId) -> Option<TwIndex>;
 
@@ -102,6 +279,6 @@ pub trait LendingPoolView {
         &self,
         period: u64,
         asset: AccountId,
-        guessed_index: u32,
+        guessed_index: Option<u32>,
     ) -> Result<u32, LendingPoolError>;
 }
@@ -4,7 +4,9 @@ mod lending_pool_actions;
 mod lending_pool_flash;
 mod lending_pool_maintain;
 mod lending_pool_manage;
+mod lending_pool_timelock;
 mod lending_pool_view;
+mod s_token_interface;
 mod v_token_interface;
 
 pub use a_token_interface::*;
@@ -12,5 +14,7 @@ pub use lending_pool_actions::*;
 pub use lending_pool_flash::*;
 pub use lending_pool_maintain::*;
 pub use lending_pool_manage::*;
+pub use lending_pool_timelock::*;
 pub use lending_pool_view::*;
+pub use s_token_interface::*;
 pub use v_token_interface::*;
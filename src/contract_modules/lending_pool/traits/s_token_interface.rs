@@ -0,0 +1,30 @@
+// SPDX-License-Identifier: BUSL-1.1
+use ink::{contract_ref, env::DefaultEnvironment, primitives::AccountId};
+use pendzl::traits::Balance;
+
+pub type LendingPoolSTokenInterfaceRef =
+    contract_ref!(LendingPoolSTokenInterface, DefaultEnvironment);
+
+/// Trait containing messages that are accessible to **SToken** - the PSP22 Wrapper of stable debts.
+#[ink::trait_definition]
+pub trait LendingPoolSTokenInterface {
+    /// Returns LendingPool's total stable debt of accounts in the context of an underlying asset.
+    ///
+    /// * `underlying_asset` - AccountId (aka address) of an asset to look up total stable debt of.
+    ///
+    /// # Errors None
+    #[ink(message)]
+    fn total_stable_debt_of(&self, underlying_asset: AccountId) -> Balance;
+    /// Returns the specified `account`'s stable debt in the context of an `underlying_asset`.
+    ///
+    /// * `underlying_asset` - AccountId (aka address) of an asset to look up account's stable debt of.
+    /// * `account` - AccountId (aka address) of an account to look up stable debt for.
+    ///
+    /// # Errors None
+    #[ink(message)]
+    fn account_stable_debt_of(
+        &self,
+        underlying_asset: AccountId,
+        account: AccountId,
+    ) -> Balance;
+}
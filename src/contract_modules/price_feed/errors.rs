@@ -1,4 +1,6 @@
 // SPDX-License-Identifier: BUSL-1.1
+use pendzl::math::errors::MathError;
+
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum PriceFeedError {
@@ -6,4 +8,19 @@ pub enum PriceFeedError {
     NoSuchAsset,
     /// The price feed is not available.
     NoPriceFeed,
+    /// The price was updated longer ago than the maximal price age of the asset.
+    StalePrice,
+    /// The price deviates from the anchor price of the asset by more than the maximal price deviation of the asset.
+    PriceDeviationTooLarge,
+    /// The prices from the sources of the asset differ by more than allowed.
+    PriceSourcesDisagree,
+    /// The asset is pegged to an asset that is pegged itself.
+    InvalidPriceType,
+    MathError(MathError),
+}
+
+impl From<MathError> for PriceFeedError {
+    fn from(error: MathError) -> Self {
+        PriceFeedError::MathError(error)
+    }
 }
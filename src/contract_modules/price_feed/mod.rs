@@ -1,6 +1,10 @@
 // SPDX-License-Identifier: BUSL-1.1
 mod errors;
 mod price_feed_trait;
+mod price_limits;
+mod price_sources;
 
 pub use errors::*;
 pub use price_feed_trait::*;
+pub use price_limits::*;
+pub use price_sources::*;
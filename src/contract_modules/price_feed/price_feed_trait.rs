@@ -11,6 +11,12 @@ pub type PriceFeedRef = contract_ref!(PriceFeed, DefaultEnvironment);
 #[ink::trait_definition]
 pub trait PriceFeed {
     /// Returns the latest price of the given 'assets'.
+    ///
+    /// # Errors
+    /// * `NoSuchAsset` returned if any of the `assets` is not supported.
+    /// * `NoPriceFeed` returned if the price of any of the `assets` is not available.
+    /// * `StalePrice` returned if the price of any of the `assets` is older than allowed.
+    /// * `PriceDeviationTooLarge` returned if the price of any of the `assets` deviates from its anchor price by more than allowed.
     #[ink(message)]
     fn get_latest_prices(
         &self,
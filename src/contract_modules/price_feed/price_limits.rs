@@ -0,0 +1,90 @@
+// SPDX-License-Identifier: BUSL-1.1
+use super::PriceFeedError;
+
+/// Limits the change of the price of an asset relative to its anchor price.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct PriceDeviationLimit {
+    /// maximal change of the price relative to the anchor price. 10^6 = 100%.
+    pub max_deviation_e6: u128,
+    /// minimal time (in milliseconds) between consecutive updates of the anchor price.
+    /// A price that deviates too much is rejected for at most this long - then the anchor can be moved to it.
+    pub anchor_cooldown: u64,
+}
+
+/// The price of an asset the later prices are compared against.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct PriceAnchor {
+    pub price: u128,
+    /// time of setting the anchor.
+    pub timestamp: u64,
+}
+
+impl PriceDeviationLimit {
+    pub fn ensure_not_deviated(
+        &self,
+        anchor: &PriceAnchor,
+        price: u128,
+    ) -> Result<(), PriceFeedError> {
+        // |price - anchor.price| / anchor.price > max_deviation_e6 / 10^6
+        if price.abs_diff(anchor.price).saturating_mul(1_000_000)
+            > self.max_deviation_e6.saturating_mul(anchor.price)
+        {
+            return Err(PriceFeedError::PriceDeviationTooLarge);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the anchor can be moved at `timestamp` - `anchor_cooldown` has passed since it was set.
+    pub fn can_reanchor(&self, anchor: &PriceAnchor, timestamp: u64) -> bool {
+        timestamp.saturating_sub(anchor.timestamp) >= self.anchor_cooldown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: PriceDeviationLimit = PriceDeviationLimit {
+        max_deviation_e6: 100_000, // 10%
+        anchor_cooldown: 1_000,
+    };
+    const ANCHOR: PriceAnchor = PriceAnchor {
+        price: 1_000,
+        timestamp: 5_000,
+    };
+
+    #[test]
+    fn ensure_not_deviated() {
+        assert_eq!(LIMIT.ensure_not_deviated(&ANCHOR, 1_000), Ok(()));
+        assert_eq!(LIMIT.ensure_not_deviated(&ANCHOR, 1_100), Ok(()));
+        assert_eq!(LIMIT.ensure_not_deviated(&ANCHOR, 900), Ok(()));
+        assert_eq!(
+            LIMIT.ensure_not_deviated(&ANCHOR, 1_101),
+            Err(PriceFeedError::PriceDeviationTooLarge)
+        );
+        assert_eq!(
+            LIMIT.ensure_not_deviated(&ANCHOR, 899),
+            Err(PriceFeedError::PriceDeviationTooLarge)
+        );
+        assert_eq!(
+            LIMIT.ensure_not_deviated(&ANCHOR, 0),
+            Err(PriceFeedError::PriceDeviationTooLarge)
+        );
+    }
+
+    #[test]
+    fn can_reanchor() {
+        assert!(!LIMIT.can_reanchor(&ANCHOR, 4_000));
+        assert!(!LIMIT.can_reanchor(&ANCHOR, 5_999));
+        assert!(LIMIT.can_reanchor(&ANCHOR, 6_000));
+        assert!(LIMIT.can_reanchor(&ANCHOR, 100_000));
+    }
+}
@@ -0,0 +1,167 @@
+// SPDX-License-Identifier: BUSL-1.1
+use ink::prelude::{string::String, vec, vec::Vec};
+use ink::primitives::AccountId;
+
+use super::PriceFeedError;
+
+/// A source of the price of an asset.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum PriceSource {
+    /// contract implementing `OracleGetters` (DIA). The price is looked up by the asset's symbol.
+    DiaOracle(AccountId),
+    /// contract implementing `PriceFeed` (for example another provider or an on-chain TWAP). The price is looked up by the asset's AccountId.
+    PriceFeed(AccountId),
+}
+
+/// The way the prices from multiple sources are combined into one price.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum PriceAggregation {
+    /// median of the available prices. For even number of prices the mean of the two middle ones.
+    Median,
+    /// the price of the first source (in order) that is available.
+    PrimaryWithFallback,
+    /// mean of the available prices. Fails if the spread between the highest and the lowest price is greater than `max_spread_e6` of the lowest price (10^6 = 100%).
+    BoundedMean { max_spread_e6: u128 },
+}
+
+/// Price sources of an asset and the way they are aggregated.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct AssetPriceSources {
+    pub sources: Vec<PriceSource>,
+    pub aggregation: PriceAggregation,
+}
+
+/// The way the price of an asset that has no (single) price pair is computed. All prices are in 10^18 precision.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum AssetPriceType {
+    /// the price is constant.
+    Fixed { price_e18: u128 },
+    /// the price is the price of the `asset` multiplied by `ratio_e18`. The `asset` can't be pegged itself.
+    Pegged { asset: AccountId, ratio_e18: u128 },
+    /// the price is the product of the prices of `first` and `second` symbols.
+    Product { first: String, second: String },
+    /// the price is the price of `numerator` symbol divided by the price of `denominator` symbol.
+    Quotient {
+        numerator: String,
+        denominator: String,
+    },
+}
+
+impl PriceAggregation {
+    /// Combines `quotes` - pairs of (source index, price) ordered by source index - into one price.
+    ///
+    /// # Returns
+    /// (price, indexes of sources that were used)
+    pub fn aggregate(
+        &self,
+        quotes: &[(usize, u128)],
+    ) -> Result<(u128, Vec<usize>), PriceFeedError> {
+        if quotes.is_empty() {
+            return Err(PriceFeedError::NoPriceFeed);
+        }
+        match self {
+            PriceAggregation::PrimaryWithFallback => {
+                Ok((quotes[0].1, vec![quotes[0].0]))
+            }
+            PriceAggregation::Median => {
+                let mut sorted = quotes.to_vec();
+                sorted.sort_by_key(|quote| quote.1);
+                let middle = sorted.len() / 2;
+                if sorted.len() % 2 == 1 {
+                    Ok((sorted[middle].1, vec![sorted[middle].0]))
+                } else {
+                    let (lower, upper) = (sorted[middle - 1], sorted[middle]);
+                    // lower.1 <= upper.1 => no overflow
+                    let price = lower.1 + (upper.1 - lower.1) / 2;
+                    Ok((price, vec![lower.0, upper.0]))
+                }
+            }
+            PriceAggregation::BoundedMean { max_spread_e6 } => {
+                let min = quotes.iter().map(|quote| quote.1).min().unwrap(); // quotes is not empty
+                let max = quotes.iter().map(|quote| quote.1).max().unwrap(); // quotes is not empty
+                if (max - min).saturating_mul(1_000_000)
+                    > max_spread_e6.saturating_mul(min)
+                {
+                    return Err(PriceFeedError::PriceSourcesDisagree);
+                }
+                let mut sum: u128 = 0;
+                for quote in quotes {
+                    sum = sum
+                        .checked_add(quote.1)
+                        .ok_or(PriceFeedError::PriceSourcesDisagree)?;
+                }
+                Ok((
+                    sum / quotes.len() as u128,
+                    quotes.iter().map(|quote| quote.0).collect(),
+                ))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn primary_with_fallback_uses_the_first_available_source() {
+        let aggregation = PriceAggregation::PrimaryWithFallback;
+        assert_eq!(
+            aggregation.aggregate(&[(0, 100), (1, 200)]),
+            Ok((100, vec![0]))
+        );
+        // the primary source (0) is not available
+        assert_eq!(
+            aggregation.aggregate(&[(1, 200), (2, 300)]),
+            Ok((200, vec![1]))
+        );
+        assert_eq!(
+            aggregation.aggregate(&[]),
+            Err(PriceFeedError::NoPriceFeed)
+        );
+    }
+
+    #[test]
+    fn median_of_available_sources() {
+        let aggregation = PriceAggregation::Median;
+        assert_eq!(
+            aggregation.aggregate(&[(0, 300), (1, 100), (2, 200)]),
+            Ok((200, vec![2]))
+        );
+        assert_eq!(
+            aggregation.aggregate(&[(0, 300), (2, 100)]),
+            Ok((200, vec![2, 0]))
+        );
+    }
+
+    #[test]
+    fn bounded_mean_fails_if_sources_disagree() {
+        let aggregation = PriceAggregation::BoundedMean {
+            max_spread_e6: 100_000, // 10%
+        };
+        assert_eq!(
+            aggregation.aggregate(&[(0, 100), (1, 110)]),
+            Ok((105, vec![0, 1]))
+        );
+        assert_eq!(
+            aggregation.aggregate(&[(0, 100), (1, 111)]),
+            Err(PriceFeedError::PriceSourcesDisagree)
+        );
+    }
+}
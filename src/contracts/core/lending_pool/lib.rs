@@ -14,10 +14,13 @@ pub mod lending_pool {
     use abax_contracts::account_registrar::AccountRegistrarView;
     use abax_contracts::lending_pool::SetReserveFeesArgs;
     use abax_contracts::lending_pool::{
-        events::FlashLoanFeeChanged, DecimalMultiplier,
+        events::FlashLoanFeeChanged, ActionsSimulation, DecimalMultiplier,
         LendingPoolATokenInterface, LendingPoolActions, LendingPoolError,
         LendingPoolFlash, LendingPoolMaintain, LendingPoolManage,
-        LendingPoolVTokenInterface, LendingPoolView, MarketRule, RuleId,
+        LendingPoolSTokenInterface, LendingPoolTimelock,
+        LendingPoolVTokenInterface, LendingPoolView, LiquidationCandidate,
+        ManageAction, MarketRule, QueuedManageAction, ReserveOperationalState,
+        RuleId,
     };
     use abax_contracts::{
         account_registrar::implementation::AccountRegistrar,
@@ -25,16 +28,20 @@ pub mod lending_pool {
             LendingPoolATokenInterfaceImpl, LendingPoolBorrowImpl,
             LendingPoolDepositImpl, LendingPoolFlashImpl,
             LendingPoolLiquidateImpl, LendingPoolMaintainImpl,
-            LendingPoolMultiOpImpl, LendingPoolStorage,
+            LendingPoolMultiOpImpl, LendingPoolSTokenInterfaceImpl,
+            LendingPoolStorage, LendingPoolTimelockImpl,
             LendingPoolVTokenInterfaceImpl, LendingPoolViewImpl,
             {LendingPoolManageImpl, ManageInternal},
         },
     };
     use abax_library::structs::{
-        AccountConfig, AccountReserveData, Action, AssetRules,
-        InterestRateModel, InterestRateModelParams, ReserveAbacusTokens,
-        ReserveData, ReserveFees, ReserveIndexes, ReserveRestrictions, TwEntry,
-        TwIndex,
+        AccountConfig, AccountHealth, AccountReserveData, Action, ActionsPause,
+        AssetRules, CloseFactor, DelistingStage, InterestAccrualMode,
+        InterestRateModel, InterestRateModelParams, LiquidationAuction,
+        LiquidationAuctionParams, PausableAction, ReserveAbacusTokens,
+        ReserveAccountLimits, ReserveData, ReserveDelisting, ReserveFees,
+        ReserveIndexes, ReserveIsolation, ReserveRestrictions, StableRateModel,
+        TwEntry, TwIndex,
     };
     use ink::{env::DefaultEnvironment, prelude::vec::Vec};
 
@@ -144,6 +151,39 @@ pub mod lending_pool {
             )
         }
 
+        #[ink(message)]
+        fn borrow_stable(
+            &mut self,
+            asset: AccountId,
+            on_behalf_of: AccountId,
+            amount: Balance,
+            data: Vec<u8>,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolBorrowImpl::borrow_stable(
+                self,
+                asset,
+                on_behalf_of,
+                amount,
+                data,
+            )
+        }
+        #[ink(message)]
+        fn repay_stable(
+            &mut self,
+            asset: AccountId,
+            on_behalf_of: AccountId,
+            amount: Balance,
+            data: Vec<u8>,
+        ) -> Result<Balance, LendingPoolError> {
+            LendingPoolBorrowImpl::repay_stable(
+                self,
+                asset,
+                on_behalf_of,
+                amount,
+                data,
+            )
+        }
+
         #[ink(message)]
         fn multi_op(
             &mut self,
@@ -176,6 +216,22 @@ pub mod lending_pool {
 
             Ok(res)
         }
+
+        #[ink(message)]
+        fn start_liquidation_auction(
+            &mut self,
+            account: AccountId,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolLiquidateImpl::start_liquidation_auction(self, account)
+        }
+
+        #[ink(message)]
+        fn close_liquidation_auction(
+            &mut self,
+            account: AccountId,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolLiquidateImpl::close_liquidation_auction(self, account)
+        }
     }
 
     impl LendingPoolFlashImpl for LendingPool {}
@@ -196,6 +252,29 @@ pub mod lending_pool {
                 receiver_params,
             )
         }
+
+        #[ink(message)]
+        fn flash_liquidate(
+            &mut self,
+            liquidated_account: AccountId,
+            asset_to_repay: AccountId,
+            asset_to_take: AccountId,
+            amount_to_repay: Balance,
+            minimum_recieved_for_one_repaid_token_e18: u128,
+            receiver: AccountId,
+            receiver_params: Vec<u8>,
+        ) -> Result<(Balance, Balance), LendingPoolError> {
+            LendingPoolFlashImpl::flash_liquidate(
+                self,
+                liquidated_account,
+                asset_to_repay,
+                asset_to_take,
+                amount_to_repay,
+                minimum_recieved_for_one_repaid_token_e18,
+                receiver,
+                receiver_params,
+            )
+        }
     }
 
     impl LendingPoolMaintainImpl for LendingPool {}
@@ -212,7 +291,7 @@ pub mod lending_pool {
         fn adjust_rate_at_target(
             &mut self,
             asset: AccountId,
-            guessed_index: u32,
+            guessed_index: Option<u32>,
         ) -> Result<u64, LendingPoolError> {
             LendingPoolMaintainImpl::adjust_rate_at_target(
                 self,
@@ -220,6 +299,67 @@ pub mod lending_pool {
                 guessed_index,
             )
         }
+
+        #[ink(message)]
+        fn settle_bad_debt(
+            &mut self,
+            account: AccountId,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolMaintainImpl::settle_bad_debt(self, account)
+        }
+
+        #[ink(message)]
+        fn settle_delisted_deposit(
+            &mut self,
+            asset: AccountId,
+            account: AccountId,
+        ) -> Result<Balance, LendingPoolError> {
+            LendingPoolMaintainImpl::settle_delisted_deposit(
+                self, asset, account,
+            )
+        }
+
+        #[ink(message)]
+        fn rebalance_stable_rate(
+            &mut self,
+            asset: AccountId,
+            account: AccountId,
+        ) -> Result<u64, LendingPoolError> {
+            LendingPoolMaintainImpl::rebalance_stable_rate(self, asset, account)
+        }
+
+        #[ink(message)]
+        fn increase_safety_fund(
+            &mut self,
+            asset: AccountId,
+            amount: Balance,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolMaintainImpl::increase_safety_fund(self, asset, amount)
+        }
+
+        #[ink(message)]
+        fn migrate_reserves(&mut self) -> Result<u32, LendingPoolError> {
+            LendingPoolMaintainImpl::migrate_reserves(self)
+        }
+
+        #[ink(message)]
+        fn migrate_accounts(
+            &mut self,
+            from_counter: u128,
+            to_counter: u128,
+        ) -> Result<u32, LendingPoolError> {
+            let accounts = self
+                .account_registrar
+                .accounts_in_range(from_counter, to_counter);
+            let next_counter = self.account_registrar.next_counter;
+            LendingPoolMaintainImpl::migrate_accounts(
+                self,
+                accounts,
+                from_counter,
+                to_counter,
+                next_counter,
+            )
+        }
     }
     impl ManageInternal for LendingPool {}
     impl LendingPoolManageImpl for LendingPool {}
@@ -286,6 +426,25 @@ pub mod lending_pool {
             )
         }
 
+        #[ink(message)]
+        fn register_stable_debt_token(
+            &mut self,
+            asset: AccountId,
+            s_token_code_hash: [u8; 32],
+            name: String,
+            symbol: String,
+            decimals: u8,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::register_stable_debt_token(
+                self,
+                asset,
+                s_token_code_hash,
+                name,
+                symbol,
+                decimals,
+            )
+        }
+
         #[ink(message)]
         fn set_reserve_is_active(
             &mut self,
@@ -295,13 +454,63 @@ pub mod lending_pool {
             LendingPoolManageImpl::set_reserve_is_active(self, asset, active)
         }
 
+        #[ink(message)]
+        fn start_reserve_delisting(
+            &mut self,
+            asset: AccountId,
+            ramp_duration: Timestamp,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::start_reserve_delisting(
+                self,
+                asset,
+                ramp_duration,
+            )
+        }
+
+        #[ink(message)]
+        fn close_reserve(
+            &mut self,
+            asset: AccountId,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::close_reserve(self, asset)
+        }
+
         #[ink(message)]
         fn set_reserve_is_frozen(
             &mut self,
             asset: AccountId,
             freeze: bool,
+            reason_code: u32,
         ) -> Result<(), LendingPoolError> {
-            LendingPoolManageImpl::set_reserve_is_frozen(self, asset, freeze)
+            LendingPoolManageImpl::set_reserve_is_frozen(
+                self,
+                asset,
+                freeze,
+                reason_code,
+            )
+        }
+
+        #[ink(message)]
+        fn set_global_action_paused(
+            &mut self,
+            action: PausableAction,
+            paused: bool,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_global_action_paused(
+                self, action, paused,
+            )
+        }
+
+        #[ink(message)]
+        fn set_reserve_action_paused(
+            &mut self,
+            asset: AccountId,
+            action: PausableAction,
+            paused: bool,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_reserve_action_paused(
+                self, asset, action, paused,
+            )
         }
 
         #[ink(message)]
@@ -339,6 +548,28 @@ pub mod lending_pool {
             LendingPoolManageImpl::set_reserve_fees(self, asset, reserve_fees)
         }
 
+        #[ink(message)]
+        fn set_reserve_accrual_mode(
+            &mut self,
+            asset: AccountId,
+            accrual_mode: InterestAccrualMode,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_reserve_accrual_mode(
+                self,
+                asset,
+                accrual_mode,
+            )
+        }
+
+        #[ink(message)]
+        fn set_reserve_tw_size(
+            &mut self,
+            asset: AccountId,
+            size: u32,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_reserve_tw_size(self, asset, size)
+        }
+
         #[ink(message)]
         fn add_market_rule(
             &mut self,
@@ -362,6 +593,19 @@ pub mod lending_pool {
             )
         }
 
+        #[ink(message)]
+        fn set_market_rule_liquidation_auction(
+            &mut self,
+            market_rule_id: RuleId,
+            auction_params: Option<LiquidationAuctionParams>,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_market_rule_liquidation_auction(
+                self,
+                market_rule_id,
+                auction_params,
+            )
+        }
+
         #[ink(message)]
         fn take_protocol_income(
             &mut self,
@@ -371,6 +615,58 @@ pub mod lending_pool {
             LendingPoolManageImpl::take_protocol_income(self, assets, to)
         }
 
+        #[ink(message)]
+        fn set_reserve_isolation(
+            &mut self,
+            asset: AccountId,
+            reserve_isolation: ReserveIsolation,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_reserve_isolation(
+                self,
+                asset,
+                reserve_isolation,
+            )
+        }
+
+        #[ink(message)]
+        fn set_reserve_account_limits(
+            &mut self,
+            asset: AccountId,
+            reserve_account_limits: ReserveAccountLimits,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_reserve_account_limits(
+                self,
+                asset,
+                reserve_account_limits,
+            )
+        }
+
+        #[ink(message)]
+        fn set_reserve_close_factor(
+            &mut self,
+            asset: AccountId,
+            close_factor: Option<CloseFactor>,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_reserve_close_factor(
+                self,
+                asset,
+                close_factor,
+            )
+        }
+
+        #[ink(message)]
+        fn set_stable_rate_model(
+            &mut self,
+            asset: AccountId,
+            stable_rate_model: Option<StableRateModel>,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolManageImpl::set_stable_rate_model(
+                self,
+                asset,
+                stable_rate_model,
+            )
+        }
+
         #[ink(message)]
         fn set_stablecoin_debt_rate_e18(
             &mut self,
@@ -384,6 +680,40 @@ pub mod lending_pool {
             )
         }
     }
+    impl LendingPoolTimelockImpl for LendingPool {}
+    impl LendingPoolTimelock for LendingPool {
+        #[ink(message)]
+        fn set_timelock_delay(
+            &mut self,
+            timelock_delay: Timestamp,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolTimelockImpl::set_timelock_delay(self, timelock_delay)
+        }
+
+        #[ink(message)]
+        fn queue_manage_action(
+            &mut self,
+            action: ManageAction,
+        ) -> Result<u32, LendingPoolError> {
+            LendingPoolTimelockImpl::queue_manage_action(self, action)
+        }
+
+        #[ink(message)]
+        fn execute_manage_action(
+            &mut self,
+            action_id: u32,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolTimelockImpl::execute_manage_action(self, action_id)
+        }
+
+        #[ink(message)]
+        fn cancel_manage_action(
+            &mut self,
+            action_id: u32,
+        ) -> Result<(), LendingPoolError> {
+            LendingPoolTimelockImpl::cancel_manage_action(self, action_id)
+        }
+    }
     impl LendingPoolViewImpl for LendingPool {}
     impl LendingPoolView for LendingPool {
         #[ink(message)]
@@ -394,6 +724,17 @@ pub mod lending_pool {
         fn view_asset_id(&self, account: AccountId) -> Option<RuleId> {
             LendingPoolViewImpl::view_asset_id(self, account)
         }
+        #[ink(message)]
+        fn view_timelock_delay(&self) -> Timestamp {
+            LendingPoolViewImpl::view_timelock_delay(self)
+        }
+        #[ink(message)]
+        fn view_queued_manage_action(
+            &self,
+            action_id: u32,
+        ) -> Option<QueuedManageAction> {
+            LendingPoolViewImpl::view_queued_manage_action(self, action_id)
+        }
 
         #[ink(message)]
         fn view_registered_assets(&self) -> Vec<AccountId> {
@@ -425,6 +766,70 @@ pub mod lending_pool {
             LendingPoolViewImpl::view_reserve_restrictions(self, asset)
         }
         #[ink(message)]
+        fn view_reserve_isolation(
+            &self,
+            asset: AccountId,
+        ) -> Option<ReserveIsolation> {
+            LendingPoolViewImpl::view_reserve_isolation(self, asset)
+        }
+        #[ink(message)]
+        fn view_reserve_account_limits(
+            &self,
+            asset: AccountId,
+        ) -> Option<ReserveAccountLimits> {
+            LendingPoolViewImpl::view_reserve_account_limits(self, asset)
+        }
+        #[ink(message)]
+        fn view_reserve_operational_state(
+            &self,
+            asset: AccountId,
+        ) -> Option<ReserveOperationalState> {
+            LendingPoolViewImpl::view_reserve_operational_state(self, asset)
+        }
+        #[ink(message)]
+        fn view_global_actions_pause(&self) -> ActionsPause {
+            LendingPoolViewImpl::view_global_actions_pause(self)
+        }
+        #[ink(message)]
+        fn view_reserve_actions_pause(
+            &self,
+            asset: AccountId,
+        ) -> Option<ActionsPause> {
+            LendingPoolViewImpl::view_reserve_actions_pause(self, asset)
+        }
+        #[ink(message)]
+        fn view_isolated_debt_e8(&self, asset: AccountId) -> Option<u128> {
+            LendingPoolViewImpl::view_isolated_debt_e8(self, asset)
+        }
+        #[ink(message)]
+        fn view_reserve_delisting(
+            &self,
+            asset: AccountId,
+        ) -> Option<ReserveDelisting> {
+            LendingPoolViewImpl::view_reserve_delisting(self, asset)
+        }
+        #[ink(message)]
+        fn view_reserve_delisting_stage(
+            &self,
+            asset: AccountId,
+        ) -> Option<DelistingStage> {
+            LendingPoolViewImpl::view_reserve_delisting_stage(self, asset)
+        }
+        #[ink(message)]
+        fn view_reserve_close_factor(
+            &self,
+            asset: AccountId,
+        ) -> Option<CloseFactor> {
+            LendingPoolViewImpl::view_reserve_close_factor(self, asset)
+        }
+        #[ink(message)]
+        fn view_stable_rate_model(
+            &self,
+            asset: AccountId,
+        ) -> Option<StableRateModel> {
+            LendingPoolViewImpl::view_stable_rate_model(self, asset)
+        }
+        #[ink(message)]
         fn view_reserve_tokens(
             &self,
             asset: AccountId,
@@ -451,6 +856,14 @@ pub mod lending_pool {
             LendingPoolViewImpl::view_reserve_fees(self, asset)
         }
 
+        #[ink(message)]
+        fn view_reserve_accrual_mode(
+            &self,
+            asset: AccountId,
+        ) -> Option<InterestAccrualMode> {
+            LendingPoolViewImpl::view_reserve_accrual_mode(self, asset)
+        }
+
         #[ink(message)]
         fn view_unupdated_account_reserve_data(
             &self,
@@ -481,6 +894,33 @@ pub mod lending_pool {
             LendingPoolViewImpl::view_market_rule(self, market_rule_id)
         }
         #[ink(message)]
+        fn view_market_rule_liquidation_auction(
+            &self,
+            market_rule_id: RuleId,
+        ) -> Option<LiquidationAuctionParams> {
+            LendingPoolViewImpl::view_market_rule_liquidation_auction(
+                self,
+                market_rule_id,
+            )
+        }
+        #[ink(message)]
+        fn view_liquidation_auction(
+            &self,
+            account: AccountId,
+        ) -> Option<LiquidationAuction> {
+            LendingPoolViewImpl::view_liquidation_auction(self, account)
+        }
+        #[ink(message)]
+        fn view_active_liquidation_auctions(
+            &self,
+            from: u32,
+            count: u32,
+        ) -> Vec<(AccountId, LiquidationAuction)> {
+            LendingPoolViewImpl::view_active_liquidation_auctions(
+                self, from, count,
+            )
+        }
+        #[ink(message)]
         fn get_account_free_collateral_coefficient(
             &self,
             account_address: AccountId,
@@ -490,6 +930,37 @@ pub mod lending_pool {
                 account_address,
             )
         }
+        #[ink(message)]
+        fn view_account_health(
+            &self,
+            account: AccountId,
+        ) -> Result<AccountHealth, LendingPoolError> {
+            LendingPoolViewImpl::view_account_health(self, account)
+        }
+        #[ink(message)]
+        fn view_max_borrowable(
+            &self,
+            account: AccountId,
+            asset: AccountId,
+        ) -> Result<Balance, LendingPoolError> {
+            LendingPoolViewImpl::view_max_borrowable(self, account, asset)
+        }
+        #[ink(message)]
+        fn view_max_withdrawable(
+            &self,
+            account: AccountId,
+            asset: AccountId,
+        ) -> Result<Balance, LendingPoolError> {
+            LendingPoolViewImpl::view_max_withdrawable(self, account, asset)
+        }
+        #[ink(message)]
+        fn simulate_actions(
+            &mut self,
+            account: AccountId,
+            actions: Vec<Action>,
+        ) -> Result<ActionsSimulation, LendingPoolError> {
+            LendingPoolViewImpl::simulate_actions(self, account, actions)
+        }
 
         #[ink(message)]
         fn view_protocol_income(
@@ -499,6 +970,40 @@ pub mod lending_pool {
             LendingPoolViewImpl::view_protocol_income(self, assets)
         }
 
+        #[ink(message)]
+        fn view_reserve_safety_fund(
+            &self,
+            asset: AccountId,
+        ) -> Option<Balance> {
+            LendingPoolViewImpl::view_reserve_safety_fund(self, asset)
+        }
+
+        #[ink(message)]
+        fn view_accounts_with_unbacked_debt(
+            &self,
+            from_counter: u128,
+            to_counter: u128,
+        ) -> Vec<AccountId> {
+            let accounts = self
+                .account_registrar
+                .accounts_in_range(from_counter, to_counter);
+            LendingPoolViewImpl::view_accounts_with_unbacked_debt(
+                self, accounts,
+            )
+        }
+
+        #[ink(message)]
+        fn view_liquidation_candidates(
+            &self,
+            from_counter: u128,
+            to_counter: u128,
+        ) -> Result<Vec<LiquidationCandidate>, LendingPoolError> {
+            let accounts = self
+                .account_registrar
+                .accounts_in_range(from_counter, to_counter);
+            LendingPoolViewImpl::view_liquidation_candidates(self, accounts)
+        }
+
         #[ink(message)]
         fn view_asset_tw_index(&self, asset: AccountId) -> Option<TwIndex> {
             LendingPoolViewImpl::view_asset_tw_index(self, asset)
@@ -518,7 +1023,7 @@ pub mod lending_pool {
             &self,
             period: u64,
             asset: AccountId,
-            guessed_index: u32,
+            guessed_index: Option<u32>,
         ) -> Result<u32, LendingPoolError> {
             LendingPoolViewImpl::view_tw_ur_from_period_longer_than(
                 self,
@@ -622,6 +1127,29 @@ pub mod lending_pool {
         }
     }
 
+    impl LendingPoolSTokenInterfaceImpl for LendingPool {}
+    impl LendingPoolSTokenInterface for LendingPool {
+        #[ink(message)]
+        fn total_stable_debt_of(&self, underlying_asset: AccountId) -> Balance {
+            LendingPoolSTokenInterfaceImpl::total_stable_debt_of(
+                self,
+                underlying_asset,
+            )
+        }
+        #[ink(message)]
+        fn account_stable_debt_of(
+            &self,
+            underlying_asset: AccountId,
+            account: AccountId,
+        ) -> Balance {
+            LendingPoolSTokenInterfaceImpl::account_stable_debt_of(
+                self,
+                underlying_asset,
+                account,
+            )
+        }
+    }
+
     impl LendingPool {
         #[ink(constructor)]
         pub fn new(admin: AccountId) -> Self {
@@ -629,6 +1157,7 @@ pub mod lending_pool {
             instance.lending_pool.next_asset_id.set(&0);
             instance.lending_pool.next_rule_id.set(&0);
             instance.lending_pool.flash_loan_fee_e6.set(&1000);
+            instance.lending_pool.accounts_migrated.set(&true);
             ink::env::emit_event::<DefaultEnvironment, FlashLoanFeeChanged>(
                 FlashLoanFeeChanged {
                     flash_loan_fee_e6: 1000,
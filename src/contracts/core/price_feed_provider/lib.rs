@@ -5,14 +5,20 @@
 #[ink::contract]
 pub mod price_feed_provider {
     use abax_contracts::dia_oracle::{OracleGetters, OracleGettersRef};
-    use abax_contracts::price_feed::{PriceFeed, PriceFeedError};
+    use abax_contracts::price_feed::{
+        AssetPriceSources, AssetPriceType, PriceAnchor, PriceDeviationLimit,
+        PriceFeed, PriceFeedError, PriceFeedRef, PriceSource,
+    };
+    use abax_library::math::E18_U128;
     use ink::codegen::TraitCallBuilder;
     use ink::prelude::string::String;
     use ink::prelude::{vec::Vec, *};
+    use ink::ToAccountId;
 
     use ink::storage::{Lazy, Mapping};
 
     use pendzl::contracts::access_control;
+    use pendzl::math::operations::{mul_div, Rounding};
     const PARAMETERS_ADMIN: RoleType = ink::selector_id!("PARAMETERS_ADMIN"); // 368_001_360_u32
 
     #[ink(storage)]
@@ -22,6 +28,16 @@ pub mod price_feed_provider {
         access: access_control::AccessControlData,
         oracle: Lazy<OracleGettersRef>,
         account_to_symbol: Mapping<AccountId, String>,
+        /// maximal age of the price (in milliseconds) for given asset. Not limited if not set.
+        max_price_age: Mapping<AccountId, u64>,
+        /// maximal change of the price relative to the anchor price for given asset. Not limited if not set.
+        price_deviation_limits: Mapping<AccountId, PriceDeviationLimit>,
+        /// the anchor price of given asset. Moved by `update_price_anchors` at most once per `anchor_cooldown`.
+        price_anchors: Mapping<AccountId, PriceAnchor>,
+        /// price sources of given asset. If not set the `oracle` is the only source.
+        price_sources: Mapping<AccountId, AssetPriceSources>,
+        /// price types of assets that have no (single) price pair. If set, takes precedence over `price_sources` and `account_to_symbol`.
+        price_types: Mapping<AccountId, AssetPriceType>,
     }
 
     impl PriceFeedProvider {
@@ -56,36 +72,455 @@ pub mod price_feed_provider {
         ) -> Option<String> {
             self.account_to_symbol.get(asset)
         }
-    }
 
-    impl PriceFeed for PriceFeedProvider {
+        /// Sets the maximal age of the price (in milliseconds) and the limit of the change of the price relative to the anchor price for the `asset`.
+        /// None means the corresponding check is disabled.
         #[ink(message)]
-        fn get_latest_prices(
+        pub fn set_price_limits(
+            &mut self,
+            asset: AccountId,
+            max_price_age: Option<u64>,
+            price_deviation_limit: Option<PriceDeviationLimit>,
+        ) -> Result<(), AccessControlError> {
+            let caller = Self::env().caller();
+            self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
+
+            match max_price_age {
+                Some(age) => self.max_price_age.insert(asset, &age),
+                None => {
+                    self.max_price_age.remove(asset);
+                    None
+                }
+            };
+            match price_deviation_limit {
+                Some(limit) => {
+                    self.price_deviation_limits.insert(asset, &limit)
+                }
+                None => {
+                    self.price_deviation_limits.remove(asset);
+                    self.price_anchors.remove(asset);
+                    None
+                }
+            };
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_price_limits(
             &self,
+            asset: AccountId,
+        ) -> (Option<u64>, Option<PriceDeviationLimit>) {
+            (
+                self.max_price_age.get(asset),
+                self.price_deviation_limits.get(asset),
+            )
+        }
+
+        /// Moves the anchor prices of `assets` to their current prices. Can be called by anyone.
+        /// The anchor of an asset is moved only if it is not set or `anchor_cooldown` has passed since it was set.
+        /// Assets without the deviation limit and the assets which price can't be read are skipped, so they don't block the others.
+        ///
+        /// # Returns
+        /// For each of `assets` - true if its anchor was moved.
+        #[ink(message)]
+        pub fn update_price_anchors(
+            &mut self,
             assets: Vec<AccountId>,
-        ) -> Result<Vec<u128>, PriceFeedError> {
-            let mut result: Vec<u128> = vec![];
+        ) -> Vec<bool> {
+            let timestamp = Self::env().block_timestamp();
+            assets
+                .iter()
+                .map(|asset| {
+                    let limit = match self.price_deviation_limits.get(asset) {
+                        Some(limit) => limit,
+                        None => return false,
+                    };
+                    if self.price_anchors.get(asset).is_some_and(|anchor| {
+                        !limit.can_reanchor(&anchor, timestamp)
+                    }) {
+                        return false;
+                    }
+                    match self._get_prices_with_sources(&[*asset]) {
+                        Ok(mut prices) => {
+                            let (price, _) = prices.pop().unwrap(); // one asset => one price
+                            self.price_anchors.insert(
+                                asset,
+                                &PriceAnchor { price, timestamp },
+                            );
+                            true
+                        }
+                        Err(_) => false,
+                    }
+                })
+                .collect()
+        }
+
+        /// Forgets the anchor price of the `asset`, so the next `update_price_anchors` sets it regardless of the cooldown.
+        /// Until then the price of the `asset` is not checked for the deviation.
+        #[ink(message)]
+        pub fn reset_price_anchor(
+            &mut self,
+            asset: AccountId,
+        ) -> Result<(), AccessControlError> {
+            let caller = Self::env().caller();
+            self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
+
+            self.price_anchors.remove(asset);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_price_anchor(
+            &self,
+            asset: AccountId,
+        ) -> Option<PriceAnchor> {
+            self.price_anchors.get(asset)
+        }
+
+        /// Sets the price sources of the `asset` and the way they are aggregated.
+        /// None means the `oracle` is the only source of the `asset`'s price.
+        #[ink(message)]
+        pub fn set_price_sources(
+            &mut self,
+            asset: AccountId,
+            price_sources: Option<AssetPriceSources>,
+        ) -> Result<(), AccessControlError> {
+            let caller = Self::env().caller();
+            self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
+
+            match price_sources {
+                Some(sources) if !sources.sources.is_empty() => {
+                    self.price_sources.insert(asset, &sources)
+                }
+                _ => {
+                    self.price_sources.remove(asset);
+                    None
+                }
+            };
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_price_sources(
+            &self,
+            asset: AccountId,
+        ) -> Option<AssetPriceSources> {
+            self.price_sources.get(asset)
+        }
+
+        /// Sets the price type of the `asset` - fixed, pegged to another asset or derived from two symbols.
+        /// None means the price is read from the `asset`'s price sources or its symbol.
+        #[ink(message)]
+        pub fn set_asset_price_type(
+            &mut self,
+            asset: AccountId,
+            price_type: Option<AssetPriceType>,
+        ) -> Result<(), AccessControlError> {
+            let caller = Self::env().caller();
+            self._ensure_has_role(PARAMETERS_ADMIN, Some(caller))?;
+
+            match price_type {
+                Some(price_type) => self.price_types.insert(asset, &price_type),
+                None => {
+                    self.price_types.remove(asset);
+                    None
+                }
+            };
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_asset_price_type(
+            &self,
+            asset: AccountId,
+        ) -> Option<AssetPriceType> {
+            self.price_types.get(asset)
+        }
+
+        /// Returns the current price of the `asset` together with the sources that were used to compute it.
+        #[ink(message)]
+        pub fn get_latest_price_with_sources(
+            &self,
+            asset: AccountId,
+        ) -> Result<(u128, Vec<PriceSource>), PriceFeedError> {
+            Ok(self._get_prices_with_sources(&[asset])?.pop().unwrap()) // one asset => one price
+        }
+
+        /// Returns prices of `assets` with the sources that were used.
+        /// The assets without configured sources are read from the `oracle` in one call.
+        fn _get_prices_with_sources(
+            &self,
+            assets: &[AccountId],
+        ) -> Result<Vec<(u128, Vec<PriceSource>)>, PriceFeedError> {
+            let mut result: Vec<Option<(u128, Vec<PriceSource>)>> =
+                vec![None; assets.len()];
+            let mut default_indexes: Vec<usize> = vec![];
             let mut symbols: Vec<String> = vec![];
 
-            for asset in assets {
-                let symbol = self
-                    .account_to_symbol
-                    .get(asset)
-                    .ok_or(PriceFeedError::NoSuchAsset)?;
-                symbols.push(symbol)
+            for (i, asset) in assets.iter().enumerate() {
+                if let Some(price_type) = self.price_types.get(asset) {
+                    result[i] =
+                        Some(self._get_typed_price(asset, &price_type)?);
+                    continue;
+                }
+                match self.price_sources.get(asset) {
+                    Some(asset_price_sources) => {
+                        result[i] = Some(self._get_aggregated_price(
+                            asset,
+                            &asset_price_sources,
+                        )?)
+                    }
+                    None => {
+                        let symbol = self
+                            .account_to_symbol
+                            .get(asset)
+                            .ok_or(PriceFeedError::NoSuchAsset)?;
+                        default_indexes.push(i);
+                        symbols.push(symbol)
+                    }
+                }
             }
+
+            if !default_indexes.is_empty() {
+                let mut oracle: OracleGettersRef = self.oracle.get().unwrap();
+                let oracle_source =
+                    PriceSource::DiaOracle(oracle.to_account_id());
+                for (i, res) in default_indexes.into_iter().zip(
+                    oracle
+                        .call_mut()
+                        .get_latest_prices(symbols)
+                        .call_v1()
+                        .invoke(),
+                ) {
+                    match res {
+                        Some((timestamp, price)) => {
+                            self._ensure_price_fresh(&assets[i], timestamp)?;
+                            result[i] = Some((price, vec![oracle_source]))
+                        }
+                        None => return Err(PriceFeedError::NoPriceFeed),
+                    }
+                }
+            }
+
+            Ok(result.into_iter().map(|r| r.unwrap()).collect()) // each asset was either aggregated or read from the oracle
+        }
+
+        /// Computes the price of the `asset` according to its `price_type`.
+        fn _get_typed_price(
+            &self,
+            asset: &AccountId,
+            price_type: &AssetPriceType,
+        ) -> Result<(u128, Vec<PriceSource>), PriceFeedError> {
+            match price_type {
+                AssetPriceType::Fixed { price_e18 } => Ok((*price_e18, vec![])),
+                AssetPriceType::Pegged {
+                    asset: base_asset,
+                    ratio_e18,
+                } => {
+                    if let Some(AssetPriceType::Pegged { .. }) =
+                        self.price_types.get(base_asset)
+                    {
+                        return Err(PriceFeedError::InvalidPriceType);
+                    }
+                    let (base_price, sources) = self
+                        ._get_prices_with_sources(&[*base_asset])?
+                        .pop()
+                        .unwrap(); // one asset => one price
+                    Ok((
+                        mul_div(
+                            base_price,
+                            *ratio_e18,
+                            E18_U128,
+                            Rounding::Down,
+                        )?,
+                        sources,
+                    ))
+                }
+                AssetPriceType::Product { first, second } => {
+                    let (first_price, second_price, source) = self
+                        ._get_symbols_prices(
+                            asset,
+                            first.clone(),
+                            second.clone(),
+                        )?;
+                    Ok((
+                        mul_div(
+                            first_price,
+                            second_price,
+                            E18_U128,
+                            Rounding::Down,
+                        )?,
+                        vec![source],
+                    ))
+                }
+                AssetPriceType::Quotient {
+                    numerator,
+                    denominator,
+                } => {
+                    let (numerator_price, denominator_price, source) = self
+                        ._get_symbols_prices(
+                            asset,
+                            numerator.clone(),
+                            denominator.clone(),
+                        )?;
+                    Ok((
+                        mul_div(
+                            numerator_price,
+                            E18_U128,
+                            denominator_price,
+                            Rounding::Down,
+                        )?,
+                        vec![source],
+                    ))
+                }
+            }
+        }
+
+        /// Reads prices of two symbols from the `oracle` ensuring they are fresh enough for the `asset`.
+        fn _get_symbols_prices(
+            &self,
+            asset: &AccountId,
+            first: String,
+            second: String,
+        ) -> Result<(u128, u128, PriceSource), PriceFeedError> {
             let mut oracle: OracleGettersRef = self.oracle.get().unwrap();
+            let source = PriceSource::DiaOracle(oracle.to_account_id());
+            let mut prices: Vec<u128> = vec![];
             for res in oracle
                 .call_mut()
-                .get_latest_prices(symbols)
+                .get_latest_prices(vec![first, second])
                 .call_v1()
                 .invoke()
             {
                 match res {
-                    Some(r) => result.push(r.1),
+                    Some((timestamp, price)) => {
+                        self._ensure_price_fresh(asset, timestamp)?;
+                        prices.push(price)
+                    }
                     None => return Err(PriceFeedError::NoPriceFeed),
                 }
             }
+            match prices[..] {
+                [first_price, second_price] => {
+                    Ok((first_price, second_price, source))
+                }
+                _ => Err(PriceFeedError::NoPriceFeed),
+            }
+        }
+
+        /// Reads the price of the `asset` from each of its sources and aggregates them.
+        /// Sources that fail or return a stale price are skipped.
+        fn _get_aggregated_price(
+            &self,
+            asset: &AccountId,
+            asset_price_sources: &AssetPriceSources,
+        ) -> Result<(u128, Vec<PriceSource>), PriceFeedError> {
+            let mut quotes: Vec<(usize, u128)> = vec![];
+            let mut any_stale = false;
+
+            for (i, source) in asset_price_sources.sources.iter().enumerate() {
+                let quote: Option<(u64, u128)> = match source {
+                    PriceSource::DiaOracle(address) => {
+                        let symbol = self
+                            .account_to_symbol
+                            .get(asset)
+                            .ok_or(PriceFeedError::NoSuchAsset)?;
+                        let oracle: OracleGettersRef = (*address).into();
+                        oracle
+                            .call()
+                            .get_latest_price(symbol)
+                            .call_v1()
+                            .try_invoke()
+                            .ok()
+                            .and_then(|res| res.ok())
+                            .flatten()
+                    }
+                    PriceSource::PriceFeed(address) => {
+                        let price_feed: PriceFeedRef = (*address).into();
+                        price_feed
+                            .call()
+                            .get_latest_prices(vec![*asset])
+                            .call_v1()
+                            .try_invoke()
+                            .ok()
+                            .and_then(|res| res.ok())
+                            .and_then(|res| res.ok())
+                            .and_then(|prices| prices.first().copied())
+                            // the freshness is ensured by the price feed itself
+                            .map(|price| (Self::env().block_timestamp(), price))
+                    }
+                };
+                if let Some((timestamp, price)) = quote {
+                    if self._ensure_price_fresh(asset, timestamp).is_ok() {
+                        quotes.push((i, price));
+                    } else {
+                        any_stale = true;
+                    }
+                }
+            }
+
+            if quotes.is_empty() && any_stale {
+                return Err(PriceFeedError::StalePrice);
+            }
+
+            let (price, used_indexes) =
+                asset_price_sources.aggregation.aggregate(&quotes)?;
+            Ok((
+                price,
+                used_indexes
+                    .into_iter()
+                    .map(|i| asset_price_sources.sources[i])
+                    .collect(),
+            ))
+        }
+
+        fn _ensure_price_fresh(
+            &self,
+            asset: &AccountId,
+            price_timestamp: u64,
+        ) -> Result<(), PriceFeedError> {
+            if let Some(max_price_age) = self.max_price_age.get(asset) {
+                let age = Self::env()
+                    .block_timestamp()
+                    .saturating_sub(price_timestamp);
+                if age > max_price_age {
+                    return Err(PriceFeedError::StalePrice);
+                }
+            }
+            Ok(())
+        }
+
+        fn _ensure_price_not_deviated(
+            &self,
+            asset: &AccountId,
+            price: u128,
+        ) -> Result<(), PriceFeedError> {
+            match (
+                self.price_deviation_limits.get(asset),
+                self.price_anchors.get(asset),
+            ) {
+                (Some(limit), Some(anchor)) => {
+                    limit.ensure_not_deviated(&anchor, price)
+                }
+                _ => Ok(()),
+            }
+        }
+    }
+
+    impl PriceFeed for PriceFeedProvider {
+        #[ink(message)]
+        fn get_latest_prices(
+            &self,
+            assets: Vec<AccountId>,
+        ) -> Result<Vec<u128>, PriceFeedError> {
+            let mut result: Vec<u128> = vec![];
+
+            for (asset, (price, _)) in
+                assets.iter().zip(self._get_prices_with_sources(&assets)?)
+            {
+                self._ensure_price_not_deviated(asset, price)?;
+                result.push(price)
+            }
             Ok(result)
         }
     }
@@ -0,0 +1,207 @@
+// SPDX-License-Identifier: BUSL-1.1
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+/// SToken is the PSP22 wrapper of the stable debt.
+/// The stable debt is not transferable - the token is used to track balances and to delegate the stable borrowing (allowances).
+#[pendzl::implementation(PSP22, PSP22Metadata)]
+#[ink::contract]
+pub mod s_token {
+    use abax_contracts::abacus_token::implementation::{
+        AbacusTokenImpl, AbacusTokenStorage,
+    };
+    use abax_contracts::{
+        abacus_token::{AbacusToken, TransferEventData},
+        lending_pool::{
+            LendingPoolSTokenInterface, LendingPoolSTokenInterfaceRef,
+        },
+    };
+    use ink::codegen::Env;
+    use ink::codegen::TraitCallBuilder;
+    use ink::prelude::string::String;
+
+    use pendzl::contracts::psp22::{self, PSP22Error};
+
+    #[ink(storage)]
+    #[derive(Default, pendzl::traits::StorageFieldGetter)]
+    pub struct SToken {
+        #[storage_field]
+        psp22: psp22::PSP22Data,
+        #[storage_field]
+        abacus_token: AbacusTokenStorage,
+        #[storage_field]
+        metadata: psp22::metadata::PSP22MetadataData,
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _balance_of(&self, owner: &AccountId) -> Balance {
+        let lending_pool: LendingPoolSTokenInterfaceRef =
+            self.abacus_token.lending_pool.into();
+        lending_pool
+            .call()
+            .account_stable_debt_of(self.abacus_token.underlying_asset, *owner)
+            .call_v1()
+            .invoke()
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _allowance(&self, owner: &AccountId, spender: &AccountId) -> Balance {
+        self.abacus_token
+            .allowances
+            .get((*owner, *spender))
+            .unwrap_or(0)
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _decrease_allowance_from_to(
+        &mut self,
+        owner: &AccountId,
+        spender: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), PSP22Error> {
+        let new_allowance = self
+            ._allowance(owner, spender)
+            .checked_sub(*amount)
+            .ok_or(PSP22Error::InsufficientAllowance)?;
+        self.abacus_token
+            .allowances
+            .insert((*owner, *spender), &new_allowance);
+        self.env().emit_event(psp22::Approval {
+            owner: *owner,
+            spender: *spender,
+            value: new_allowance,
+        });
+        Ok(())
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _increase_allowance_from_to(
+        &mut self,
+        owner: &AccountId,
+        spender: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), PSP22Error> {
+        let new_allowance = self
+            ._allowance(owner, spender)
+            .checked_add(*amount)
+            .ok_or(PSP22Error::Custom("Overflow".into()))?;
+        self.abacus_token
+            .allowances
+            .insert((*owner, *spender), &new_allowance);
+        self.env().emit_event(psp22::Approval {
+            owner: *owner,
+            spender: *spender,
+            value: new_allowance,
+        });
+        Ok(())
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _total_supply(&self) -> Balance {
+        let lending_pool: LendingPoolSTokenInterfaceRef =
+            self.abacus_token.lending_pool.into();
+        lending_pool
+            .call()
+            .total_stable_debt_of(self.abacus_token.underlying_asset)
+            .call_v1()
+            .invoke()
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _update(
+        &mut self,
+        from: Option<&AccountId>,
+        to: Option<&AccountId>,
+        amount: &Balance,
+    ) -> Result<(), PSP22Error> {
+        Err(PSP22Error::Custom("StableDebtNotTransferable".into()))
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _approve(
+        &mut self,
+        owner: &AccountId,
+        spender: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), PSP22Error> {
+        self.abacus_token
+            .allowances
+            .insert((owner, spender), amount);
+        self.env().emit_event(psp22::Approval {
+            owner: *owner,
+            spender: *spender,
+            value: *amount,
+        });
+        Ok(())
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _mint_to(
+        &mut self,
+        to: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), PSP22Error> {
+        panic!("Unsupported operation!")
+    }
+
+    #[overrider(PSP22Internal)]
+    fn _burn_from(
+        &mut self,
+        from: &AccountId,
+        amount: &Balance,
+    ) -> Result<(), PSP22Error> {
+        panic!("Unsupported operation!")
+    }
+
+    impl AbacusTokenImpl for SToken {}
+    impl AbacusToken for SToken {
+        #[ink(message)]
+        fn emit_transfer_events(
+            &mut self,
+            transfer_event_data: Vec<TransferEventData>,
+        ) -> Result<(), PSP22Error> {
+            AbacusTokenImpl::emit_transfer_events(self, transfer_event_data)
+        }
+
+        #[ink(message)]
+        fn emit_transfer_event_and_decrease_allowance(
+            &mut self,
+            transfer_event_data: TransferEventData,
+            from: AccountId,
+            to: AccountId,
+            decrease_allowance_by: Balance,
+        ) -> Result<(), PSP22Error> {
+            AbacusTokenImpl::emit_transfer_event_and_decrease_allowance(
+                self,
+                transfer_event_data,
+                from,
+                to,
+                decrease_allowance_by,
+            )
+        }
+
+        #[ink(message)]
+        fn get_lending_pool(&self) -> AccountId {
+            AbacusTokenImpl::get_lending_pool(self)
+        }
+    }
+
+    impl SToken {
+        #[ink(constructor)]
+        pub fn new(
+            name: String,
+            symbol: String,
+            decimal: u8,
+            lending_pool: AccountId,
+            underlying_asset: AccountId,
+        ) -> Self {
+            let mut instance = Self::default();
+            instance.metadata.name.set(&name.into());
+            instance.metadata.symbol.set(&symbol.into());
+            instance.metadata.decimals.set(&decimal);
+
+            instance.abacus_token.lending_pool = lending_pool;
+            instance.abacus_token.underlying_asset = underlying_asset;
+            instance
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::math::{
+    errors::MathError,
+    operations::{mul_div, Rounding},
+};
+
+use super::{e18_mul_e0_to_e18, E18_U128};
+
+/// Maximal number of the Taylor series terms summed by `compound_interest_multiplier_e18`.
+/// For any exponent for which the result fits in u128 the terms vanish (at 10^-18 precision) before this limit.
+pub const MAX_TAYLOR_TERMS: u128 = 192;
+
+/// Returns exp(`rate_e18` * `delta_timestamp`) in E18 notation - the multiplier of an index accumulating interest with continuous compounding.
+///
+/// The Taylor series of exp is summed until its terms vanish (at 10^-18 precision).
+/// Each term is rounded down, so the result is never greater than the exact value and it is never smaller than the simple interest multiplier (1 + rate * dt).
+pub fn compound_interest_multiplier_e18(
+    rate_e18: u64,
+    delta_timestamp: u64,
+) -> Result<u128, MathError> {
+    let exponent_e18 = e18_mul_e0_to_e18(rate_e18, delta_timestamp);
+    let mut multiplier_e18 = E18_U128;
+    let mut term_e18 = E18_U128;
+    let mut n: u128 = 1;
+    while term_e18 != 0 && n <= MAX_TAYLOR_TERMS {
+        // term_n = term_{n-1} * x / n
+        term_e18 = mul_div(
+            term_e18,
+            exponent_e18,
+            E18_U128.checked_mul(n).ok_or(MathError::Overflow)?,
+            Rounding::Down,
+        )?;
+        multiplier_e18 = multiplier_e18
+            .checked_add(term_e18)
+            .ok_or(MathError::Overflow)?;
+        n += 1;
+    }
+    Ok(multiplier_e18)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use primitive_types::U256;
+
+    /// exp(`exponent_e18`) in E18 notation, rounded down, calculated with 10^-36 precision.
+    fn exact_exp_e18(exponent_e18: u128) -> u128 {
+        let e18 = U256::from(E18_U128);
+        let e36 = e18 * e18;
+        let x = U256::from(exponent_e18);
+        let mut sum = e36;
+        let mut term = e36;
+        let mut n = 1u64;
+        while !term.is_zero() {
+            term = term * x / (e18 * U256::from(n));
+            sum += term;
+            n += 1;
+        }
+        (sum / e18).as_u128()
+    }
+
+    /// xorshift64 - deterministic pseudo random numbers for the property tests.
+    fn next(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    #[test]
+    fn zero_exponent() {
+        assert_eq!(compound_interest_multiplier_e18(0, 1_000), Ok(E18_U128));
+        assert_eq!(
+            compound_interest_multiplier_e18(1_000_000, 0),
+            Ok(E18_U128)
+        );
+    }
+
+    #[test]
+    fn one() {
+        // e = 2.718281828459045235..., rounding down of the terms loses 9 * 10^-18
+        assert_eq!(
+            compound_interest_multiplier_e18(1_000_000_000, 1_000_000_000),
+            Ok(2_718_281_828_459_045_226)
+        );
+    }
+
+    #[test]
+    fn error_against_exact_exp_is_bounded() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2_000 {
+            // exponents from 0 up to 40 with various orders of magnitude
+            let rate_e18 = next(&mut state) % 2_000_000_000;
+            let delta_timestamp =
+                next(&mut state) % 10_u64.pow((next(&mut state) % 11) as u32);
+            let exponent_e18 = e18_mul_e0_to_e18(rate_e18, delta_timestamp);
+
+            let multiplier_e18 =
+                compound_interest_multiplier_e18(rate_e18, delta_timestamp)
+                    .unwrap();
+            let exact_e18 = exact_exp_e18(exponent_e18);
+
+            // never greater than the exact value
+            assert!(multiplier_e18 <= exact_e18);
+            // the error is at most 10^-15 relative (plus rounding of the last terms)
+            assert!(
+                exact_e18 - multiplier_e18 <= 200 + exact_e18 / 1_000_000_000_000_000,
+                "rate_e18: {}, delta_timestamp: {}, multiplier_e18: {}, exact_e18: {}",
+                rate_e18,
+                delta_timestamp,
+                multiplier_e18,
+                exact_e18
+            );
+            // never smaller than the simple interest
+            assert!(multiplier_e18 >= E18_U128 + exponent_e18);
+        }
+    }
+
+    #[test]
+    fn is_monotonic_in_time() {
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        for _ in 0..500 {
+            let rate_e18 = next(&mut state) % 2_000_000_000;
+            let delta_timestamp = next(&mut state) % 10_000_000_000;
+            assert!(
+                compound_interest_multiplier_e18(rate_e18, delta_timestamp)
+                    .unwrap()
+                    <= compound_interest_multiplier_e18(
+                        rate_e18,
+                        delta_timestamp + 1
+                    )
+                    .unwrap()
+            );
+        }
+    }
+}
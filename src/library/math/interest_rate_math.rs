@@ -1,13 +1,141 @@
 // SPDX-License-Identifier: BUSL-1.1
-use pendzl::math::{errors::MathError, operations::mul_div};
+use pendzl::math::{
+    errors::MathError,
+    operations::{mul_div, Rounding},
+};
 
-use crate::structs::InterestRateModel;
+use crate::structs::{
+    AdaptiveInterestRateModel, ContinuousInterestRateModel, InterestRateModel,
+    MultiKinkRateParams, TwoSlopeRateParams, RATE_KINKS_UR_E6,
+};
 
 use super::E6_U32;
 
+/// Returns the debt rate (E18 notation) at `utilization_rate_e6` according to the chosen interest rate model.
 pub fn utilization_rate_to_interest_rate_e18(
     utilization_rate_e6: u32,
     interest_rate_model: &InterestRateModel,
+) -> Result<u64, MathError> {
+    match interest_rate_model {
+        InterestRateModel::Adaptive(model) => {
+            adaptive_utilization_rate_to_interest_rate_e18(
+                utilization_rate_e6,
+                model,
+            )
+        }
+        InterestRateModel::TwoSlope(params) => {
+            two_slope_utilization_rate_to_interest_rate_e18(
+                utilization_rate_e6,
+                params,
+            )
+        }
+        InterestRateModel::MultiKink(params) => {
+            multi_kink_utilization_rate_to_interest_rate_e18(
+                utilization_rate_e6,
+                params,
+            )
+        }
+        InterestRateModel::Fixed { rate_e18 } => Ok(*rate_e18),
+        InterestRateModel::Continuous(model) => {
+            continuous_utilization_rate_to_interest_rate_e18(
+                utilization_rate_e6,
+                model,
+            )
+        }
+    }
+}
+
+/// Returns the value at `x` of the line going through (`x_0`, `y_0`) and (`x_1`, `y_1`), where `x_0` <= `x` and `x_0` < `x_1` and `y_0` <= `y_1`.
+/// For `x` > `x_1` the line is extrapolated.
+fn interpolate_rate_e18(
+    x: u32,
+    (x_0, y_0): (u32, u64),
+    (x_1, y_1): (u32, u64),
+) -> Result<u64, MathError> {
+    let growth = mul_div(
+        x.saturating_sub(x_0) as u128,
+        y_1.saturating_sub(y_0) as u128,
+        x_1.saturating_sub(x_0) as u128,
+        Rounding::Up,
+    )?;
+    u64::try_from(growth)
+        .map_err(|_| MathError::Overflow)?
+        .checked_add(y_0)
+        .ok_or(MathError::Overflow)
+}
+
+pub fn two_slope_utilization_rate_to_interest_rate_e18(
+    utilization_rate_e6: u32,
+    params: &TwoSlopeRateParams,
+) -> Result<u64, MathError> {
+    if utilization_rate_e6 <= params.optimal_ur_e6 {
+        interpolate_rate_e18(
+            utilization_rate_e6,
+            (0, params.base_rate_e18),
+            (params.optimal_ur_e6, params.rate_at_optimal_ur_e18),
+        )
+    } else {
+        interpolate_rate_e18(
+            utilization_rate_e6,
+            (params.optimal_ur_e6, params.rate_at_optimal_ur_e18),
+            (E6_U32, params.rate_at_max_ur_e18),
+        )
+    }
+}
+
+pub fn multi_kink_utilization_rate_to_interest_rate_e18(
+    utilization_rate_e6: u32,
+    params: &MultiKinkRateParams,
+) -> Result<u64, MathError> {
+    let mut previous_kink = (0, 0);
+    for (kink_ur_e6, kink_rate_e18) in RATE_KINKS_UR_E6
+        .iter()
+        .zip(params.rates_at_kinks_e18.iter())
+    {
+        let kink = (*kink_ur_e6, *kink_rate_e18);
+        if utilization_rate_e6 <= kink.0 {
+            return interpolate_rate_e18(
+                utilization_rate_e6,
+                previous_kink,
+                kink,
+            );
+        }
+        previous_kink = kink;
+    }
+    // utilization above 100% - the last segment is extrapolated
+    let last = RATE_KINKS_UR_E6.len() - 1;
+    interpolate_rate_e18(
+        utilization_rate_e6,
+        (
+            RATE_KINKS_UR_E6[last - 1],
+            params.rates_at_kinks_e18[last - 1],
+        ),
+        (RATE_KINKS_UR_E6[last], params.rates_at_kinks_e18[last]),
+    )
+}
+
+pub fn continuous_utilization_rate_to_interest_rate_e18(
+    utilization_rate_e6: u32,
+    model: &ContinuousInterestRateModel,
+) -> Result<u64, MathError> {
+    if utilization_rate_e6 <= model.target_ur_e6 {
+        interpolate_rate_e18(
+            utilization_rate_e6,
+            (0, 0),
+            (model.target_ur_e6, model.rate_at_target_ur_e18),
+        )
+    } else {
+        interpolate_rate_e18(
+            utilization_rate_e6,
+            (model.target_ur_e6, model.rate_at_target_ur_e18),
+            (E6_U32, model.rate_at_max_ur_e18),
+        )
+    }
+}
+
+pub fn adaptive_utilization_rate_to_interest_rate_e18(
+    utilization_rate_e6: u32,
+    interest_rate_model: &AdaptiveInterestRateModel,
 ) -> Result<u64, MathError> {
     if utilization_rate_e6 == 0 {
         return Ok(0);
@@ -57,15 +185,16 @@ mod tests {
     // 0.01 % / 365 * 24 * 60 * 60 * E18
     const ONE_PERCENT_APR_E18: u64 = 3_170_979;
     const TEN_PERCENT_APR: u64 = 10 * ONE_PERCENT_APR_E18;
-    const INTEREST_RATE_MDOEL: InterestRateModel = InterestRateModel {
-        target_ur_e6: 900_000,                         // 90%
-        min_rate_at_target_e18: ONE_PERCENT_APR_E18, // 1%, not imporant for this test
-        max_rate_at_target_e18: TEN_PERCENT_APR, // 10%, not imporant for this test
-        rate_at_target_ur_e18: TEN_PERCENT_APR,  // 10%
-        rate_at_max_ur_e18: 100 * ONE_PERCENT_APR_E18, // 100%
-        minimal_time_between_adjustments: 0,
-        last_adjustment_timestamp: 0,
-    };
+    const INTEREST_RATE_MDOEL: InterestRateModel =
+        InterestRateModel::Adaptive(AdaptiveInterestRateModel {
+            target_ur_e6: 900_000,                         // 90%
+            min_rate_at_target_e18: ONE_PERCENT_APR_E18, // 1%, not imporant for this test
+            max_rate_at_target_e18: TEN_PERCENT_APR, // 10%, not imporant for this test
+            rate_at_target_ur_e18: TEN_PERCENT_APR,  // 10%
+            rate_at_max_ur_e18: 100 * ONE_PERCENT_APR_E18, // 100%
+            minimal_time_between_adjustments: 0,
+            last_adjustment_timestamp: 0,
+        });
 
     #[test]
     fn utilization_rate_to_interest_rate_e18_tests() {
@@ -131,4 +260,181 @@ mod tests {
             Ok(190 * ONE_PERCENT_APR_E18)
         );
     }
+
+    #[test]
+    fn two_slope_utilization_rate_to_interest_rate_e18_tests() {
+        let model = InterestRateModel::TwoSlope(TwoSlopeRateParams {
+            optimal_ur_e6: 800_000,
+            base_rate_e18: ONE_PERCENT_APR_E18,
+            rate_at_optimal_ur_e18: 5 * ONE_PERCENT_APR_E18,
+            rate_at_max_ur_e18: 105 * ONE_PERCENT_APR_E18,
+        });
+        //0%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(0, &model),
+            Ok(ONE_PERCENT_APR_E18)
+        );
+        //40%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(400_000, &model),
+            Ok(3 * ONE_PERCENT_APR_E18)
+        );
+        //80%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(800_000, &model),
+            Ok(5 * ONE_PERCENT_APR_E18)
+        );
+        //90%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(900_000, &model),
+            Ok(55 * ONE_PERCENT_APR_E18)
+        );
+        //100%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(1_000_000, &model),
+            Ok(105 * ONE_PERCENT_APR_E18)
+        );
+    }
+
+    #[test]
+    fn multi_kink_utilization_rate_to_interest_rate_e18_tests() {
+        let model = InterestRateModel::MultiKink(MultiKinkRateParams {
+            rates_at_kinks_e18: [
+                2 * ONE_PERCENT_APR_E18,
+                3 * ONE_PERCENT_APR_E18,
+                4 * ONE_PERCENT_APR_E18,
+                6 * ONE_PERCENT_APR_E18,
+                10 * ONE_PERCENT_APR_E18,
+                30 * ONE_PERCENT_APR_E18,
+                100 * ONE_PERCENT_APR_E18,
+            ],
+        });
+        //0%
+        assert_eq!(utilization_rate_to_interest_rate_e18(0, &model), Ok(0));
+        //25%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(250_000, &model),
+            Ok(ONE_PERCENT_APR_E18)
+        );
+        //50%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(500_000, &model),
+            Ok(2 * ONE_PERCENT_APR_E18)
+        );
+        //85%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(850_000, &model),
+            Ok(8 * ONE_PERCENT_APR_E18)
+        );
+        //100%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(1_000_000, &model),
+            Ok(100 * ONE_PERCENT_APR_E18)
+        );
+        //105% - the last segment is extrapolated
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(1_050_000, &model),
+            Ok(170 * ONE_PERCENT_APR_E18)
+        );
+    }
+
+    #[test]
+    fn multi_kink_utilization_rate_to_interest_rate_e18_at_kinks() {
+        let rates_at_kinks_e18 = [
+            2 * ONE_PERCENT_APR_E18,
+            3 * ONE_PERCENT_APR_E18,
+            4 * ONE_PERCENT_APR_E18,
+            6 * ONE_PERCENT_APR_E18,
+            10 * ONE_PERCENT_APR_E18,
+            30 * ONE_PERCENT_APR_E18,
+            100 * ONE_PERCENT_APR_E18,
+        ];
+        let model = InterestRateModel::MultiKink(MultiKinkRateParams {
+            rates_at_kinks_e18,
+        });
+
+        let mut previous_rate = 0;
+        for (kink_ur_e6, kink_rate_e18) in
+            RATE_KINKS_UR_E6.iter().zip(rates_at_kinks_e18.iter())
+        {
+            let below =
+                utilization_rate_to_interest_rate_e18(kink_ur_e6 - 1, &model)
+                    .unwrap();
+            let at = utilization_rate_to_interest_rate_e18(*kink_ur_e6, &model)
+                .unwrap();
+            let above =
+                utilization_rate_to_interest_rate_e18(kink_ur_e6 + 1, &model)
+                    .unwrap();
+            // the curve is continuous at the kink and the kink's rate is hit exactly
+            assert_eq!(at, *kink_rate_e18);
+            assert!(previous_rate <= below && below < at && at < above);
+            previous_rate = at;
+        }
+
+        // just above 50% - the second segment rounded up
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(500_001, &model),
+            Ok(2 * ONE_PERCENT_APR_E18 + 32)
+        );
+        // just below and above 95%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(949_999, &model),
+            Ok(30 * ONE_PERCENT_APR_E18 - 1_268)
+        );
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(950_001, &model),
+            Ok(30 * ONE_PERCENT_APR_E18 + 4_440)
+        );
+    }
+
+    #[test]
+    fn continuous_utilization_rate_to_interest_rate_e18_tests() {
+        let model =
+            InterestRateModel::Continuous(ContinuousInterestRateModel {
+                target_ur_e6: 800_000,
+                min_rate_at_target_e18: ONE_PERCENT_APR_E18,
+                max_rate_at_target_e18: 20 * ONE_PERCENT_APR_E18,
+                rate_at_target_ur_e18: TEN_PERCENT_APR,
+                rate_at_max_ur_e18: 100 * ONE_PERCENT_APR_E18,
+                full_adjustment_duration: 1_000,
+                last_update_timestamp: 0,
+            });
+        //0%
+        assert_eq!(utilization_rate_to_interest_rate_e18(0, &model), Ok(0));
+        //40%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(400_000, &model),
+            Ok(5 * ONE_PERCENT_APR_E18)
+        );
+        //80%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(800_000, &model),
+            Ok(TEN_PERCENT_APR)
+        );
+        //90%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(900_000, &model),
+            Ok(55 * ONE_PERCENT_APR_E18)
+        );
+        //100%
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(1_000_000, &model),
+            Ok(100 * ONE_PERCENT_APR_E18)
+        );
+    }
+
+    #[test]
+    fn fixed_utilization_rate_to_interest_rate_e18_tests() {
+        let model = InterestRateModel::Fixed {
+            rate_e18: TEN_PERCENT_APR,
+        };
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(0, &model),
+            Ok(TEN_PERCENT_APR)
+        );
+        assert_eq!(
+            utilization_rate_to_interest_rate_e18(1_000_000, &model),
+            Ok(TEN_PERCENT_APR)
+        );
+    }
 }
@@ -1,7 +1,9 @@
 // SPDX-License-Identifier: BUSL-1.1
+mod compound_interest;
 mod constants;
 mod operations;
 
+pub use compound_interest::*;
 pub use constants::*;
 pub use operations::*;
 
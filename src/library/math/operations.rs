@@ -117,3 +117,53 @@ pub fn calculate_asset_amount_value_e8(
     let denominator = decimal_multplier.checked_mul(E10_U128).unwrap(); // here e18 coming from price is adjusted to e8 // this should not fail as long decimal multiplier is smaller than 3 *10^28
     mul_div(*amount, *price_e18, denominator, Rounding::Down).unwrap()
 }
+
+/// Returns the average rate of `total` borrowed with `average_rate_e18` and `amount` borrowed with `rate_e18`, weighted by amounts.
+pub fn add_to_weighted_rate_e18(
+    total: u128,
+    average_rate_e18: u64,
+    amount: u128,
+    rate_e18: u64,
+) -> Result<u64, MathError> {
+    let new_total = total.checked_add(amount).ok_or(MathError::Overflow)?;
+    if new_total == 0 {
+        return Ok(0);
+    }
+    let weighted_rates_sum = U256::from(average_rate_e18)
+        .checked_mul(U256::from(total))
+        .ok_or(MathError::Overflow)?
+        .checked_add(
+            U256::from(rate_e18)
+                .checked_mul(U256::from(amount))
+                .ok_or(MathError::Overflow)?,
+        )
+        .ok_or(MathError::Overflow)?;
+    u64::try_from(weighted_rates_sum / U256::from(new_total))
+        .map_err(|_| MathError::Overflow)
+}
+
+/// Returns the average rate of `total` borrowed with `average_rate_e18` after removing `amount` borrowed with `rate_e18`, weighted by amounts.
+///
+/// # Note
+/// In case of rounding errors the result saturates at 0.
+pub fn remove_from_weighted_rate_e18(
+    total: u128,
+    average_rate_e18: u64,
+    amount: u128,
+    rate_e18: u64,
+) -> Result<u64, MathError> {
+    let new_total = total.saturating_sub(amount);
+    if new_total == 0 {
+        return Ok(0);
+    }
+    let weighted_rates_sum = U256::from(average_rate_e18)
+        .checked_mul(U256::from(total))
+        .ok_or(MathError::Overflow)?
+        .saturating_sub(
+            U256::from(rate_e18)
+                .checked_mul(U256::from(amount))
+                .ok_or(MathError::Overflow)?,
+        );
+    u64::try_from(weighted_rates_sum / U256::from(new_total))
+        .map_err(|_| MathError::Overflow)
+}
@@ -1,6 +1,115 @@
 // SPDX-License-Identifier: BUSL-1.1
+use ink::prelude::{vec, vec::Vec};
+
+use super::AssetId;
+
 pub type Bitmap128 = u128;
 
+const PAGE_BITS: u32 = Bitmap128::BITS;
+
+/// Bitmap of assets - the bit at position `asset_id` is set if the asset is in the set.
+/// The bits are stored in 128-bit pages which are added when a bit of an asset beyond the last page is set
+/// (and trimmed when the last pages become empty), so the number of registered assets is not limited by the bitmap.
+#[derive(
+    Debug, Default, Clone, PartialEq, Eq, scale::Encode, scale::Decode,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct AssetsBitmap {
+    pages: Vec<Bitmap128>,
+}
+
+impl From<Bitmap128> for AssetsBitmap {
+    fn from(page: Bitmap128) -> Self {
+        let mut bitmap = AssetsBitmap { pages: vec![page] };
+        bitmap.trim();
+        bitmap
+    }
+}
+
+impl AssetsBitmap {
+    pub fn contains(&self, asset_id: AssetId) -> bool {
+        self.pages
+            .get((asset_id / PAGE_BITS) as usize)
+            .is_some_and(|page| (page >> (asset_id % PAGE_BITS)) & 1 == 1)
+    }
+
+    pub fn insert(&mut self, asset_id: AssetId) {
+        let page_index = (asset_id / PAGE_BITS) as usize;
+        if self.pages.len() <= page_index {
+            self.pages.resize(page_index + 1, 0);
+        }
+        self.pages[page_index] |= 1_u128 << (asset_id % PAGE_BITS);
+    }
+
+    pub fn remove(&mut self, asset_id: AssetId) {
+        if let Some(page) = self.pages.get_mut((asset_id / PAGE_BITS) as usize)
+        {
+            *page &= !(1_u128 << (asset_id % PAGE_BITS));
+            self.trim();
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pages.iter().all(|page| *page == 0)
+    }
+
+    /// Returns the assets that are in `self` or in `other`.
+    pub fn union(&self, other: &AssetsBitmap) -> AssetsBitmap {
+        let (longer, shorter) = if self.pages.len() >= other.pages.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut union = longer.clone();
+        for (page, other_page) in union.pages.iter_mut().zip(&shorter.pages) {
+            *page |= other_page;
+        }
+        union
+    }
+
+    /// Returns the assets that are both in `self` and in `other`.
+    pub fn intersection(&self, other: &AssetsBitmap) -> AssetsBitmap {
+        let mut intersection = AssetsBitmap {
+            pages: self
+                .pages
+                .iter()
+                .zip(&other.pages)
+                .map(|(page, other_page)| page & other_page)
+                .collect(),
+        };
+        intersection.trim();
+        intersection
+    }
+
+    /// Iterates over the assets in the bitmap in the ascending order of `asset_id`.
+    pub fn iter(&self) -> impl Iterator<Item = AssetId> + '_ {
+        self.pages
+            .iter()
+            .enumerate()
+            .flat_map(|(page_index, page)| {
+                let mut rest = *page;
+                core::iter::from_fn(move || {
+                    if rest == 0 {
+                        return None;
+                    }
+                    let bit = rest.trailing_zeros();
+                    rest &= rest - 1;
+                    Some(page_index as u32 * PAGE_BITS + bit)
+                })
+            })
+    }
+
+    /// removes the empty pages from the end, so the stored bitmap doesn't grow with the ids of removed assets.
+    fn trim(&mut self) {
+        while self.pages.last() == Some(&0) {
+            self.pages.pop();
+        }
+    }
+}
+
 /// stores information about which asset is deposited, used as collatearl and borrowed by an account and which `market_rule_id` the account has chosen.
 #[derive(Debug, Default, scale::Encode, scale::Decode)]
 #[cfg_attr(
@@ -9,11 +118,112 @@ pub type Bitmap128 = u128;
 )]
 pub struct AccountConfig {
     /// active account deposits. The order in the Bitmap corresponds to the `assets_id`.
-    pub deposits: Bitmap128,
+    pub deposits: AssetsBitmap,
     /// turned on collaterals. The order in the Bitmap corresponds to the `assets_id`.
-    pub collaterals: Bitmap128,
+    pub collaterals: AssetsBitmap,
     /// active borrow_variable. The order in the Bitmap corresponds to the `assets_id`.
-    pub borrows: Bitmap128,
+    pub borrows: AssetsBitmap,
+    /// active borrow_stable. The order in the Bitmap corresponds to the `assets_id`.
+    pub stable_borrows: AssetsBitmap,
     /// id of `MarketRule` chosen by account
     pub market_rule_id: u32,
+    /// `asset_id` of the isolated collateral used by account. If set, it is the only collateral that backs account's debt.
+    pub isolated_collateral: Option<u32>,
+}
+
+impl AccountConfig {
+    /// Returns true if the account has debt but no collateral (no deposit used as collateral) that could back it.
+    /// The remaining deposits of such account can't be taken by liquidators - they are seized when the debt is written off.
+    pub fn has_unbacked_debt(&self) -> bool {
+        !self.debts().is_empty()
+            && self.deposits.intersection(&self.collaterals).is_empty()
+    }
+
+    /// Returns the assets borrowed by the account - with the variable or the stable rate.
+    pub fn debts(&self) -> AssetsBitmap {
+        self.borrows.union(&self.stable_borrows)
+    }
+
+    /// Turns off the isolation mode if the account has no debt and doesn't use the isolated collateral anymore.
+    pub fn exit_isolation_if_unused(&mut self) {
+        if let Some(isolated_id) = self.isolated_collateral {
+            if self.debts().is_empty()
+                && !self.collaterals.contains(isolated_id)
+            {
+                self.isolated_collateral = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn assets_bitmap_grows_beyond_a_page() {
+        let mut bitmap = AssetsBitmap::default();
+        assert!(bitmap.is_empty());
+
+        bitmap.insert(3);
+        bitmap.insert(127);
+        bitmap.insert(128);
+        bitmap.insert(300);
+        assert!(bitmap.contains(3));
+        assert!(bitmap.contains(127));
+        assert!(bitmap.contains(128));
+        assert!(bitmap.contains(300));
+        assert!(!bitmap.contains(4));
+        assert!(!bitmap.contains(1_000));
+        assert_eq!(bitmap.iter().collect::<Vec<_>>(), vec![3, 127, 128, 300]);
+
+        // the emptied pages are trimmed
+        bitmap.remove(300);
+        bitmap.remove(128);
+        assert_eq!(bitmap, AssetsBitmap::from((1_u128 << 127) | (1 << 3)));
+        bitmap.remove(3);
+        bitmap.remove(127);
+        assert!(bitmap.is_empty());
+        assert_eq!(bitmap, AssetsBitmap::default());
+    }
+
+    #[test]
+    fn assets_bitmap_union_and_intersection() {
+        let mut a = AssetsBitmap::from(0b110);
+        a.insert(200);
+        let b = AssetsBitmap::from(0b011);
+
+        assert_eq!(a.union(&b).iter().collect::<Vec<_>>(), vec![0, 1, 2, 200]);
+        assert_eq!(b.union(&a), a.union(&b));
+        assert_eq!(a.intersection(&b), AssetsBitmap::from(0b010));
+        assert_eq!(b.intersection(&a), AssetsBitmap::from(0b010));
+
+        let mut c = AssetsBitmap::default();
+        c.insert(200);
+        assert_eq!(a.intersection(&c).iter().collect::<Vec<_>>(), vec![200]);
+        assert!(b.intersection(&c).is_empty());
+    }
+
+    #[test]
+    fn unbacked_debt_and_isolation_exit() {
+        let mut config = AccountConfig::default();
+        config.deposits.insert(130);
+        config.stable_borrows.insert(1);
+        assert!(config.has_unbacked_debt());
+
+        config.collaterals.insert(130);
+        assert!(!config.has_unbacked_debt());
+
+        config.isolated_collateral = Some(130);
+        config.exit_isolation_if_unused();
+        assert_eq!(config.isolated_collateral, Some(130));
+
+        config.stable_borrows.remove(1);
+        config.exit_isolation_if_unused();
+        assert_eq!(config.isolated_collateral, Some(130));
+
+        config.collaterals.remove(130);
+        config.exit_isolation_if_unused();
+        assert_eq!(config.isolated_collateral, None);
+    }
 }
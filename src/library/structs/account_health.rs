@@ -0,0 +1,75 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::math::{
+    errors::MathError,
+    operations::{mul_div, Rounding},
+};
+
+use crate::math::E18_U128;
+
+/// Health of an account - values of its collaterals and debts with and without the coefficients of its market rule applied.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct AccountHealth {
+    /// sum of values of deposits that back the debt. E8 notation.
+    pub total_collateral_value_e8: u128,
+    /// sum of values of debts (variable and stable). E8 notation.
+    pub total_debt_value_e8: u128,
+    /// sum of values of deposits that back the debt weighted by the collateral coefficients. E6 notation.
+    pub collateral_power_e6: u128,
+    /// sum of values of debts weighted by the borrow coefficients. E6 notation.
+    pub debt_power_e6: u128,
+    /// `collateral_power_e6` / `debt_power_e6`. E18 notation. The account can be liquidated if it is below 10^18. u128::MAX if there is no debt.
+    pub health_factor_e18: u128,
+}
+
+impl AccountHealth {
+    /// sets `health_factor_e18` based on the collateral and debt powers.
+    pub fn update_health_factor(&mut self) -> Result<(), MathError> {
+        self.health_factor_e18 = if self.debt_power_e6 == 0 {
+            u128::MAX
+        } else {
+            mul_div(
+                self.collateral_power_e6,
+                E18_U128,
+                self.debt_power_e6,
+                Rounding::Down,
+            )?
+        };
+        Ok(())
+    }
+
+    pub fn is_collateralized(&self) -> bool {
+        self.collateral_power_e6 >= self.debt_power_e6
+    }
+
+    /// Returns the collateral power exceeding the debt power. E6 notation.
+    pub fn free_power_e6(&self) -> u128 {
+        self.collateral_power_e6.saturating_sub(self.debt_power_e6)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn health_factor() {
+        let mut health = AccountHealth {
+            collateral_power_e6: 1_500_000,
+            debt_power_e6: 1_000_000,
+            ..Default::default()
+        };
+        health.update_health_factor().unwrap();
+        assert_eq!(health.health_factor_e18, 1_500_000_000_000_000_000);
+        assert_eq!(health.free_power_e6(), 500_000);
+
+        health.debt_power_e6 = 2_000_000;
+        health.update_health_factor().unwrap();
+        assert_eq!(health.health_factor_e18, 750_000_000_000_000_000);
+        assert_eq!(health.free_power_e6(), 0);
+
+        health.debt_power_e6 = 0;
+        health.update_health_factor().unwrap();
+        assert_eq!(health.health_factor_e18, u128::MAX);
+    }
+}
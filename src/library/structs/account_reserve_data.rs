@@ -1,9 +1,13 @@
 // SPDX-License-Identifier: BUSL-1.1
-use pendzl::{math::errors::MathError, traits::Balance};
+use pendzl::{
+    math::errors::MathError,
+    traits::{Balance, Timestamp},
+};
 
 use crate::math::{
-    e0_mul_e18_div_e18_to_e0_rdown, e0_mul_e18_div_e18_to_e0_rup,
-    e0_mul_e6_to_e0_rup, E18_U128, E6_U32,
+    add_to_weighted_rate_e18, e0_mul_e18_div_e18_to_e0_rdown,
+    e0_mul_e18_div_e18_to_e0_rup, e0_mul_e6_to_e0_rup, e18_mul_e0_to_e18,
+    E18_U128, E6_U32,
 };
 
 use super::{
@@ -26,6 +30,12 @@ pub struct AccountReserveData {
     pub applied_deposit_index_e18: u128,
     /// index that is used to accumulate debt interest.
     pub applied_debt_index_e18: u128,
+    /// underlying asset amount of stable debt plus accumulated interest.
+    pub stable_debt: Balance,
+    /// stable rate locked by the account. 10^24 = 100%  millisecond Percentage Rate.
+    pub stable_rate_e18: u64,
+    /// timestamp until which the stable interest was accumulated.
+    pub applied_stable_timestamp: Timestamp,
 }
 
 /// type used to identify asset
@@ -39,7 +49,7 @@ impl AccountReserveData {
         reserve_data: &mut ReserveData,
         amount: &u128,
     ) -> Result<(), MathError> {
-        account_config.deposits |= 1_u128 << *asset_id;
+        account_config.deposits.insert(*asset_id);
 
         self.deposit = self
             .deposit
@@ -60,7 +70,7 @@ impl AccountReserveData {
         amount: &u128,
     ) -> Result<(), MathError> {
         if *amount == self.deposit {
-            account_config.deposits &= !(1_u128 << asset_id);
+            account_config.deposits.remove(*asset_id);
         }
         self.deposit = self
             .deposit
@@ -70,7 +80,7 @@ impl AccountReserveData {
         reserve_data.decrease_total_deposit(amount)?;
 
         if self.deposit < reserve_restrictions.minimal_collateral {
-            account_config.collaterals &= !(1_u128 << asset_id);
+            account_config.collaterals.remove(*asset_id);
         }
         Ok(())
     }
@@ -82,7 +92,7 @@ impl AccountReserveData {
         reserve_data: &mut ReserveData,
         amount: &u128,
     ) -> Result<(), MathError> {
-        account_config.borrows |= 1_u128 << *asset_id;
+        account_config.borrows.insert(*asset_id);
 
         self.debt =
             self.debt.checked_add(*amount).ok_or(MathError::Overflow)?;
@@ -100,7 +110,7 @@ impl AccountReserveData {
         amount: &u128,
     ) -> Result<(), MathError> {
         if *amount == self.debt {
-            account_config.borrows &= !(1_u128 << asset_id);
+            account_config.borrows.remove(*asset_id);
         }
         self.debt =
             self.debt.checked_sub(*amount).ok_or(MathError::Underflow)?;
@@ -110,6 +120,108 @@ impl AccountReserveData {
         Ok(())
     }
 
+    pub fn increase_account_stable_debt(
+        &mut self,
+        asset_id: &AssetId,
+        account_config: &mut AccountConfig,
+        reserve_data: &mut ReserveData,
+        amount: &u128,
+        stable_rate_e18: &u64,
+    ) -> Result<(), MathError> {
+        account_config.stable_borrows.insert(*asset_id);
+
+        reserve_data.increase_total_stable_debt(amount, stable_rate_e18)?;
+
+        // the account's rate is the average of the previous rate and the new rate weighted by debts.
+        self.stable_rate_e18 = add_to_weighted_rate_e18(
+            self.stable_debt,
+            self.stable_rate_e18,
+            *amount,
+            *stable_rate_e18,
+        )?;
+        self.stable_debt = self
+            .stable_debt
+            .checked_add(*amount)
+            .ok_or(MathError::Overflow)?;
+
+        Ok(())
+    }
+
+    pub fn decrease_account_stable_debt(
+        &mut self,
+        asset_id: &AssetId,
+        account_config: &mut AccountConfig,
+        reserve_data: &mut ReserveData,
+        amount: &u128,
+    ) -> Result<(), MathError> {
+        self.stable_debt = self
+            .stable_debt
+            .checked_sub(*amount)
+            .ok_or(MathError::Underflow)?;
+
+        reserve_data
+            .decrease_total_stable_debt(amount, &self.stable_rate_e18)?;
+
+        if self.stable_debt == 0 {
+            account_config.stable_borrows.remove(*asset_id);
+            self.stable_rate_e18 = 0;
+        }
+
+        Ok(())
+    }
+
+    /// changes the stable rate of the account to `new_stable_rate_e18` and updates the reserve's average stable rate.
+    pub fn rebalance_stable_rate(
+        &mut self,
+        reserve_data: &mut ReserveData,
+        new_stable_rate_e18: &u64,
+    ) -> Result<(), MathError> {
+        reserve_data.decrease_total_stable_debt(
+            &self.stable_debt,
+            &self.stable_rate_e18,
+        )?;
+        reserve_data.increase_total_stable_debt(
+            &self.stable_debt,
+            new_stable_rate_e18,
+        )?;
+        self.stable_rate_e18 = *new_stable_rate_e18;
+        Ok(())
+    }
+
+    /// accumulates stable interest with the account's stable rate up to the `timestamp`.
+    ///
+    /// # Returns
+    /// accumulated_stable_debt_interest
+    pub fn accumulate_account_stable_interest(
+        &mut self,
+        timestamp: &Timestamp,
+    ) -> Result<Balance, MathError> {
+        if self.applied_stable_timestamp >= *timestamp {
+            return Ok(0);
+        }
+        let delta_timestamp = timestamp
+            .checked_sub(self.applied_stable_timestamp)
+            .ok_or(MathError::Underflow)?;
+        self.applied_stable_timestamp = *timestamp;
+
+        if self.stable_debt == 0 || self.stable_rate_e18 == 0 {
+            return Ok(0);
+        }
+
+        let interest = e0_mul_e18_div_e18_to_e0_rup(
+            self.stable_debt,
+            e18_mul_e0_to_e18(self.stable_rate_e18, delta_timestamp),
+            E18_U128,
+        )?;
+
+        self.stable_debt = self
+            .stable_debt
+            .checked_add(interest)
+            .ok_or(MathError::Overflow)?;
+
+        Ok(interest)
+    }
+
     /// based on the `reserve_indexes_and_fees` and Self.applied_cumulative_**_index_e18 accumulates interest
     ///
     /// # Returns
@@ -120,7 +232,7 @@ impl AccountReserveData {
         reserve_fees: &mut ReserveFees,
         (deposit_fee_reduction_e6, debt_fee_reduction_e6): &FeeReductions,
     ) -> Result<(Balance, Balance), MathError> {
-        if self.applied_deposit_index_e18 >= reserve_indexes.deposit_index_e18
+        if self.applied_deposit_index_e18 == reserve_indexes.deposit_index_e18
             && self.applied_debt_index_e18 >= reserve_indexes.debt_index_e18
         {
             return Ok((0, 0));
@@ -165,6 +277,17 @@ impl AccountReserveData {
             self.deposit = updated_deposit;
 
             reserve_fees.increase_earned_fee(&fee)?;
+        } else if self.deposit != 0
+            && self.applied_deposit_index_e18
+                > reserve_indexes.deposit_index_e18
+        {
+            // deposit index was decreased by the socialization of a bad debt.
+            // The loss was already subtracted from the reserve's total deposit.
+            self.deposit = e0_mul_e18_div_e18_to_e0_rdown(
+                self.deposit,
+                reserve_indexes.deposit_index_e18,
+                self.applied_deposit_index_e18,
+            )?;
         }
         self.applied_deposit_index_e18 = reserve_indexes.deposit_index_e18;
 
@@ -219,6 +342,166 @@ impl AccountReserveData {
             debt: 0,
             applied_deposit_index_e18: E18_U128,
             applied_debt_index_e18: E18_U128,
+            stable_debt: 0,
+            stable_rate_e18: 0,
+            applied_stable_timestamp: 0,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::structs::AssetsBitmap;
+
+    #[test]
+    fn stable_debt_increases_and_decreases() {
+        let mut account_config = AccountConfig::default();
+        let mut reserve_data = ReserveData::default();
+        let mut account_data = AccountReserveData::my_default();
+
+        account_data
+            .increase_account_stable_debt(
+                &2,
+                &mut account_config,
+                &mut reserve_data,
+                &100,
+                &1000,
+            )
+            .unwrap();
+        assert_eq!(account_config.stable_borrows, AssetsBitmap::from(0b100));
+        assert_eq!(account_data.stable_debt, 100);
+        assert_eq!(account_data.stable_rate_e18, 1000);
+        assert_eq!(reserve_data.total_stable_debt, 100);
+        assert_eq!(reserve_data.average_stable_rate_e18, 1000);
+
+        // the rate is averaged weighted by the debts: (100 * 1000 + 300 * 2000) / 400
+        account_data
+            .increase_account_stable_debt(
+                &2,
+                &mut account_config,
+                &mut reserve_data,
+                &300,
+                &2000,
+            )
+            .unwrap();
+        assert_eq!(account_data.stable_debt, 400);
+        assert_eq!(account_data.stable_rate_e18, 1750);
+        assert_eq!(reserve_data.total_stable_debt, 400);
+        assert_eq!(reserve_data.average_stable_rate_e18, 1750);
+
+        account_data
+            .decrease_account_stable_debt(
+                &2,
+                &mut account_config,
+                &mut reserve_data,
+                &100,
+            )
+            .unwrap();
+        assert_eq!(account_config.stable_borrows, AssetsBitmap::from(0b100));
+        assert_eq!(account_data.stable_debt, 300);
+        assert_eq!(account_data.stable_rate_e18, 1750);
+        assert_eq!(reserve_data.total_stable_debt, 300);
+        assert_eq!(reserve_data.average_stable_rate_e18, 1750);
+
+        account_data
+            .decrease_account_stable_debt(
+                &2,
+                &mut account_config,
+                &mut reserve_data,
+                &300,
+            )
+            .unwrap();
+        assert!(account_config.stable_borrows.is_empty());
+        assert_eq!(account_data.stable_debt, 0);
+        assert_eq!(account_data.stable_rate_e18, 0);
+        assert_eq!(reserve_data.total_stable_debt, 0);
+        assert_eq!(reserve_data.average_stable_rate_e18, 0);
+
+        assert_eq!(
+            account_data.decrease_account_stable_debt(
+                &2,
+                &mut account_config,
+                &mut reserve_data,
+                &1,
+            ),
+            Err(MathError::Underflow)
+        );
+    }
+
+    #[test]
+    fn stable_interest_accumulates_with_account_rate() {
+        let mut account_data = AccountReserveData {
+            stable_debt: 1_000_000_000_000,
+            stable_rate_e18: 1_000_000,
+            applied_stable_timestamp: 1000,
+            ..AccountReserveData::my_default()
+        };
+
+        // 10^12 * 10^6 * 1000 / 10^18
+        assert_eq!(
+            account_data.accumulate_account_stable_interest(&2000),
+            Ok(1000)
+        );
+        assert_eq!(account_data.stable_debt, 1_000_000_001_000);
+        assert_eq!(account_data.applied_stable_timestamp, 2000);
+
+        assert_eq!(
+            account_data.accumulate_account_stable_interest(&2000),
+            Ok(0)
+        );
+        assert_eq!(
+            account_data.accumulate_account_stable_interest(&1500),
+            Ok(0)
+        );
+        assert_eq!(account_data.applied_stable_timestamp, 2000);
+
+        let mut no_debt_data = AccountReserveData {
+            stable_rate_e18: 1_000_000,
+            applied_stable_timestamp: 1000,
+            ..AccountReserveData::my_default()
+        };
+        assert_eq!(
+            no_debt_data.accumulate_account_stable_interest(&2000),
+            Ok(0)
+        );
+        assert_eq!(no_debt_data.stable_debt, 0);
+        assert_eq!(no_debt_data.applied_stable_timestamp, 2000);
+    }
+
+    #[test]
+    fn stable_rate_rebalance_updates_reserve_average() {
+        let mut account_config = AccountConfig::default();
+        let mut reserve_data = ReserveData::default();
+        let mut first = AccountReserveData::my_default();
+        let mut second = AccountReserveData::my_default();
+
+        first
+            .increase_account_stable_debt(
+                &0,
+                &mut account_config,
+                &mut reserve_data,
+                &100,
+                &1000,
+            )
+            .unwrap();
+        second
+            .increase_account_stable_debt(
+                &0,
+                &mut account_config,
+                &mut reserve_data,
+                &100,
+                &3000,
+            )
+            .unwrap();
+        assert_eq!(reserve_data.average_stable_rate_e18, 2000);
+
+        first
+            .rebalance_stable_rate(&mut reserve_data, &3000)
+            .unwrap();
+        assert_eq!(first.stable_rate_e18, 3000);
+        assert_eq!(first.stable_debt, 100);
+        assert_eq!(reserve_data.total_stable_debt, 200);
+        assert_eq!(reserve_data.average_stable_rate_e18, 3000);
+    }
+}
@@ -0,0 +1,159 @@
+// SPDX-License-Identifier: BUSL-1.1
+use super::Operation;
+
+/// Action that can be paused by the EMERGENCY_ADMIN.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PausableAction {
+    Deposit,
+    Withdraw,
+    /// variable and stable borrows.
+    Borrow,
+    /// variable and stable repays.
+    Repay,
+    Liquidate,
+    FlashLoan,
+    /// AToken (deposit) and VToken (debt) transfers.
+    Transfer,
+}
+
+/// Stores which actions are paused - either globally or in a reserve.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ActionsPause {
+    pub deposit: bool,
+    pub withdraw: bool,
+    pub borrow: bool,
+    pub repay: bool,
+    pub liquidate: bool,
+    pub flash_loan: bool,
+    pub transfer: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ActionsPauseError {
+    /// returned if the action is paused (globally or in the reserve).
+    ActionPaused(PausableAction),
+    /// returned if the pause flag of the action is already set to the value.
+    AlreadySet,
+}
+
+impl From<Operation> for PausableAction {
+    fn from(op: Operation) -> Self {
+        match op {
+            Operation::Deposit => PausableAction::Deposit,
+            Operation::Withdraw => PausableAction::Withdraw,
+            Operation::Borrow | Operation::BorrowStable => {
+                PausableAction::Borrow
+            }
+            Operation::Repay | Operation::RepayStable => PausableAction::Repay,
+        }
+    }
+}
+
+impl ActionsPause {
+    fn flag_mut(&mut self, action: PausableAction) -> &mut bool {
+        match action {
+            PausableAction::Deposit => &mut self.deposit,
+            PausableAction::Withdraw => &mut self.withdraw,
+            PausableAction::Borrow => &mut self.borrow,
+            PausableAction::Repay => &mut self.repay,
+            PausableAction::Liquidate => &mut self.liquidate,
+            PausableAction::FlashLoan => &mut self.flash_loan,
+            PausableAction::Transfer => &mut self.transfer,
+        }
+    }
+
+    pub fn is_paused(&self, action: PausableAction) -> bool {
+        let mut actions_pause = *self;
+        *actions_pause.flag_mut(action)
+    }
+
+    pub fn set_paused(
+        &mut self,
+        action: PausableAction,
+        paused: bool,
+    ) -> Result<(), ActionsPauseError> {
+        let flag = self.flag_mut(action);
+        if *flag == paused {
+            return Err(ActionsPauseError::AlreadySet);
+        }
+        *flag = paused;
+        Ok(())
+    }
+
+    /// Returns the actions paused either in `self` or in `other`.
+    pub fn union(&self, other: &ActionsPause) -> ActionsPause {
+        ActionsPause {
+            deposit: self.deposit || other.deposit,
+            withdraw: self.withdraw || other.withdraw,
+            borrow: self.borrow || other.borrow,
+            repay: self.repay || other.repay,
+            liquidate: self.liquidate || other.liquidate,
+            flash_loan: self.flash_loan || other.flash_loan,
+            transfer: self.transfer || other.transfer,
+        }
+    }
+
+    pub fn ensure_not_paused(
+        &self,
+        action: PausableAction,
+    ) -> Result<(), ActionsPauseError> {
+        if self.is_paused(action) {
+            return Err(ActionsPauseError::ActionPaused(action));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pausing_an_action_leaves_the_others_working() {
+        let mut actions_pause = ActionsPause::default();
+        assert_eq!(
+            actions_pause.set_paused(PausableAction::Borrow, true),
+            Ok(())
+        );
+        assert_eq!(
+            actions_pause.set_paused(PausableAction::Borrow, true),
+            Err(ActionsPauseError::AlreadySet)
+        );
+
+        assert_eq!(
+            actions_pause.ensure_not_paused(PausableAction::from(
+                Operation::BorrowStable
+            )),
+            Err(ActionsPauseError::ActionPaused(PausableAction::Borrow))
+        );
+        assert_eq!(
+            actions_pause
+                .ensure_not_paused(PausableAction::from(Operation::Repay)),
+            Ok(())
+        );
+        assert_eq!(
+            actions_pause.ensure_not_paused(PausableAction::Deposit),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn union_pauses_actions_paused_in_either() {
+        let mut global = ActionsPause::default();
+        global.set_paused(PausableAction::Deposit, true).unwrap();
+        let mut reserve = ActionsPause::default();
+        reserve.set_paused(PausableAction::Transfer, true).unwrap();
+
+        let actions_pause = global.union(&reserve);
+        assert!(actions_pause.is_paused(PausableAction::Deposit));
+        assert!(actions_pause.is_paused(PausableAction::Transfer));
+        assert!(!actions_pause.is_paused(PausableAction::Repay));
+        assert!(!actions_pause.is_paused(PausableAction::Liquidate));
+    }
+}
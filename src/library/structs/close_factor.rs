@@ -0,0 +1,255 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::{
+    math::{
+        errors::MathError,
+        operations::{mul_div, Rounding},
+    },
+    traits::Balance,
+};
+use primitive_types::U256;
+
+use crate::math::{E18_U128, E6_U128, E6_U32};
+
+/// Defines how much of the debt in a reserve can be repaid during a single liquidation.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct CloseFactor {
+    /// maximal part of the liquidated account's debt that can be repaid in one liquidation. 1e6 == 100%.
+    pub close_factor_e6: u32,
+    /// if Some, the repaid amount is additionally capped so that the liquidated account
+    /// returns only to this collateralization (collateral power / debt power). 1e6 == 100%.
+    pub target_collateralization_e6: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum CloseFactorError {
+    /// returned if the close factor to be set is invalid.
+    InvalidCloseFactor,
+}
+
+impl CloseFactor {
+    /// verifies that:
+    /// 1) the close factor is greater than 0 and not greater than 100%
+    /// 2) the target collateralization is greater than 100%
+    pub fn validate(&self) -> Result<(), CloseFactorError> {
+        if self.close_factor_e6 == 0 || self.close_factor_e6 > E6_U32 {
+            return Err(CloseFactorError::InvalidCloseFactor);
+        }
+        if let Some(target) = self.target_collateralization_e6 {
+            if target <= E6_U32 {
+                return Err(CloseFactorError::InvalidCloseFactor);
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the maximal amount of `debt` that can be repaid in one liquidation.
+    ///
+    /// * `debt` - debt of the liquidated account in the repaid asset (with accumulated interest).
+    /// * `collateral_power_e6` - weighted collateral value of the liquidated account.
+    /// * `debt_power_e6` - weighted debt value of the liquidated account.
+    /// * `repay_price_e18` - price of the repaid asset.
+    /// * `repay_decimal_multiplier` - decimal multiplier of the repaid asset.
+    /// * `repay_borrow_coefficient_e6` - borrow coefficient of the repaid asset.
+    /// * `take_collateral_coefficient_e6` - collateral coefficient of the taken asset.
+    /// * `total_penalty_e6` - sum of penalties of repaid and taken assets actually paid in the liquidation (e.g. a part of them during an auction).
+    ///
+    /// # Note
+    /// The target collateralization cap is applied only if repaying the debt
+    /// improves the collateralization i.e. `target * borrow_coefficient > (1 + penalty) * collateral_coefficient`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn max_amount_to_repay(
+        &self,
+        debt: Balance,
+        collateral_power_e6: u128,
+        debt_power_e6: u128,
+        repay_price_e18: u128,
+        repay_decimal_multiplier: u128,
+        repay_borrow_coefficient_e6: u128,
+        take_collateral_coefficient_e6: u128,
+        total_penalty_e6: u128,
+    ) -> Result<Balance, MathError> {
+        let mut max_amount =
+            mul_div(debt, self.close_factor_e6 as u128, E6_U128, Rounding::Up)?;
+
+        if let Some(target_e6) = self.target_collateralization_e6 {
+            let target = U256::from(target_e6);
+            let gain = target
+                .checked_mul(U256::from(repay_borrow_coefficient_e6))
+                .ok_or(MathError::Overflow)?;
+            let loss = U256::from(E6_U128)
+                .checked_add(U256::from(total_penalty_e6))
+                .ok_or(MathError::Overflow)?
+                .checked_mul(U256::from(take_collateral_coefficient_e6))
+                .ok_or(MathError::Overflow)?;
+            let missing = target
+                .checked_mul(U256::from(debt_power_e6))
+                .ok_or(MathError::Overflow)?
+                .saturating_sub(
+                    U256::from(collateral_power_e6)
+                        .checked_mul(U256::from(E6_U128))
+                        .ok_or(MathError::Overflow)?,
+                );
+
+            if gain > loss && repay_price_e18 != 0 {
+                let numerator = missing
+                    .checked_mul(U256::from(repay_decimal_multiplier))
+                    .ok_or(MathError::Overflow)?
+                    .checked_mul(U256::from(E18_U128))
+                    .ok_or(MathError::Overflow)?;
+                let denominator = U256::from(repay_price_e18)
+                    .checked_mul(gain.saturating_sub(loss))
+                    .ok_or(MathError::Overflow)?;
+                let (quotient, remainder) = numerator.div_mod(denominator);
+                let target_amount = if remainder.is_zero() {
+                    quotient
+                } else {
+                    quotient.saturating_add(U256::one())
+                };
+                if target_amount < U256::from(max_amount) {
+                    max_amount = target_amount.as_u128();
+                }
+            }
+        }
+
+        Ok(max_amount.min(debt))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    const E6_DECIMAL_MULTIPLIER: u128 = 1_000_000;
+
+    #[test]
+    fn max_amount_to_repay_close_factor_only() {
+        let close_factor = CloseFactor {
+            close_factor_e6: 500_000, // 50%
+            target_collateralization_e6: None,
+        };
+        assert_eq!(
+            close_factor.max_amount_to_repay(
+                100_000_000,
+                90_000_000,
+                100_000_000,
+                E18_U128,
+                E6_DECIMAL_MULTIPLIER,
+                1_000_000,
+                800_000,
+                100_000,
+            ),
+            Ok(50_000_000)
+        );
+    }
+
+    #[test]
+    fn max_amount_to_repay_target_collateralization() {
+        // collateral power 90$, debt power 100$, target 110%
+        // after repaying x: 90 - x * 1.1 * 0.8 = 1.1 * (100 - x) => x = 90.(90)
+        let close_factor = CloseFactor {
+            close_factor_e6: 1_000_000, // 100%
+            target_collateralization_e6: Some(1_100_000),
+        };
+        assert_eq!(
+            close_factor.max_amount_to_repay(
+                100_000_000,
+                90_000_000,
+                100_000_000,
+                E18_U128,
+                E6_DECIMAL_MULTIPLIER,
+                1_000_000,
+                800_000,
+                100_000,
+            ),
+            Ok(90_909_091)
+        );
+
+        // repaying does not improve collateralization => only close factor applies
+        assert_eq!(
+            close_factor.max_amount_to_repay(
+                100_000_000,
+                90_000_000,
+                100_000_000,
+                E18_U128,
+                E6_DECIMAL_MULTIPLIER,
+                1_000_000,
+                1_000_000,
+                100_000,
+            ),
+            Ok(100_000_000)
+        );
+    }
+
+    #[test]
+    fn max_amount_to_repay_target_collateralization_partial_penalty() {
+        let close_factor = CloseFactor {
+            close_factor_e6: 1_000_000, // 100%
+            target_collateralization_e6: Some(1_100_000),
+        };
+        // half of the penalty is paid
+        // after repaying x: 90 - x * 1.05 * 0.8 = 1.1 * (100 - x) => x = 76.923...
+        assert_eq!(
+            close_factor.max_amount_to_repay(
+                100_000_000,
+                90_000_000,
+                100_000_000,
+                E18_U128,
+                E6_DECIMAL_MULTIPLIER,
+                1_000_000,
+                800_000,
+                50_000,
+            ),
+            Ok(76_923_077)
+        );
+
+        // no penalty is paid
+        // after repaying x: 90 - x * 0.8 = 1.1 * (100 - x) => x = 66.(6)
+        assert_eq!(
+            close_factor.max_amount_to_repay(
+                100_000_000,
+                90_000_000,
+                100_000_000,
+                E18_U128,
+                E6_DECIMAL_MULTIPLIER,
+                1_000_000,
+                800_000,
+                0,
+            ),
+            Ok(66_666_667)
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert_eq!(
+            CloseFactor {
+                close_factor_e6: 0,
+                target_collateralization_e6: None,
+            }
+            .validate(),
+            Err(CloseFactorError::InvalidCloseFactor)
+        );
+        assert_eq!(
+            CloseFactor {
+                close_factor_e6: 500_000,
+                target_collateralization_e6: Some(1_000_000),
+            }
+            .validate(),
+            Err(CloseFactorError::InvalidCloseFactor)
+        );
+        assert_eq!(
+            CloseFactor {
+                close_factor_e6: 500_000,
+                target_collateralization_e6: Some(1_050_000),
+            }
+            .validate(),
+            Ok(())
+        );
+    }
+}
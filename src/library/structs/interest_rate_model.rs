@@ -1,15 +1,48 @@
 // SPDX-License-Identifier: BUSL-1.1
-use pendzl::{math::errors::MathError, traits::Timestamp};
+use pendzl::{
+    math::{
+        errors::MathError,
+        operations::{mul_div, Rounding},
+    },
+    traits::Timestamp,
+};
 
-use crate::math::interest_rate_math::utilization_rate_to_interest_rate_e18;
+use crate::math::{
+    interest_rate_math::adaptive_utilization_rate_to_interest_rate_e18,
+    E6_U128, E6_U32,
+};
 
-/// used to manage interest rate model
+/// Utilization rates (10^6 = 100%) at which the rates of the multi-kink interest rate model are set.
+pub const RATE_KINKS_UR_E6: [u32; 7] = [
+    500_000, 600_000, 700_000, 800_000, 900_000, 950_000, 1_000_000,
+];
+
+/// used to choose and set the interest rate model (strategy) of a reserve.
+#[derive(Debug, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum InterestRateModelParams {
+    /// kinked linear curve with the rate at the target utilization adjusted based on the time-weighted utilization rate.
+    Adaptive(AdaptiveRateParams),
+    /// classic two-slope curve.
+    TwoSlope(TwoSlopeRateParams),
+    /// piecewise linear curve with rates set at `RATE_KINKS_UR_E6`.
+    MultiKink(MultiKinkRateParams),
+    /// the same rate regardless of the utilization.
+    Fixed { rate_e18: u64 },
+    /// kinked linear curve with the rate at the target utilization drifting continuously towards the rate implied by the utilization.
+    Continuous(ContinuousRateParams),
+}
+
+/// used to manage the adaptive interest rate model
 #[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
 #[cfg_attr(
     feature = "std",
     derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
 )]
-pub struct InterestRateModelParams {
+pub struct AdaptiveRateParams {
     pub target_ur_e6: u32,
     pub min_rate_at_target_e18: u64,
     pub max_rate_at_target_e18: u64,
@@ -19,13 +52,130 @@ pub struct InterestRateModelParams {
     pub minimal_time_between_adjustments: u64,
 }
 
+/// Parameters of the two-slope interest rate model.
+/// The rate grows linearly from `base_rate_e18` at 0% utilization to `rate_at_optimal_ur_e18` at `optimal_ur_e6`
+/// and then (usually steeply) to `rate_at_max_ur_e18` at 100% utilization.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct TwoSlopeRateParams {
+    pub optimal_ur_e6: u32,
+    pub base_rate_e18: u64,
+    pub rate_at_optimal_ur_e18: u64,
+    pub rate_at_max_ur_e18: u64,
+}
+
+/// Parameters of the multi-kink interest rate model.
+/// `rates_at_kinks_e18[i]` is the rate at utilization `RATE_KINKS_UR_E6[i]` - at 50%, 60%, 70%, 80%, 90%, 95% and 100%.
+/// Between the kinks (and between 0% and 50%, starting from 0) the rate is interpolated linearly.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct MultiKinkRateParams {
+    pub rates_at_kinks_e18: [u64; 7],
+}
+
+/// Parameters of the continuous interest rate model.
+/// The rate grows linearly from 0 at 0% utilization to the rate at target at `target_ur_e6` and then to `rate_at_max_ur_e18` at 100% utilization.
+/// The rate at target drifts (within [`min_rate_at_target_e18`, `max_rate_at_target_e18`]) with a speed proportional to the distance from the target utilization -
+/// if the utilization stays at 100% (0%) the rate at target moves from the minimal to the maximal one (from the maximal to the minimal one) in `full_adjustment_duration`.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ContinuousRateParams {
+    pub target_ur_e6: u32,
+    pub min_rate_at_target_e18: u64,
+    pub max_rate_at_target_e18: u64,
+
+    pub rate_at_max_ur_e18: u64,
+
+    pub full_adjustment_duration: u64,
+}
+
 /// type used to represent interest rate model
+#[derive(Debug, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum InterestRateModel {
+    Adaptive(AdaptiveInterestRateModel),
+    TwoSlope(TwoSlopeRateParams),
+    MultiKink(MultiKinkRateParams),
+    Fixed { rate_e18: u64 },
+    Continuous(ContinuousInterestRateModel),
+}
+
+impl InterestRateModel {
+    pub fn new(params: InterestRateModelParams, timestamp: Timestamp) -> Self {
+        match params {
+            InterestRateModelParams::Adaptive(params) => {
+                InterestRateModel::Adaptive(AdaptiveInterestRateModel::new(
+                    params, timestamp,
+                ))
+            }
+            InterestRateModelParams::TwoSlope(params) => {
+                InterestRateModel::TwoSlope(params)
+            }
+            InterestRateModelParams::MultiKink(params) => {
+                InterestRateModel::MultiKink(params)
+            }
+            InterestRateModelParams::Fixed { rate_e18 } => {
+                InterestRateModel::Fixed { rate_e18 }
+            }
+            InterestRateModelParams::Continuous(params) => {
+                InterestRateModel::Continuous(ContinuousInterestRateModel::new(
+                    params, timestamp,
+                ))
+            }
+        }
+    }
+
+    /// Drifts the rate at target of the continuous model for the time elapsed since its last update, during which the utilization was `utilization_rate_e6`.
+    ///
+    /// # Returns
+    /// true if the model has changed (and has to be stored), false otherwise.
+    pub fn drift_rate_at_target(
+        &mut self,
+        utilization_rate_e6: u32,
+        timestamp: Timestamp,
+    ) -> Result<bool, MathError> {
+        match self {
+            InterestRateModel::Continuous(model) => {
+                if model.last_update_timestamp >= timestamp {
+                    return Ok(false);
+                }
+                model.drift_rate_at_target(utilization_rate_e6, timestamp)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns the adaptive model if it is the chosen strategy.
+    pub fn as_adaptive_mut(
+        &mut self,
+    ) -> Option<&mut AdaptiveInterestRateModel> {
+        match self {
+            InterestRateModel::Adaptive(model) => Some(model),
+            _ => None,
+        }
+    }
+}
+
+/// type used to represent the adaptive interest rate model
 #[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
 #[cfg_attr(
     feature = "std",
     derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
 )]
-pub struct InterestRateModel {
+pub struct AdaptiveInterestRateModel {
     pub target_ur_e6: u32,
     pub min_rate_at_target_e18: u64,
     pub max_rate_at_target_e18: u64,
@@ -37,9 +187,9 @@ pub struct InterestRateModel {
     pub last_adjustment_timestamp: u64,
 }
 
-impl InterestRateModel {
-    pub fn new(params: InterestRateModelParams, timestamp: Timestamp) -> Self {
-        InterestRateModel {
+impl AdaptiveInterestRateModel {
+    pub fn new(params: AdaptiveRateParams, timestamp: Timestamp) -> Self {
+        AdaptiveInterestRateModel {
             target_ur_e6: params.target_ur_e6,
             min_rate_at_target_e18: params.min_rate_at_target_e18,
             max_rate_at_target_e18: params.max_rate_at_target_e18,
@@ -52,14 +202,16 @@ impl InterestRateModel {
     }
 }
 
-impl InterestRateModel {
+impl AdaptiveInterestRateModel {
     pub fn adjust_rate_at_target(
         &mut self,
         utilization_rate_e6: u32,
         timestamp: Timestamp,
     ) -> Result<u64, MathError> {
-        let current_rate_e18 =
-            utilization_rate_to_interest_rate_e18(utilization_rate_e6, self)?;
+        let current_rate_e18 = adaptive_utilization_rate_to_interest_rate_e18(
+            utilization_rate_e6,
+            self,
+        )?;
 
         if current_rate_e18 < self.min_rate_at_target_e18 {
             self.rate_at_target_ur_e18 = self.min_rate_at_target_e18;
@@ -75,6 +227,97 @@ impl InterestRateModel {
     }
 }
 
+/// type used to represent the continuous interest rate model
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ContinuousInterestRateModel {
+    pub target_ur_e6: u32,
+    pub min_rate_at_target_e18: u64,
+    pub max_rate_at_target_e18: u64,
+
+    pub rate_at_target_ur_e18: u64,
+    pub rate_at_max_ur_e18: u64,
+
+    pub full_adjustment_duration: u64,
+    pub last_update_timestamp: u64,
+}
+
+impl ContinuousInterestRateModel {
+    pub fn new(params: ContinuousRateParams, timestamp: Timestamp) -> Self {
+        ContinuousInterestRateModel {
+            target_ur_e6: params.target_ur_e6,
+            min_rate_at_target_e18: params.min_rate_at_target_e18,
+            max_rate_at_target_e18: params.max_rate_at_target_e18,
+            rate_at_target_ur_e18: params.min_rate_at_target_e18,
+            rate_at_max_ur_e18: params.rate_at_max_ur_e18,
+            full_adjustment_duration: params.full_adjustment_duration,
+            last_update_timestamp: timestamp,
+        }
+    }
+
+    /// Moves the rate at target towards `max_rate_at_target_e18` if `utilization_rate_e6` is above the target and towards `min_rate_at_target_e18` if it is below.
+    /// The move is proportional to the time elapsed since the last update and to the distance from the target utilization
+    /// (normalized, so that it is 100% at 0% and at 100% utilization).
+    pub fn drift_rate_at_target(
+        &mut self,
+        utilization_rate_e6: u32,
+        timestamp: Timestamp,
+    ) -> Result<u64, MathError> {
+        let elapsed = timestamp.saturating_sub(self.last_update_timestamp);
+        self.last_update_timestamp = self.last_update_timestamp.max(timestamp);
+
+        let is_above_target = utilization_rate_e6 > self.target_ur_e6;
+        let distance_e6 = if is_above_target {
+            mul_div(
+                (utilization_rate_e6.min(E6_U32) - self.target_ur_e6) as u128,
+                E6_U128,
+                E6_U32.saturating_sub(self.target_ur_e6) as u128,
+                Rounding::Down,
+            )?
+        } else {
+            mul_div(
+                (self.target_ur_e6 - utilization_rate_e6) as u128,
+                E6_U128,
+                self.target_ur_e6 as u128,
+                Rounding::Down,
+            )?
+        };
+
+        let range = self
+            .max_rate_at_target_e18
+            .saturating_sub(self.min_rate_at_target_e18);
+        let step = u64::try_from(
+            mul_div(
+                range as u128,
+                distance_e6
+                    .checked_mul(elapsed as u128)
+                    .ok_or(MathError::Overflow)?,
+                E6_U128
+                    .checked_mul(self.full_adjustment_duration as u128)
+                    .ok_or(MathError::Overflow)?,
+                Rounding::Down,
+            )?
+            .min(range as u128),
+        )
+        .map_err(|_| MathError::Overflow)?;
+
+        self.rate_at_target_ur_e18 = if is_above_target {
+            self.rate_at_target_ur_e18
+                .saturating_add(step)
+                .min(self.max_rate_at_target_e18)
+        } else {
+            self.rate_at_target_ur_e18
+                .saturating_sub(step)
+                .max(self.min_rate_at_target_e18)
+        };
+
+        Ok(self.rate_at_target_ur_e18)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -82,15 +325,16 @@ mod tests {
 
     const ONE_PERCENT_APR_E18: u64 = 3_170_979;
     const TEN_PERCENT_APR: u64 = 10 * ONE_PERCENT_APR_E18;
-    const INTEREST_RATE_MDOEL: InterestRateModel = InterestRateModel {
-        target_ur_e6: 900_000,                         // 90%
-        min_rate_at_target_e18: ONE_PERCENT_APR_E18,   // 1%,
-        max_rate_at_target_e18: 2 * TEN_PERCENT_APR,   // 10%,
-        rate_at_target_ur_e18: TEN_PERCENT_APR,        // 10%
-        rate_at_max_ur_e18: 100 * ONE_PERCENT_APR_E18, // 100%
-        minimal_time_between_adjustments: 0,
-        last_adjustment_timestamp: 0,
-    };
+    const INTEREST_RATE_MDOEL: AdaptiveInterestRateModel =
+        AdaptiveInterestRateModel {
+            target_ur_e6: 900_000,                         // 90%
+            min_rate_at_target_e18: ONE_PERCENT_APR_E18,   // 1%,
+            max_rate_at_target_e18: 2 * TEN_PERCENT_APR,   // 10%,
+            rate_at_target_ur_e18: TEN_PERCENT_APR,        // 10%
+            rate_at_max_ur_e18: 100 * ONE_PERCENT_APR_E18, // 100%
+            minimal_time_between_adjustments: 0,
+            last_adjustment_timestamp: 0,
+        };
 
     #[test]
     fn adjust_rate_at_target() {
@@ -123,4 +367,36 @@ mod tests {
             Ok(19 * ONE_PERCENT_APR_E18)
         );
     }
+
+    #[test]
+    fn drift_rate_at_target() {
+        let mut model = ContinuousInterestRateModel::new(
+            ContinuousRateParams {
+                target_ur_e6: 800_000,
+                min_rate_at_target_e18: 1_000,
+                max_rate_at_target_e18: 11_000,
+                rate_at_max_ur_e18: 100_000,
+                full_adjustment_duration: 1_000,
+            },
+            0,
+        );
+        assert_eq!(model.rate_at_target_ur_e18, 1_000);
+
+        // 100% utilization for a half of the full adjustment duration
+        assert_eq!(model.drift_rate_at_target(1_000_000, 500), Ok(6_000));
+        // 90% utilization - half of the maximal distance above the target
+        assert_eq!(model.drift_rate_at_target(900_000, 700), Ok(7_000));
+        // at target the rate doesn't move
+        assert_eq!(model.drift_rate_at_target(800_000, 900), Ok(7_000));
+        // 40% utilization - half of the maximal distance below the target
+        assert_eq!(model.drift_rate_at_target(400_000, 1_100), Ok(6_000));
+        // the rate at target is clamped
+        assert_eq!(model.drift_rate_at_target(0, 10_000), Ok(1_000));
+        assert_eq!(model.drift_rate_at_target(1_500_000, 20_000), Ok(11_000));
+
+        // the enum stores the model only if it has drifted
+        let mut model = InterestRateModel::Continuous(model);
+        assert_eq!(model.drift_rate_at_target(0, 20_000), Ok(false));
+        assert_eq!(model.drift_rate_at_target(0, 20_001), Ok(true));
+    }
 }
@@ -0,0 +1,360 @@
+// SPDX-License-Identifier: BUSL-1.1
+//! Layouts of the stored structs from before the fields were added to them.
+//!
+//! The structs are SCALE encoded, so data stored in the old layout can't be decoded as the new one.
+//! Data in the new layouts is stored under the new (versioned) storage keys, while the data in the old
+//! layouts stays readable under the old keys until it is migrated.
+use ink::{prelude::vec::Vec, primitives::AccountId};
+use pendzl::traits::Balance;
+
+use super::{
+    AccountConfig, AccountReserveData, AdaptiveInterestRateModel, AssetsBitmap,
+    Bitmap128, InterestAccrualMode, InterestRateModel, ReserveAbacusTokens,
+    ReserveData, ReserveFees, ReserveIndexes, ReserveIndexesAndFees, TwIndex,
+    DEFAULT_TW_INDEX_SIZE,
+};
+
+/// `AccountConfig` before the stable borrowing and the isolation mode.
+#[derive(Debug, Default, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct AccountConfigV1 {
+    pub deposits: Bitmap128,
+    pub collaterals: Bitmap128,
+    pub borrows: Bitmap128,
+    pub market_rule_id: u32,
+}
+
+impl From<AccountConfigV1> for AccountConfig {
+    fn from(legacy: AccountConfigV1) -> Self {
+        AccountConfig {
+            deposits: AssetsBitmap::from(legacy.deposits),
+            collaterals: AssetsBitmap::from(legacy.collaterals),
+            borrows: AssetsBitmap::from(legacy.borrows),
+            stable_borrows: AssetsBitmap::default(),
+            market_rule_id: legacy.market_rule_id,
+            isolated_collateral: None,
+        }
+    }
+}
+
+/// `AccountReserveData` before the stable borrowing.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct AccountReserveDataV1 {
+    pub deposit: Balance,
+    pub debt: Balance,
+    pub applied_deposit_index_e18: u128,
+    pub applied_debt_index_e18: u128,
+}
+
+impl From<AccountReserveDataV1> for AccountReserveData {
+    fn from(legacy: AccountReserveDataV1) -> Self {
+        AccountReserveData {
+            deposit: legacy.deposit,
+            debt: legacy.debt,
+            applied_deposit_index_e18: legacy.applied_deposit_index_e18,
+            applied_debt_index_e18: legacy.applied_debt_index_e18,
+            stable_debt: 0,
+            stable_rate_e18: 0,
+            applied_stable_timestamp: 0,
+        }
+    }
+}
+
+/// converts the account's reserve datas keeping their order (the index corresponds to the `asset_id`).
+pub fn account_reserve_datas_from_v1(
+    legacy: Vec<Option<AccountReserveDataV1>>,
+) -> Vec<Option<AccountReserveData>> {
+    legacy
+        .into_iter()
+        .map(|data| data.map(AccountReserveData::from))
+        .collect()
+}
+
+/// `ReserveData` before the stable borrowing.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveDataV1 {
+    pub activated: bool,
+    pub frozen: bool,
+    pub total_deposit: Balance,
+    pub current_deposit_rate_e18: u64,
+    pub total_debt: Balance,
+    pub current_debt_rate_e18: u64,
+}
+
+impl From<ReserveDataV1> for ReserveData {
+    fn from(legacy: ReserveDataV1) -> Self {
+        ReserveData {
+            activated: legacy.activated,
+            frozen: legacy.frozen,
+            total_deposit: legacy.total_deposit,
+            current_deposit_rate_e18: legacy.current_deposit_rate_e18,
+            total_debt: legacy.total_debt,
+            current_debt_rate_e18: legacy.current_debt_rate_e18,
+            total_stable_debt: 0,
+            average_stable_rate_e18: 0,
+        }
+    }
+}
+
+/// `ReserveAbacusTokens` before the stable borrowing.
+#[derive(Debug, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveAbacusTokensV1 {
+    pub a_token_address: AccountId,
+    pub v_token_address: AccountId,
+}
+
+impl From<ReserveAbacusTokensV1> for ReserveAbacusTokens {
+    fn from(legacy: ReserveAbacusTokensV1) -> Self {
+        ReserveAbacusTokens::new(
+            &legacy.a_token_address,
+            &legacy.v_token_address,
+        )
+    }
+}
+
+/// `InterestRateModel` before the interest rate strategies - the only model was the adaptive one.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct InterestRateModelV1 {
+    pub target_ur_e6: u32,
+    pub min_rate_at_target_e18: u64,
+    pub max_rate_at_target_e18: u64,
+
+    pub rate_at_target_ur_e18: u64,
+    pub rate_at_max_ur_e18: u64,
+
+    pub minimal_time_between_adjustments: u64,
+    pub last_adjustment_timestamp: u64,
+}
+
+impl From<InterestRateModelV1> for InterestRateModel {
+    fn from(legacy: InterestRateModelV1) -> Self {
+        InterestRateModel::Adaptive(AdaptiveInterestRateModel {
+            target_ur_e6: legacy.target_ur_e6,
+            min_rate_at_target_e18: legacy.min_rate_at_target_e18,
+            max_rate_at_target_e18: legacy.max_rate_at_target_e18,
+            rate_at_target_ur_e18: legacy.rate_at_target_ur_e18,
+            rate_at_max_ur_e18: legacy.rate_at_max_ur_e18,
+            minimal_time_between_adjustments: legacy
+                .minimal_time_between_adjustments,
+            last_adjustment_timestamp: legacy.last_adjustment_timestamp,
+        })
+    }
+}
+
+/// `ReserveIndexesAndFees` before the interest accrual modes - the interest was always accrued as the simple one.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveIndexesAndFeesV1 {
+    pub indexes: ReserveIndexes,
+    pub fees: ReserveFees,
+}
+
+impl From<ReserveIndexesAndFeesV1> for ReserveIndexesAndFees {
+    fn from(legacy: ReserveIndexesAndFeesV1) -> Self {
+        ReserveIndexesAndFees {
+            indexes: legacy.indexes,
+            fees: legacy.fees,
+            accrual_mode: InterestAccrualMode::Simple,
+        }
+    }
+}
+
+/// `TwIndex` before the ring of TW entries was resizable - the ring had the constant size of `DEFAULT_TW_INDEX_SIZE`.
+#[derive(Debug, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct TwIndexV1 {
+    pub value: u32,
+}
+
+impl From<TwIndexV1> for TwIndex {
+    fn from(legacy: TwIndexV1) -> Self {
+        TwIndex {
+            value: legacy.value,
+            size: DEFAULT_TW_INDEX_SIZE,
+            resize_timestamp: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::prelude::vec;
+    use scale::{Decode, DecodeAll, Encode};
+
+    #[test]
+    fn account_config_v1_decodes_and_converts() {
+        let encoded = AccountConfigV1 {
+            deposits: 0b101,
+            collaterals: 0b100,
+            borrows: 0b10,
+            market_rule_id: 3,
+        }
+        .encode();
+
+        // the legacy bytes are not a valid `AccountConfig` - they must be read with the legacy layout
+        assert!(AccountConfig::decode_all(&mut &encoded[..]).is_err());
+
+        let config = AccountConfig::from(
+            AccountConfigV1::decode(&mut &encoded[..]).unwrap(),
+        );
+        assert_eq!(config.deposits.iter().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(config.collaterals.iter().collect::<Vec<_>>(), vec![2]);
+        assert_eq!(config.borrows.iter().collect::<Vec<_>>(), vec![1]);
+        assert!(config.stable_borrows.is_empty());
+        assert_eq!(config.market_rule_id, 3);
+        assert_eq!(config.isolated_collateral, None);
+    }
+
+    #[test]
+    fn account_reserve_datas_v1_keep_order() {
+        let data = AccountReserveDataV1 {
+            deposit: 10,
+            debt: 5,
+            applied_deposit_index_e18: 7,
+            applied_debt_index_e18: 8,
+        };
+        let encoded = vec![None, Some(data)].encode();
+
+        let datas = account_reserve_datas_from_v1(
+            Vec::<Option<AccountReserveDataV1>>::decode(&mut &encoded[..])
+                .unwrap(),
+        );
+        assert_eq!(datas.len(), 2);
+        assert!(datas[0].is_none());
+        let converted = datas[1].unwrap();
+        assert_eq!(converted.deposit, 10);
+        assert_eq!(converted.debt, 5);
+        assert_eq!(converted.applied_deposit_index_e18, 7);
+        assert_eq!(converted.applied_debt_index_e18, 8);
+        assert_eq!(converted.stable_debt, 0);
+        assert_eq!(converted.stable_rate_e18, 0);
+    }
+
+    #[test]
+    fn reserve_data_v1_converts_without_stable_debt() {
+        let encoded = ReserveDataV1 {
+            activated: true,
+            frozen: true,
+            total_deposit: 100,
+            current_deposit_rate_e18: 1,
+            total_debt: 50,
+            current_debt_rate_e18: 2,
+        }
+        .encode();
+
+        let reserve_data = ReserveData::from(
+            ReserveDataV1::decode(&mut &encoded[..]).unwrap(),
+        );
+        assert!(reserve_data.activated);
+        assert!(reserve_data.frozen);
+        assert_eq!(reserve_data.total_deposit, 100);
+        assert_eq!(reserve_data.total_debt, 50);
+        assert_eq!(reserve_data.current_debt_rate_e18, 2);
+        assert_eq!(reserve_data.total_stable_debt, 0);
+        assert_eq!(reserve_data.average_stable_rate_e18, 0);
+    }
+
+    #[test]
+    fn reserve_abacus_tokens_v1_converts_without_s_token() {
+        let tokens = ReserveAbacusTokens::from(ReserveAbacusTokensV1 {
+            a_token_address: AccountId::from([1; 32]),
+            v_token_address: AccountId::from([2; 32]),
+        });
+        assert_eq!(tokens.a_token_address, AccountId::from([1; 32]));
+        assert_eq!(tokens.v_token_address, AccountId::from([2; 32]));
+        assert_eq!(tokens.s_token_address, None);
+    }
+
+    #[test]
+    fn interest_rate_model_v1_converts_to_adaptive() {
+        let encoded = InterestRateModelV1 {
+            target_ur_e6: 900_000,
+            min_rate_at_target_e18: 1,
+            max_rate_at_target_e18: 2,
+            rate_at_target_ur_e18: 3,
+            rate_at_max_ur_e18: 4,
+            minimal_time_between_adjustments: 5,
+            last_adjustment_timestamp: 6,
+        }
+        .encode();
+
+        match InterestRateModel::from(
+            InterestRateModelV1::decode(&mut &encoded[..]).unwrap(),
+        ) {
+            InterestRateModel::Adaptive(model) => {
+                assert_eq!(model.target_ur_e6, 900_000);
+                assert_eq!(model.min_rate_at_target_e18, 1);
+                assert_eq!(model.max_rate_at_target_e18, 2);
+                assert_eq!(model.rate_at_target_ur_e18, 3);
+                assert_eq!(model.rate_at_max_ur_e18, 4);
+                assert_eq!(model.minimal_time_between_adjustments, 5);
+                assert_eq!(model.last_adjustment_timestamp, 6);
+            }
+            _ => panic!("expected the adaptive model"),
+        }
+    }
+
+    #[test]
+    fn reserve_indexes_and_fees_v1_converts_to_simple_accrual() {
+        let encoded = ReserveIndexesAndFeesV1 {
+            indexes: ReserveIndexes::new(&7),
+            fees: ReserveFees {
+                deposit_fee_e6: 1,
+                debt_fee_e6: 2,
+                earned_fee: 3,
+            },
+        }
+        .encode();
+
+        assert!(ReserveIndexesAndFees::decode(&mut &encoded[..]).is_err());
+
+        let converted = ReserveIndexesAndFees::from(
+            ReserveIndexesAndFeesV1::decode(&mut &encoded[..]).unwrap(),
+        );
+        assert_eq!(converted.indexes.update_timestamp, 7);
+        assert_eq!(converted.fees.deposit_fee_e6, 1);
+        assert_eq!(converted.fees.debt_fee_e6, 2);
+        assert_eq!(converted.fees.earned_fee, 3);
+        assert_eq!(converted.accrual_mode, InterestAccrualMode::Simple);
+    }
+
+    #[test]
+    fn tw_index_v1_keeps_value_in_default_ring() {
+        let encoded = TwIndexV1 { value: 5 }.encode();
+
+        assert!(TwIndex::decode(&mut &encoded[..]).is_err());
+
+        let tw_index =
+            TwIndex::from(TwIndexV1::decode(&mut &encoded[..]).unwrap());
+        assert_eq!(tw_index.value, 5);
+        assert_eq!(tw_index.size, DEFAULT_TW_INDEX_SIZE);
+        assert_eq!(tw_index.resize_timestamp, 0);
+        assert_eq!(tw_index.next().value, 6);
+    }
+}
@@ -0,0 +1,120 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::{
+    math::{
+        errors::MathError,
+        operations::{mul_div, Rounding},
+    },
+    traits::Timestamp,
+};
+
+use crate::math::E6_U128;
+
+/// Parameters of the Dutch-auction liquidation mode of a market rule.
+///
+/// In this mode the liquidation penalties (from `AssetRules`) are not paid in full from the start.
+/// They grow linearly from `start_penalty_part_e6` of the penalties at the start of the auction to the full penalties after `duration`.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct LiquidationAuctionParams {
+    /// time after which the full penalties are paid.
+    pub duration: Timestamp,
+    /// part of the penalties paid at the start of the auction. 10^6 = 100%
+    pub start_penalty_part_e6: u128,
+}
+
+/// Liquidation auction of an account.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct LiquidationAuction {
+    /// timestamp at which the auction has started.
+    pub start_timestamp: Timestamp,
+    /// position of the auction in the list of active auctions.
+    pub index: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum LiquidationAuctionError {
+    /// returned if the auction params to be set are invalid (`duration` is 0 or `start_penalty_part_e6` is greater than 100%).
+    InvalidAuctionParams,
+    /// returned if one tries to start an auction for an account whose market rule has no auction mode.
+    AuctionsDisabled,
+    /// returned if one tries to start an auction for an account that already has an active auction.
+    AuctionAlreadyStarted,
+    /// returned if one tries to liquidate (in the auction mode) or to close the auction of an account that has no active auction.
+    AuctionNotStarted,
+}
+
+impl LiquidationAuctionParams {
+    pub fn validate(&self) -> Result<(), LiquidationAuctionError> {
+        if self.duration == 0 || self.start_penalty_part_e6 > E6_U128 {
+            return Err(LiquidationAuctionError::InvalidAuctionParams);
+        }
+        Ok(())
+    }
+
+    /// Returns the part of the penalties (10^6 = 100%) paid at `timestamp` in the auction started at `start_timestamp`.
+    pub fn penalty_part_e6(
+        &self,
+        start_timestamp: &Timestamp,
+        timestamp: &Timestamp,
+    ) -> Result<u128, MathError> {
+        let elapsed = timestamp.saturating_sub(*start_timestamp);
+        if elapsed >= self.duration {
+            return Ok(E6_U128);
+        }
+        let growth = mul_div(
+            E6_U128.saturating_sub(self.start_penalty_part_e6),
+            elapsed as u128,
+            self.duration as u128,
+            Rounding::Down,
+        )?;
+        self.start_penalty_part_e6
+            .checked_add(growth)
+            .ok_or(MathError::Overflow)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn penalty_part_grows_linearly_to_full_penalty() {
+        let params = LiquidationAuctionParams {
+            duration: 1_000,
+            start_penalty_part_e6: 200_000,
+        };
+
+        assert_eq!(params.penalty_part_e6(&5_000, &5_000), Ok(200_000));
+        assert_eq!(params.penalty_part_e6(&5_000, &5_500), Ok(600_000));
+        assert_eq!(params.penalty_part_e6(&5_000, &6_000), Ok(1_000_000));
+        assert_eq!(params.penalty_part_e6(&5_000, &9_000), Ok(1_000_000));
+    }
+
+    #[test]
+    fn invalid_params_are_rejected() {
+        assert_eq!(
+            LiquidationAuctionParams {
+                duration: 0,
+                start_penalty_part_e6: 0,
+            }
+            .validate(),
+            Err(LiquidationAuctionError::InvalidAuctionParams)
+        );
+        assert_eq!(
+            LiquidationAuctionParams {
+                duration: 1,
+                start_penalty_part_e6: 1_000_001,
+            }
+            .validate(),
+            Err(LiquidationAuctionError::InvalidAuctionParams)
+        );
+    }
+}
@@ -1,24 +1,41 @@
 // SPDX-License-Identifier: BUSL-1.1
 mod account_config;
+mod account_health;
 mod account_reserve_data;
+mod actions_pause;
 mod asset_rules;
+mod close_factor;
 mod fee_reduction;
 mod interest_rate_model;
+pub mod legacy;
+mod liquidation_auction;
 mod multi_op;
+mod parameter_bounds;
 mod reserve_abacus_tokens;
 mod reserve_data;
+mod reserve_delisting;
 mod reserve_indexes_and_fees;
+mod reserve_isolation;
 mod reserve_restrictions;
+mod stable_rate_model;
 mod tw;
 
 pub use account_config::*;
+pub use account_health::*;
 pub use account_reserve_data::*;
+pub use actions_pause::*;
 pub use asset_rules::*;
+pub use close_factor::*;
 pub use fee_reduction::*;
 pub use interest_rate_model::*;
+pub use liquidation_auction::*;
 pub use multi_op::*;
+pub use parameter_bounds::*;
 pub use reserve_abacus_tokens::*;
 pub use reserve_data::*;
+pub use reserve_delisting::*;
 pub use reserve_indexes_and_fees::*;
+pub use reserve_isolation::*;
 pub use reserve_restrictions::*;
+pub use stable_rate_model::*;
 pub use tw::*;
@@ -24,6 +24,8 @@ pub enum Operation {
     Withdraw,
     Borrow,
     Repay,
+    BorrowStable,
+    RepayStable,
 }
 
 /// Action to be executed in multi-op
@@ -0,0 +1,267 @@
+// SPDX-License-Identifier: BUSL-1.1
+use crate::math::{E6_U128, E6_U32};
+
+use super::{
+    AdaptiveRateParams, AssetRules, ContinuousRateParams,
+    InterestRateModelParams, MultiKinkRateParams, ReserveAccountLimits,
+    ReserveFees, TwoSlopeRateParams,
+};
+
+/// Errors returned if a managed risk parameter is out of its bounds.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ParameterBoundsError {
+    /// returned if the deposit fee is greater than 100% (10^6).
+    DepositFeeTooHigh,
+    /// returned if the debt fee is greater than 100% (10^6).
+    DebtFeeTooHigh,
+    /// returned if the flash loan fee is greater than 100% (10^6).
+    FlashLoanFeeTooHigh,
+    /// returned if the collateral coefficient is greater than 1 (10^6).
+    CollateralCoefficientTooHigh,
+    /// returned if the borrow coefficient is lower than 1 (10^6).
+    BorrowCoefficientTooLow,
+    /// returned if the liquidation penalty is greater than 100% (10^6).
+    PenaltyTooHigh,
+    /// returned if the target utilization rate is not strictly between 0 and 100% (10^6).
+    TargetUtilizationRateOutOfBounds,
+    /// returned if `min_rate_at_target_e18` is greater than `max_rate_at_target_e18`.
+    MinRateAboveMaxRate,
+    /// returned if `max_rate_at_target_e18` is greater than `rate_at_max_ur_e18`.
+    MaxRateAboveRateAtMaxUtilization,
+    /// returned if the rates of the two-slope or multi-kink interest rate model decrease with the utilization.
+    RatesNotIncreasing,
+    /// returned if the full adjustment duration of the continuous interest rate model is 0.
+    ZeroAdjustmentDuration,
+    /// returned if the maximal debt share of an account is not in (0, 100%] (10^6).
+    DebtShareOutOfBounds,
+}
+
+pub fn validate_flash_loan_fee_e6(
+    flash_loan_fee_e6: u128,
+) -> Result<(), ParameterBoundsError> {
+    if flash_loan_fee_e6 > E6_U128 {
+        return Err(ParameterBoundsError::FlashLoanFeeTooHigh);
+    }
+    Ok(())
+}
+
+impl ReserveFees {
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        if self.deposit_fee_e6 > E6_U32 {
+            return Err(ParameterBoundsError::DepositFeeTooHigh);
+        }
+        if self.debt_fee_e6 > E6_U32 {
+            return Err(ParameterBoundsError::DebtFeeTooHigh);
+        }
+        Ok(())
+    }
+}
+
+impl InterestRateModelParams {
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        match self {
+            InterestRateModelParams::Adaptive(params) => params.validate(),
+            InterestRateModelParams::TwoSlope(params) => params.validate(),
+            InterestRateModelParams::MultiKink(params) => params.validate(),
+            InterestRateModelParams::Fixed { .. } => Ok(()),
+            InterestRateModelParams::Continuous(params) => params.validate(),
+        }
+    }
+}
+
+impl TwoSlopeRateParams {
+    /// verifies that 0 < optimal_ur < 100% and base_rate <= rate_at_optimal_ur <= rate_at_max_ur
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        if self.optimal_ur_e6 == 0 || self.optimal_ur_e6 >= E6_U32 {
+            return Err(ParameterBoundsError::TargetUtilizationRateOutOfBounds);
+        }
+        if self.base_rate_e18 > self.rate_at_optimal_ur_e18
+            || self.rate_at_optimal_ur_e18 > self.rate_at_max_ur_e18
+        {
+            return Err(ParameterBoundsError::RatesNotIncreasing);
+        }
+        Ok(())
+    }
+}
+
+impl MultiKinkRateParams {
+    /// verifies that the rates at kinks are non-decreasing.
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        if self
+            .rates_at_kinks_e18
+            .windows(2)
+            .any(|rates| rates[0] > rates[1])
+        {
+            return Err(ParameterBoundsError::RatesNotIncreasing);
+        }
+        Ok(())
+    }
+}
+
+impl ContinuousRateParams {
+    /// verifies the same bounds as for the adaptive model and that the full adjustment duration is not 0.
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        AdaptiveRateParams {
+            target_ur_e6: self.target_ur_e6,
+            min_rate_at_target_e18: self.min_rate_at_target_e18,
+            max_rate_at_target_e18: self.max_rate_at_target_e18,
+            rate_at_max_ur_e18: self.rate_at_max_ur_e18,
+            minimal_time_between_adjustments: 0,
+        }
+        .validate()?;
+        if self.full_adjustment_duration == 0 {
+            return Err(ParameterBoundsError::ZeroAdjustmentDuration);
+        }
+        Ok(())
+    }
+}
+
+impl AdaptiveRateParams {
+    /// verifies that:
+    /// 1) 0 < target_ur < 100%, so the piecewise rate curve never divides by zero
+    /// 2) min_rate_at_target <= max_rate_at_target <= rate_at_max_ur
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        if self.target_ur_e6 == 0 || self.target_ur_e6 >= E6_U32 {
+            return Err(ParameterBoundsError::TargetUtilizationRateOutOfBounds);
+        }
+        if self.min_rate_at_target_e18 > self.max_rate_at_target_e18 {
+            return Err(ParameterBoundsError::MinRateAboveMaxRate);
+        }
+        if self.max_rate_at_target_e18 > self.rate_at_max_ur_e18 {
+            return Err(ParameterBoundsError::MaxRateAboveRateAtMaxUtilization);
+        }
+        Ok(())
+    }
+}
+
+impl ReserveAccountLimits {
+    pub fn validate(&self) -> Result<(), ParameterBoundsError> {
+        if self.debt_concentration_limit.is_some_and(|limit| {
+            limit.maximal_debt_share_e6 == 0
+                || limit.maximal_debt_share_e6 > E6_U32
+        }) {
+            return Err(ParameterBoundsError::DebtShareOutOfBounds);
+        }
+        Ok(())
+    }
+}
+
+impl AssetRules {
+    /// verifies that the collateral coefficient is not greater than 1, the borrow coefficient is not lower than 1 and the penalty is not greater than 100%.
+    pub fn validate_bounds(&self) -> Result<(), ParameterBoundsError> {
+        if self
+            .collateral_coefficient_e6
+            .is_some_and(|coefficient| coefficient > E6_U128)
+        {
+            return Err(ParameterBoundsError::CollateralCoefficientTooHigh);
+        }
+        if self
+            .borrow_coefficient_e6
+            .is_some_and(|coefficient| coefficient < E6_U128)
+        {
+            return Err(ParameterBoundsError::BorrowCoefficientTooLow);
+        }
+        if self.penalty_e6.is_some_and(|penalty| penalty > E6_U128) {
+            return Err(ParameterBoundsError::PenaltyTooHigh);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INTEREST_RATE_MODEL_PARAMS: AdaptiveRateParams = AdaptiveRateParams {
+        target_ur_e6: 900_000,
+        min_rate_at_target_e18: 1,
+        max_rate_at_target_e18: 10,
+        rate_at_max_ur_e18: 100,
+        minimal_time_between_adjustments: 0,
+    };
+
+    #[test]
+    fn interest_rate_model_params_bounds() {
+        assert_eq!(INTEREST_RATE_MODEL_PARAMS.validate(), Ok(()));
+        assert_eq!(
+            AdaptiveRateParams {
+                target_ur_e6: 0,
+                ..INTEREST_RATE_MODEL_PARAMS
+            }
+            .validate(),
+            Err(ParameterBoundsError::TargetUtilizationRateOutOfBounds)
+        );
+        assert_eq!(
+            AdaptiveRateParams {
+                target_ur_e6: 1_000_000,
+                ..INTEREST_RATE_MODEL_PARAMS
+            }
+            .validate(),
+            Err(ParameterBoundsError::TargetUtilizationRateOutOfBounds)
+        );
+        assert_eq!(
+            AdaptiveRateParams {
+                min_rate_at_target_e18: 11,
+                ..INTEREST_RATE_MODEL_PARAMS
+            }
+            .validate(),
+            Err(ParameterBoundsError::MinRateAboveMaxRate)
+        );
+        assert_eq!(
+            AdaptiveRateParams {
+                rate_at_max_ur_e18: 9,
+                ..INTEREST_RATE_MODEL_PARAMS
+            }
+            .validate(),
+            Err(ParameterBoundsError::MaxRateAboveRateAtMaxUtilization)
+        );
+    }
+
+    #[test]
+    fn multi_kink_rates_bounds() {
+        let mut params = MultiKinkRateParams {
+            rates_at_kinks_e18: [1, 2, 3, 4, 5, 6, 7],
+        };
+        assert_eq!(params.validate(), Ok(()));
+        params.rates_at_kinks_e18[5] = 8;
+        assert_eq!(
+            InterestRateModelParams::MultiKink(params).validate(),
+            Err(ParameterBoundsError::RatesNotIncreasing)
+        );
+    }
+
+    #[test]
+    fn asset_rules_bounds() {
+        let asset_rules = AssetRules {
+            collateral_coefficient_e6: Some(920_000),
+            borrow_coefficient_e6: Some(1_080_000),
+            penalty_e6: Some(40_000),
+        };
+        assert_eq!(asset_rules.validate_bounds(), Ok(()));
+        assert_eq!(
+            AssetRules {
+                collateral_coefficient_e6: Some(1_000_001),
+                ..asset_rules
+            }
+            .validate_bounds(),
+            Err(ParameterBoundsError::CollateralCoefficientTooHigh)
+        );
+        assert_eq!(
+            AssetRules {
+                borrow_coefficient_e6: Some(999_999),
+                ..asset_rules
+            }
+            .validate_bounds(),
+            Err(ParameterBoundsError::BorrowCoefficientTooLow)
+        );
+        assert_eq!(
+            AssetRules {
+                penalty_e6: Some(1_000_001),
+                ..asset_rules
+            }
+            .validate_bounds(),
+            Err(ParameterBoundsError::PenaltyTooHigh)
+        );
+    }
+}
@@ -12,6 +12,8 @@ pub struct ReserveAbacusTokens {
     pub a_token_address: AccountId,
     /// address of the token wrapping debt - vToken
     pub v_token_address: AccountId,
+    /// address of the token wrapping stable debt - sToken. None if stable borrowing was never enabled.
+    pub s_token_address: Option<AccountId>,
 }
 
 impl ReserveAbacusTokens {
@@ -22,6 +24,7 @@ impl ReserveAbacusTokens {
         ReserveAbacusTokens {
             a_token_address: *a_token_address,
             v_token_address: *v_token_address,
+            s_token_address: None,
         }
     }
 }
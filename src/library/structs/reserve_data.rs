@@ -1,12 +1,13 @@
 // SPDX-License-Identifier: BUSL-1.1
 use pendzl::{
     math::{errors::MathError, operations::mul_div},
-    traits::Balance,
+    traits::{Balance, Timestamp},
 };
 
 use crate::math::{
-    e18_mul_e18_div_e18_to_e18_rdown,
-    interest_rate_math::utilization_rate_to_interest_rate_e18, E6_U128, E6_U32,
+    add_to_weighted_rate_e18, e18_mul_e18_div_e18_to_e18_rdown,
+    interest_rate_math::utilization_rate_to_interest_rate_e18,
+    remove_from_weighted_rate_e18, E6_U128, E6_U32,
 };
 
 use super::InterestRateModel;
@@ -32,6 +33,24 @@ pub struct ReserveData {
     pub total_debt: Balance,
     // current interest rate for debt per millisecond. 10^24 = 100%  millisecond Percentage Rate.
     pub current_debt_rate_e18: u64,
+
+    /// total stable debt. It is sum of stable debts with accumulated interests. Total supply of sToken.
+    pub total_stable_debt: Balance,
+    /// average of accounts' stable rates weighted by their stable debts. 10^24 = 100%  millisecond Percentage Rate.
+    pub average_stable_rate_e18: u64,
+}
+
+/// Records why and since when a reserve is frozen.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveFreeze {
+    /// code of the reason of freezing, defined off-chain. 0 means unspecified.
+    pub reason_code: u32,
+    /// timestamp at which the reserve has been frozen.
+    pub since: Timestamp,
 }
 
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -54,6 +73,8 @@ impl Default for ReserveData {
             current_deposit_rate_e18: 0,
             total_debt: 0,
             current_debt_rate_e18: 0,
+            total_stable_debt: 0,
+            average_stable_rate_e18: 0,
         }
     }
 }
@@ -84,7 +105,7 @@ impl ReserveData {
         if self.frozen == freeze {
             return Err(ReserveDataError::AlreadySet);
         }
-        self.activated = freeze;
+        self.frozen = freeze;
         Ok(())
     }
 
@@ -141,6 +162,53 @@ impl ReserveData {
         Ok(())
     }
 
+    /// increases total stable debt by `amount` borrowed with `stable_rate_e18` and updates the average stable rate.
+    pub fn increase_total_stable_debt(
+        &mut self,
+        amount: &Balance,
+        stable_rate_e18: &u64,
+    ) -> Result<(), MathError> {
+        self.average_stable_rate_e18 = add_to_weighted_rate_e18(
+            self.total_stable_debt,
+            self.average_stable_rate_e18,
+            *amount,
+            *stable_rate_e18,
+        )?;
+        self.total_stable_debt = self
+            .total_stable_debt
+            .checked_add(*amount)
+            .ok_or(MathError::Overflow)?;
+        Ok(())
+    }
+
+    /// decreases total stable debt by `amount` borrowed with `stable_rate_e18` and updates the average stable rate.
+    pub fn decrease_total_stable_debt(
+        &mut self,
+        amount: &Balance,
+        stable_rate_e18: &u64,
+    ) -> Result<(), MathError> {
+        self.average_stable_rate_e18 = remove_from_weighted_rate_e18(
+            self.total_stable_debt,
+            self.average_stable_rate_e18,
+            *amount,
+            *stable_rate_e18,
+        )?;
+        // total stable debt can be slightly smaller than the sum of accounts' stable debts due to the rounding of the interest.
+        self.total_stable_debt = self.total_stable_debt.saturating_sub(*amount);
+        Ok(())
+    }
+
+    pub fn add_stable_interest(
+        &mut self,
+        interest: Balance,
+    ) -> Result<Balance, MathError> {
+        self.total_stable_debt = self
+            .total_stable_debt
+            .checked_add(interest)
+            .ok_or(MathError::Overflow)?;
+        Ok(interest)
+    }
+
     pub fn add_interests(
         &mut self,
         interests: (Balance, Balance),
@@ -154,7 +222,10 @@ impl ReserveData {
         if self.total_deposit == 0 {
             return Ok(E6_U32);
         }
-        let total_debt = self.total_debt;
+        let total_debt = self
+            .total_debt
+            .checked_add(self.total_stable_debt)
+            .ok_or(MathError::Overflow)?;
         match u32::try_from(mul_div(
             total_debt,
             E6_U128,
@@ -171,7 +242,7 @@ impl ReserveData {
         interest_rate_model: &InterestRateModel,
     ) -> Result<(), MathError> {
         ink::env::debug_println!("recalculate_current_rates");
-        if self.total_debt == 0 {
+        if self.total_debt == 0 && self.total_stable_debt == 0 {
             self.current_debt_rate_e18 = 0;
             self.current_deposit_rate_e18 = 0;
             return Ok(());
@@ -195,10 +266,58 @@ impl ReserveData {
                 self.total_debt,
                 self.current_debt_rate_e18,
                 self.total_deposit,
-            )?;
+            )?
+            .checked_add(e18_mul_e18_div_e18_to_e18_rdown(
+                self.total_stable_debt,
+                self.average_stable_rate_e18,
+                self.total_deposit,
+            )?)
+            .ok_or(MathError::Overflow)?;
         } else {
             self.current_deposit_rate_e18 = 0;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_is_frozen_changes_only_frozen() {
+        let mut reserve_data = ReserveData::default();
+
+        assert_eq!(reserve_data.set_is_frozen(true), Ok(()));
+        assert!(reserve_data.frozen);
+        assert!(reserve_data.activated);
+        assert_eq!(
+            reserve_data.set_is_frozen(true),
+            Err(ReserveDataError::AlreadySet)
+        );
+
+        assert_eq!(reserve_data.set_is_frozen(false), Ok(()));
+        assert!(!reserve_data.frozen);
+        assert!(reserve_data.activated);
+    }
+
+    #[test]
+    fn frozen_reserve_stays_active_and_inactive_reserve_is_not_frozen() {
+        let mut reserve_data = ReserveData::default();
+
+        reserve_data.set_is_frozen(true).unwrap();
+        assert_eq!(
+            reserve_data.ensure_not_frozen(),
+            Err(ReserveDataError::Frozen)
+        );
+        assert_eq!(reserve_data.ensure_activated(), Ok(()));
+
+        reserve_data.set_is_frozen(false).unwrap();
+        reserve_data.set_is_active(false).unwrap();
+        assert_eq!(
+            reserve_data.ensure_activated(),
+            Err(ReserveDataError::Inactive)
+        );
+        assert_eq!(reserve_data.ensure_not_frozen(), Ok(()));
+    }
+}
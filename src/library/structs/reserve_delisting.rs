@@ -0,0 +1,142 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::{
+    math::{
+        errors::MathError,
+        operations::{mul_div, Rounding},
+    },
+    traits::Timestamp,
+};
+
+/// Stage of the reserve in the delisting workflow.
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum DelistingStage {
+    /// the reserve is not being delisted.
+    Listed,
+    /// borrows are blocked and the collateral coefficients are linearly ramped down to 0.
+    RampDown,
+    /// the asset has no collateral power. Deposits can be settled (withdrawn to their owners) and debts can be liquidated by anyone - without the penalty if the account is collateralized.
+    Settlement,
+    /// the reserve is empty and deactivated.
+    Closed,
+}
+
+/// Stores the state of the delisting of the reserve.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveDelisting {
+    /// timestamp at which the delisting has started (borrows have been blocked).
+    pub start_timestamp: Timestamp,
+    /// duration of the period in which the collateral coefficients are ramped down to 0.
+    pub ramp_duration: Timestamp,
+    /// true if the reserve has been closed.
+    pub is_closed: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ReserveDelistingError {
+    /// returned if the delisting of a reserve that is already being delisted is tried to be started.
+    AlreadyDelisting,
+    /// returned if one tries to deposit, to borrow or to use as collateral an asset that is being delisted.
+    Delisting,
+    /// returned if the action requires the reserve to be in the settlement stage.
+    NotInSettlement,
+    /// returned if one tries to close the reserve that still has deposits or debts.
+    ReserveNotEmpty,
+    /// returned if one tries to activate the reserve that has been closed.
+    Closed,
+}
+
+impl ReserveDelisting {
+    pub fn new(start_timestamp: Timestamp, ramp_duration: Timestamp) -> Self {
+        ReserveDelisting {
+            start_timestamp,
+            ramp_duration,
+            is_closed: false,
+        }
+    }
+
+    pub fn ramp_end_timestamp(&self) -> Timestamp {
+        self.start_timestamp.saturating_add(self.ramp_duration)
+    }
+
+    pub fn stage(&self, timestamp: &Timestamp) -> DelistingStage {
+        if self.is_closed {
+            DelistingStage::Closed
+        } else if *timestamp < self.ramp_end_timestamp() {
+            DelistingStage::RampDown
+        } else {
+            DelistingStage::Settlement
+        }
+    }
+
+    pub fn ensure_in_settlement(
+        &self,
+        timestamp: &Timestamp,
+    ) -> Result<(), ReserveDelistingError> {
+        if self.stage(timestamp) != DelistingStage::Settlement {
+            return Err(ReserveDelistingError::NotInSettlement);
+        }
+        Ok(())
+    }
+
+    /// Returns the `collateral_coefficient_e6` decreased proportionally to the time left until the end of the ramp.
+    pub fn ramped_collateral_coefficient_e6(
+        &self,
+        collateral_coefficient_e6: u128,
+        timestamp: &Timestamp,
+    ) -> Result<u128, MathError> {
+        let ramp_end_timestamp = self.ramp_end_timestamp();
+        if *timestamp >= ramp_end_timestamp {
+            return Ok(0);
+        }
+        let time_left =
+            ramp_end_timestamp - timestamp.max(&self.start_timestamp);
+        mul_div(
+            collateral_coefficient_e6,
+            time_left as u128,
+            self.ramp_duration as u128,
+            Rounding::Down,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn collateral_coefficient_is_ramped_down_linearly() {
+        let delisting = ReserveDelisting::new(1_000, 100);
+
+        assert_eq!(
+            delisting.ramped_collateral_coefficient_e6(800_000, &1_000),
+            Ok(800_000)
+        );
+        assert_eq!(
+            delisting.ramped_collateral_coefficient_e6(800_000, &1_025),
+            Ok(600_000)
+        );
+        assert_eq!(
+            delisting.ramped_collateral_coefficient_e6(800_000, &1_100),
+            Ok(0)
+        );
+        assert_eq!(delisting.stage(&1_099), DelistingStage::RampDown);
+        assert_eq!(delisting.stage(&1_100), DelistingStage::Settlement);
+    }
+
+    #[test]
+    fn zero_ramp_duration_starts_settlement_immediately() {
+        let delisting = ReserveDelisting::new(1_000, 0);
+
+        assert_eq!(delisting.stage(&1_000), DelistingStage::Settlement);
+        assert_eq!(
+            delisting.ramped_collateral_coefficient_e6(800_000, &1_000),
+            Ok(0)
+        );
+    }
+}
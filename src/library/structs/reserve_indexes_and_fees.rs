@@ -1,12 +1,15 @@
 // SPDX-License-Identifier: BUSL-1.1
 use pendzl::{
-    math::errors::MathError,
+    math::{
+        errors::MathError,
+        operations::{mul_div, Rounding},
+    },
     traits::{Balance, Timestamp},
 };
 
 use crate::math::{
-    e18_mul_e0_to_e18, e18_mul_e18_to_e18_rdown, e18_mul_e18_to_e18_rup,
-    E18_U128,
+    compound_interest_multiplier_e18, e18_mul_e0_to_e18,
+    e18_mul_e18_to_e18_rdown, e18_mul_e18_to_e18_rup, E18_U128,
 };
 
 use super::ReserveData;
@@ -22,6 +25,8 @@ pub struct ReserveIndexesAndFees {
     pub indexes: ReserveIndexes,
     /// fee is used to accumulate accounts debt interest. The real rate is the current_borrow_rate * (1+fee). 10^6 =100%
     pub fees: ReserveFees,
+    /// how the interest is accrued in the indexes.
+    pub accrual_mode: InterestAccrualMode,
 }
 
 impl ReserveIndexesAndFees {
@@ -29,6 +34,52 @@ impl ReserveIndexesAndFees {
         ReserveIndexesAndFees {
             indexes: ReserveIndexes::new(timestamp),
             fees: *fees,
+            accrual_mode: InterestAccrualMode::default(),
+        }
+    }
+
+    /// updates the indexes using the reserve's accrual mode.
+    pub fn update_indexes(
+        &mut self,
+        reserve_data: &ReserveData,
+        timestamp: &Timestamp,
+    ) -> Result<(), MathError> {
+        self.indexes
+            .update(reserve_data, timestamp, &self.accrual_mode)
+    }
+}
+
+/// Defines how the interest is accrued in the indexes between their updates.
+#[derive(
+    Debug, Default, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy,
+)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum InterestAccrualMode {
+    /// simple interest over the time since the last update - the index is multiplied by (1 + rate * dt).
+    #[default]
+    Simple,
+    /// continuously compounded interest - the index is multiplied by exp(rate * dt).
+    /// Matters for reserves that are not updated for long periods.
+    Compound,
+}
+
+impl InterestAccrualMode {
+    /// Returns the multiplier (E18 notation) of an index accumulating `rate_e18` over `delta_timestamp`.
+    pub fn index_multiplier_e18(
+        &self,
+        rate_e18: u64,
+        delta_timestamp: u64,
+    ) -> Result<u128, MathError> {
+        match self {
+            InterestAccrualMode::Simple => E18_U128
+                .checked_add(e18_mul_e0_to_e18(rate_e18, delta_timestamp))
+                .ok_or(MathError::Overflow),
+            InterestAccrualMode::Compound => {
+                compound_interest_multiplier_e18(rate_e18, delta_timestamp)
+            }
         }
     }
 }
@@ -68,6 +119,16 @@ impl ReserveFees {
         Ok(())
     }
 
+    /// decreases earned fee by up to `amount`.
+    ///
+    /// # Returns
+    /// the part of `amount` that was covered by the earned fee.
+    pub fn cover_with_earned_fee(&mut self, amount: &Balance) -> Balance {
+        let covered = self.earned_fee.min(*amount);
+        self.earned_fee -= covered;
+        covered
+    }
+
     pub fn take_earned_fee(&mut self) -> Balance {
         let earned_fee = self.earned_fee;
         self.earned_fee = 0;
@@ -103,6 +164,7 @@ impl ReserveIndexes {
         &mut self,
         reserve_data: &ReserveData,
         timestamp: &Timestamp,
+        accrual_mode: &InterestAccrualMode,
     ) -> Result<(), MathError> {
         let delta_timestamp = timestamp
             .checked_sub(self.update_timestamp)
@@ -110,19 +172,15 @@ impl ReserveIndexes {
         if delta_timestamp == 0 {
             return Ok(());
         }
-        let mut deposit_index_multiplier_e18: u128 = E18_U128;
-        let mut debt_index_multiplier_e18: u128 = E18_U128;
-
         // total deposit can be 0 while rate isn't 0 for aabx stable token
         if reserve_data.current_deposit_rate_e18 != 0
             && reserve_data.total_deposit != 0
         {
-            deposit_index_multiplier_e18 = deposit_index_multiplier_e18
-                .checked_add(e18_mul_e0_to_e18(
+            let deposit_index_multiplier_e18 = accrual_mode
+                .index_multiplier_e18(
                     reserve_data.current_deposit_rate_e18,
                     delta_timestamp,
-                ))
-                .ok_or(MathError::Overflow)?;
+                )?;
 
             self.deposit_index_e18 = e18_mul_e18_to_e18_rdown(
                 self.deposit_index_e18,
@@ -134,12 +192,10 @@ impl ReserveIndexes {
         if reserve_data.current_debt_rate_e18 != 0
             && reserve_data.total_debt != 0
         {
-            debt_index_multiplier_e18 = debt_index_multiplier_e18
-                .checked_add(e18_mul_e0_to_e18(
-                    reserve_data.current_debt_rate_e18,
-                    delta_timestamp,
-                ))
-                .ok_or(MathError::Overflow)?;
+            let debt_index_multiplier_e18 = accrual_mode.index_multiplier_e18(
+                reserve_data.current_debt_rate_e18,
+                delta_timestamp,
+            )?;
             ink::env::debug_println!("delta_timestamp: {}", delta_timestamp);
             ink::env::debug_println!(
                 "reserve_data.current_debt_rate_e18: {}",
@@ -158,4 +214,32 @@ impl ReserveIndexes {
         self.update_timestamp = *timestamp;
         Ok(())
     }
+
+    /// decreases the deposit index proportionally to the `loss` that is socialized among all depositors.
+    ///
+    /// * `total_deposit` - total deposit of the reserve before the loss.
+    /// * `loss` - amount to be socialized. Must not be greater than `total_deposit`.
+    ///
+    /// # Note
+    /// The deposit index never reaches 0, so the accounts' deposits can still be accumulated.
+    pub fn socialize_deposit_loss(
+        &mut self,
+        total_deposit: &Balance,
+        loss: &Balance,
+    ) -> Result<(), MathError> {
+        if *loss == 0 {
+            return Ok(());
+        }
+        let remaining_deposit = total_deposit
+            .checked_sub(*loss)
+            .ok_or(MathError::Underflow)?;
+        self.deposit_index_e18 = mul_div(
+            self.deposit_index_e18,
+            remaining_deposit,
+            *total_deposit,
+            Rounding::Down,
+        )?
+        .max(1);
+        Ok(())
+    }
 }
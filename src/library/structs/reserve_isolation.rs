@@ -0,0 +1,177 @@
+// SPDX-License-Identifier: BUSL-1.1
+use super::AccountConfig;
+
+/// Stores the isolation mode parameters of the reserve.
+///
+/// An isolated asset can be used as collateral only on its own and the total debt backed by it is limited by the debt ceiling.
+/// Accounts using an isolated collateral can borrow only the assets that are isolation borrowable.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveIsolation {
+    /// if set the asset is an isolated collateral. The value is the maximal total debt (in USD, 10^8 precision) of all accounts using the asset as collateral.
+    pub debt_ceiling_e8: Option<u128>,
+    /// true if the asset can be borrowed by accounts using an isolated collateral.
+    pub is_isolation_borrowable: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ReserveIsolationError {
+    /// returned if an isolated asset is to be used as collateral together with other collaterals or by an account that has debt, or if other collateral is to be used together with an isolated one.
+    IsolatedCollateralNotSole,
+    /// returned if an account using an isolated collateral tries to borrow an asset that is not isolation borrowable.
+    IsolationBorrowDisabled,
+    /// returned if after the action the total debt backed by an isolated collateral is greater than its debt ceiling.
+    DebtCeilingReached,
+    /// returned if the isolation is turned on for an asset that is already used as collateral by some accounts.
+    AssetUsedAsCollateral,
+}
+
+impl ReserveIsolation {
+    pub fn is_isolated(&self) -> bool {
+        self.debt_ceiling_e8.is_some()
+    }
+
+    pub fn ensure_isolation_borrowable(
+        &self,
+    ) -> Result<(), ReserveIsolationError> {
+        if !self.is_isolation_borrowable {
+            return Err(ReserveIsolationError::IsolationBorrowDisabled);
+        }
+        Ok(())
+    }
+
+    pub fn ensure_debt_ceiling_not_reached(
+        &self,
+        isolated_debt_e8: u128,
+    ) -> Result<(), ReserveIsolationError> {
+        match self.debt_ceiling_e8 {
+            Some(debt_ceiling_e8) if isolated_debt_e8 > debt_ceiling_e8 => {
+                Err(ReserveIsolationError::DebtCeilingReached)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks whether the isolation of the asset can be changed to `new` while `collateral_users` accounts use the asset as collateral.
+    ///
+    /// Turning the isolation on would stop counting the collateral of these accounts, so it is allowed only if there are none.
+    pub fn ensure_can_be_changed_to(
+        &self,
+        new: &ReserveIsolation,
+        collateral_users: u32,
+    ) -> Result<(), ReserveIsolationError> {
+        if !self.is_isolated() && new.is_isolated() && collateral_users > 0 {
+            return Err(ReserveIsolationError::AssetUsedAsCollateral);
+        }
+        Ok(())
+    }
+
+    /// Checks whether the asset (with `asset_id`) can be turned on as collateral by the account with `account_config`.
+    pub fn ensure_can_be_collateral(
+        &self,
+        asset_id: u32,
+        account_config: &AccountConfig,
+    ) -> Result<(), ReserveIsolationError> {
+        if self.is_isolated() {
+            let has_other_collaterals =
+                account_config.collaterals.iter().any(|id| id != asset_id);
+            if has_other_collaterals || !account_config.debts().is_empty() {
+                return Err(ReserveIsolationError::IsolatedCollateralNotSole);
+            }
+        } else if account_config
+            .isolated_collateral
+            .is_some_and(|isolated_id| isolated_id != asset_id)
+        {
+            return Err(ReserveIsolationError::IsolatedCollateralNotSole);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOT_ISOLATED: ReserveIsolation = ReserveIsolation {
+        debt_ceiling_e8: None,
+        is_isolation_borrowable: true,
+    };
+    const ISOLATED: ReserveIsolation = ReserveIsolation {
+        debt_ceiling_e8: Some(1_000),
+        is_isolation_borrowable: false,
+    };
+
+    #[test]
+    fn ensure_debt_ceiling_not_reached() {
+        assert_eq!(ISOLATED.ensure_debt_ceiling_not_reached(1_000), Ok(()));
+        assert_eq!(
+            ISOLATED.ensure_debt_ceiling_not_reached(1_001),
+            Err(ReserveIsolationError::DebtCeilingReached)
+        );
+        assert_eq!(
+            NOT_ISOLATED.ensure_debt_ceiling_not_reached(u128::MAX),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ensure_isolation_borrowable() {
+        assert_eq!(NOT_ISOLATED.ensure_isolation_borrowable(), Ok(()));
+        assert_eq!(
+            ISOLATED.ensure_isolation_borrowable(),
+            Err(ReserveIsolationError::IsolationBorrowDisabled)
+        );
+    }
+
+    #[test]
+    fn ensure_can_be_changed_to() {
+        assert_eq!(NOT_ISOLATED.ensure_can_be_changed_to(&ISOLATED, 0), Ok(()));
+        assert_eq!(
+            NOT_ISOLATED.ensure_can_be_changed_to(&ISOLATED, 1),
+            Err(ReserveIsolationError::AssetUsedAsCollateral)
+        );
+        // the debt ceiling of an isolated asset can be changed and the isolation can be turned off
+        assert_eq!(ISOLATED.ensure_can_be_changed_to(&ISOLATED, 1), Ok(()));
+        assert_eq!(ISOLATED.ensure_can_be_changed_to(&NOT_ISOLATED, 1), Ok(()));
+        assert_eq!(
+            NOT_ISOLATED.ensure_can_be_changed_to(&NOT_ISOLATED, 1),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn ensure_can_be_collateral() {
+        let mut config = AccountConfig::default();
+        assert_eq!(ISOLATED.ensure_can_be_collateral(3, &config), Ok(()));
+
+        config.collaterals.insert(3);
+        assert_eq!(ISOLATED.ensure_can_be_collateral(3, &config), Ok(()));
+
+        config.collaterals.insert(5);
+        assert_eq!(
+            ISOLATED.ensure_can_be_collateral(3, &config),
+            Err(ReserveIsolationError::IsolatedCollateralNotSole)
+        );
+
+        let mut config = AccountConfig::default();
+        config.borrows.insert(5);
+        assert_eq!(
+            ISOLATED.ensure_can_be_collateral(3, &config),
+            Err(ReserveIsolationError::IsolatedCollateralNotSole)
+        );
+
+        let config = AccountConfig {
+            isolated_collateral: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(NOT_ISOLATED.ensure_can_be_collateral(3, &config), Ok(()));
+        assert_eq!(
+            NOT_ISOLATED.ensure_can_be_collateral(5, &config),
+            Err(ReserveIsolationError::IsolatedCollateralNotSole)
+        );
+    }
+}
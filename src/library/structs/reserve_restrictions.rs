@@ -1,5 +1,10 @@
 // SPDX-License-Identifier: BUSL-1.1
-use pendzl::traits::Balance;
+use pendzl::{
+    math::operations::{mul_div, Rounding},
+    traits::Balance,
+};
+
+use crate::math::E6_U128;
 
 use super::{AccountReserveData, ReserveData};
 
@@ -12,7 +17,7 @@ use super::{AccountReserveData, ReserveData};
 pub struct ReserveRestrictions {
     /// maximal allowed total deposit
     pub maximal_total_deposit: Option<Balance>,
-    /// maximal allowad total debt
+    /// maximal allowad total debt (variable and stable)
     pub maximal_total_debt: Option<Balance>,
     /// minimal collateral that can be used by each account.
     /// if account's collateral drops below this value (during withdraw) then it will be automatically turned off (as collateral).
@@ -39,6 +44,34 @@ impl ReserveRestrictions {
     }
 }
 
+/// Stores limits on a single account's deposit and debt in the reserve.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ReserveAccountLimits {
+    /// maximal deposit of an account.
+    pub maximal_account_deposit: Option<Balance>,
+    /// maximal debt (variable and stable) of an account.
+    pub maximal_account_debt: Option<Balance>,
+    /// maximal share of an account in the total debt of the reserve.
+    pub debt_concentration_limit: Option<DebtConcentrationLimit>,
+}
+
+/// Limits the share of an account in the total debt (variable and stable) of the reserve.
+#[derive(Debug, scale::Encode, scale::Decode, Default, Copy, Clone)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct DebtConcentrationLimit {
+    /// maximal share of an account's debt in the total debt. 10^6 = 100%
+    pub maximal_debt_share_e6: u32,
+    /// the limit is enforced only if the total debt is not smaller than this value - so that the first borrowers of a reserve are not blocked.
+    pub minimal_total_debt: Balance,
+}
+
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum ReserveRestrictionsError {
@@ -50,6 +83,12 @@ pub enum ReserveRestrictionsError {
     MinimalDebt,
     /// returned if after the action minimal collaetral restricion would be no satisfied.
     MinimalCollateral,
+    /// returned if after the action the account's deposit is greater than the maximal account deposit.
+    MaxAccountDepositReached,
+    /// returned if after the action the account's debt is greater than the maximal account debt.
+    MaxAccountDebtReached,
+    /// returned if after the action the account's share in the total debt is greater than the maximal debt share.
+    MaxDebtConcentrationReached,
 }
 
 impl ReserveRestrictions {
@@ -73,7 +112,10 @@ impl ReserveRestrictions {
     ) -> Result<(), ReserveRestrictionsError> {
         match self.maximal_total_debt {
             Some(max_total_debt)
-                if reserve_data.total_debt > max_total_debt =>
+                if reserve_data
+                    .total_debt
+                    .saturating_add(reserve_data.total_stable_debt)
+                    > max_total_debt =>
             {
                 Err(ReserveRestrictionsError::MaxDebtReached)
             }
@@ -90,6 +132,11 @@ impl ReserveRestrictions {
         {
             return Err(ReserveRestrictionsError::MinimalDebt);
         }
+        if account_reserve_data.stable_debt != 0
+            && account_reserve_data.stable_debt < self.minimal_debt
+        {
+            return Err(ReserveRestrictionsError::MinimalDebt);
+        }
         Ok(())
     }
 
@@ -103,3 +150,132 @@ impl ReserveRestrictions {
         Ok(())
     }
 }
+
+impl ReserveAccountLimits {
+    pub fn ensure_account_deposit_within_limit(
+        &self,
+        account_reserve_data: &AccountReserveData,
+    ) -> Result<(), ReserveRestrictionsError> {
+        match self.maximal_account_deposit {
+            Some(max_account_deposit)
+                if account_reserve_data.deposit > max_account_deposit =>
+            {
+                Err(ReserveRestrictionsError::MaxAccountDepositReached)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// ensures that the account's debt (variable and stable) doesn't exceed the maximal account debt and the maximal share in the total debt.
+    pub fn ensure_account_debt_within_limits(
+        &self,
+        account_reserve_data: &AccountReserveData,
+        reserve_data: &ReserveData,
+    ) -> Result<(), ReserveRestrictionsError> {
+        let account_debt = account_reserve_data
+            .debt
+            .saturating_add(account_reserve_data.stable_debt);
+        if self
+            .maximal_account_debt
+            .is_some_and(|max_account_debt| account_debt > max_account_debt)
+        {
+            return Err(ReserveRestrictionsError::MaxAccountDebtReached);
+        }
+        if let Some(limit) = self.debt_concentration_limit {
+            let total_debt = reserve_data
+                .total_debt
+                .saturating_add(reserve_data.total_stable_debt);
+            if total_debt >= limit.minimal_total_debt {
+                let max_account_debt = mul_div(
+                    total_debt,
+                    limit.maximal_debt_share_e6 as u128,
+                    E6_U128,
+                    Rounding::Down,
+                )
+                .map_err(|_| {
+                    ReserveRestrictionsError::MaxDebtConcentrationReached
+                })?;
+                if account_debt > max_account_debt {
+                    return Err(
+                        ReserveRestrictionsError::MaxDebtConcentrationReached,
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn debt_concentration_limit() {
+        let account_limits = ReserveAccountLimits {
+            maximal_account_deposit: None,
+            maximal_account_debt: Some(1_000),
+            debt_concentration_limit: Some(DebtConcentrationLimit {
+                maximal_debt_share_e6: 250_000,
+                minimal_total_debt: 2_000,
+            }),
+        };
+        let account_reserve_data = AccountReserveData {
+            debt: 600,
+            stable_debt: 200,
+            ..Default::default()
+        };
+
+        // total debt below the minimal total debt - the share is not limited
+        let reserve_data = ReserveData {
+            total_debt: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            account_limits.ensure_account_debt_within_limits(
+                &account_reserve_data,
+                &reserve_data
+            ),
+            Ok(())
+        );
+
+        // 800 > 25% of 3_000
+        let reserve_data = ReserveData {
+            total_debt: 2_000,
+            total_stable_debt: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            account_limits.ensure_account_debt_within_limits(
+                &account_reserve_data,
+                &reserve_data
+            ),
+            Err(ReserveRestrictionsError::MaxDebtConcentrationReached)
+        );
+
+        // 800 <= 25% of 4_000
+        let reserve_data = ReserveData {
+            total_debt: 4_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            account_limits.ensure_account_debt_within_limits(
+                &account_reserve_data,
+                &reserve_data
+            ),
+            Ok(())
+        );
+
+        let account_reserve_data = AccountReserveData {
+            debt: 1_001,
+            ..Default::default()
+        };
+        assert_eq!(
+            account_limits.ensure_account_debt_within_limits(
+                &account_reserve_data,
+                &reserve_data
+            ),
+            Err(ReserveRestrictionsError::MaxAccountDebtReached)
+        );
+    }
+}
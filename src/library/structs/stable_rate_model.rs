@@ -0,0 +1,40 @@
+// SPDX-License-Identifier: BUSL-1.1
+use pendzl::math::errors::MathError;
+
+use super::ReserveData;
+
+/// Parameters of stable rate borrowing in a reserve.
+#[derive(Debug, Default, scale::Encode, scale::Decode, Clone, Copy)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct StableRateModel {
+    /// premium added to the current variable debt rate to get the stable rate offered for new stable borrows. 10^24 = 100%  millisecond Percentage Rate.
+    pub stable_rate_premium_e18: u64,
+    /// if the difference between the account's stable rate and the currently offered stable rate is greater than this value, the account's rate can be rebalanced. 10^24 = 100%  millisecond Percentage Rate.
+    pub rebalance_threshold_e18: u64,
+}
+
+impl StableRateModel {
+    /// Returns the stable rate offered for new stable borrows.
+    pub fn current_stable_rate_e18(
+        &self,
+        reserve_data: &ReserveData,
+    ) -> Result<u64, MathError> {
+        reserve_data
+            .current_debt_rate_e18
+            .checked_add(self.stable_rate_premium_e18)
+            .ok_or(MathError::Overflow)
+    }
+
+    /// Returns true if `account_stable_rate_e18` diverges from the `current_stable_rate_e18` by more than the rebalance threshold.
+    pub fn can_rebalance(
+        &self,
+        account_stable_rate_e18: u64,
+        current_stable_rate_e18: u64,
+    ) -> bool {
+        account_stable_rate_e18.abs_diff(current_stable_rate_e18)
+            > self.rebalance_threshold_e18
+    }
+}
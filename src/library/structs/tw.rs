@@ -1,24 +1,54 @@
 // SPDX-License-Identifier: BUSL-1.1
 use pendzl::traits::Timestamp;
 
-const TW_INDEX_SIZE: u32 = 60;
+/// Size of the ring of TW entries of a newly registered reserve.
+pub const DEFAULT_TW_INDEX_SIZE: u32 = 60;
 
 #[cfg(build = "release")]
-const TW_INDEX_SIZE: u32 = 3600 * 8;
+pub const DEFAULT_TW_INDEX_SIZE: u32 = 3600 * 8;
+
+/// Minimal size of the ring of TW entries - the latest entry and at least one older entry have to fit.
+pub const MIN_TW_INDEX_SIZE: u32 = 2;
+/// Maximal size of the ring of TW entries.
+pub const MAX_TW_INDEX_SIZE: u32 = 3600 * 24;
 
 #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum TwError {
+    /// returned if the index is outside of the ring of TW entries.
+    InvalidIndex,
+    /// returned if there is no valid entry at the index - it was not written yet or it is left from before the last resize of the ring.
+    EntryNotFound,
+    /// returned if the entry at the index is not distant enough from the latest entry - an earlier index should be used.
+    EntryTooRecent,
+    /// returned if all the entries distant enough from the latest entry have already been overwritten - the ring is too small for the period or was resized recently.
+    EntryOverwritten,
+    /// returned if the size of the ring is out of [`MIN_TW_INDEX_SIZE`, `MAX_TW_INDEX_SIZE`].
+    InvalidSize,
+}
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode, Clone, Copy)]
 #[cfg_attr(
     feature = "std",
     derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
 )]
-/// An index in cyclic group of size `tail`
+/// An index in cyclic group of size `size`
 pub struct TwIndex {
     pub value: u32,
+    /// size of the ring of TW entries.
+    pub size: u32,
+    /// timestamp of the latest entry at the moment of the last resize of the ring.
+    /// Entries older than it are left from before the resize and are no longer valid.
+    pub resize_timestamp: Timestamp,
 }
 
 impl TwIndex {
-    pub fn new() -> Self {
-        TwIndex { value: 0 }
+    pub fn new(size: u32) -> Self {
+        TwIndex {
+            value: 0,
+            size,
+            resize_timestamp: 0,
+        }
     }
 
     pub fn next(&self) -> Self {
@@ -26,9 +56,173 @@ impl TwIndex {
             .value
             .overflowing_add(1)
             .0
-            .checked_rem(TW_INDEX_SIZE)
+            .checked_rem(self.size)
             .unwrap();
-        TwIndex { value: index }
+        TwIndex {
+            value: index,
+            ..*self
+        }
+    }
+
+    /// Returns the index in the ring of the given index `value`.
+    pub fn at(&self, value: u32) -> Result<Self, TwError> {
+        if value >= self.size {
+            return Err(TwError::InvalidIndex);
+        }
+        Ok(TwIndex { value, ..*self })
+    }
+
+    /// Returns the index of the ring resized to `size`.
+    /// The resized ring starts from the `latest_entry` which is to be moved to the index 0 (and removed from its previous index).
+    /// All the other entries become invalid and are overwritten as the ring is filled again.
+    pub fn resized(
+        &self,
+        size: u32,
+        latest_entry: Option<&TwEntry>,
+    ) -> Result<Self, TwError> {
+        if !(MIN_TW_INDEX_SIZE..=MAX_TW_INDEX_SIZE).contains(&size) {
+            return Err(TwError::InvalidSize);
+        }
+        Ok(TwIndex {
+            value: 0,
+            size,
+            resize_timestamp: latest_entry
+                .map(|entry| entry.timestamp)
+                .unwrap_or_default(),
+        })
+    }
+
+    /// Returns the entry if it is valid in the current ring.
+    pub fn valid_entry(
+        &self,
+        entry: Option<TwEntry>,
+    ) -> Result<TwEntry, TwError> {
+        match entry {
+            Some(entry) if entry.timestamp >= self.resize_timestamp => {
+                Ok(entry)
+            }
+            _ => Err(TwError::EntryNotFound),
+        }
+    }
+
+    /// Returns the index of the oldest valid entry and the number of the valid entries in the ring.
+    /// The valid entries are ordered by their timestamps starting from the oldest one.
+    fn valid_range(
+        &self,
+        get_entry: &impl Fn(u32) -> Option<TwEntry>,
+    ) -> (u32, u32) {
+        let next = self.next();
+        if self.valid_entry(get_entry(next.value)).is_ok() {
+            return (next.value, self.size);
+        }
+        // the ring was not filled yet - the entries start at the index 0 (or 1 before the first wrap of a new ring)
+        let first = if self.valid_entry(get_entry(0)).is_ok() {
+            0
+        } else {
+            1
+        };
+        (first, self.value.saturating_add(1).saturating_sub(first))
+    }
+
+    /// Returns the error explaining why no entry distant by at least `period` from the `latest_entry` could be found.
+    fn not_distant_enough_error(
+        &self,
+        period: u64,
+        latest_entry: &TwEntry,
+        get_entry: &impl Fn(u32) -> Option<TwEntry>,
+    ) -> TwError {
+        let (oldest, _) = self.valid_range(get_entry);
+        let oldest_timestamp = self
+            .valid_entry(get_entry(oldest))
+            .map(|entry| entry.timestamp)
+            .unwrap_or(self.resize_timestamp);
+        if latest_entry.timestamp.saturating_sub(oldest_timestamp) < period
+            && (self.resize_timestamp != 0 || oldest == self.next().value)
+        {
+            return TwError::EntryOverwritten;
+        }
+        TwError::EntryTooRecent
+    }
+
+    /// Returns the most recent entry distant by at least `period` from the `latest_entry` (the one at `self`).
+    /// Starts at `guessed_index` and walks towards the latest entry, never past it - the entries following it are the oldest ones.
+    pub fn walk_to_distant_entry(
+        &self,
+        period: u64,
+        guessed_index: u32,
+        latest_entry: &TwEntry,
+        get_entry: &impl Fn(u32) -> Option<TwEntry>,
+    ) -> Result<TwEntry, TwError> {
+        let is_distant = |entry: &TwEntry| {
+            latest_entry.timestamp.saturating_sub(entry.timestamp) >= period
+        };
+        let mut curr_index = self.at(guessed_index)?;
+        let mut curr_entry = self.valid_entry(get_entry(curr_index.value))?;
+        if curr_index.value == self.value || !is_distant(&curr_entry) {
+            return Err(self.not_distant_enough_error(
+                period,
+                latest_entry,
+                get_entry,
+            ));
+        }
+        loop {
+            let next_index = curr_index.next();
+            if next_index.value == self.value {
+                break;
+            }
+            let next_entry = self.valid_entry(get_entry(next_index.value))?;
+            if !is_distant(&next_entry) {
+                break;
+            }
+            curr_index = next_index;
+            curr_entry = next_entry;
+        }
+        Ok(curr_entry)
+    }
+
+    /// Returns the most recent entry distant by at least `period` from the `latest_entry` (the one at `self`).
+    /// The valid entries are ordered by their timestamps (modulo the wrap-around of the ring) so the entry is binary searched.
+    pub fn search_distant_entry(
+        &self,
+        period: u64,
+        latest_entry: &TwEntry,
+        get_entry: &impl Fn(u32) -> Option<TwEntry>,
+    ) -> Result<TwEntry, TwError> {
+        let is_distant = |entry: &TwEntry| {
+            latest_entry.timestamp.saturating_sub(entry.timestamp) >= period
+        };
+        let (oldest, len) = self.valid_range(get_entry);
+        let entry_at = |position: u32| {
+            self.valid_entry(get_entry(
+                oldest.saturating_add(position).checked_rem(self.size)?,
+            ))
+            .ok()
+        };
+        // the latest entry is at the position `len - 1` and is never returned
+        let mut low_entry = match entry_at(0) {
+            Some(entry) if len >= 2 && is_distant(&entry) => entry,
+            _ => {
+                return Err(self.not_distant_enough_error(
+                    period,
+                    latest_entry,
+                    get_entry,
+                ))
+            }
+        };
+        // invariant: the entry at `low` is distant enough, the entry at `high` is not
+        let mut low: u32 = 0;
+        let mut high: u32 = len.saturating_sub(1);
+        while high.saturating_sub(low) > 1 {
+            let mid = low.saturating_add(high.saturating_sub(low) / 2);
+            let mid_entry = entry_at(mid).ok_or(TwError::EntryNotFound)?;
+            if is_distant(&mid_entry) {
+                low = mid;
+                low_entry = mid_entry;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low_entry)
     }
 }
 
@@ -44,3 +238,229 @@ pub struct TwEntry {
     pub timestamp: Timestamp,
     pub accumulator: u64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_wraps_at_size() {
+        let index = TwIndex::new(3);
+        assert_eq!(index.next().value, 1);
+        assert_eq!(index.next().next().value, 2);
+        assert_eq!(index.next().next().next().value, 0);
+        assert_eq!(index.at(3), Err(TwError::InvalidIndex));
+    }
+
+    #[test]
+    fn resize_invalidates_older_entries() {
+        let latest = TwEntry {
+            timestamp: 100,
+            accumulator: 7,
+        };
+        let index = TwIndex::new(60).at(42).unwrap();
+        assert_eq!(index.resized(1, Some(&latest)), Err(TwError::InvalidSize));
+        assert_eq!(
+            index.resized(MAX_TW_INDEX_SIZE + 1, Some(&latest)),
+            Err(TwError::InvalidSize)
+        );
+
+        let resized = index.resized(10, Some(&latest)).unwrap();
+        assert_eq!(resized.value, 0);
+        assert_eq!(resized.size, 10);
+        assert_eq!(resized.valid_entry(Some(latest)), Ok(latest));
+        assert_eq!(
+            resized.valid_entry(Some(TwEntry {
+                timestamp: 99,
+                accumulator: 5,
+            })),
+            Err(TwError::EntryNotFound)
+        );
+        assert_eq!(resized.valid_entry(None), Err(TwError::EntryNotFound));
+    }
+
+    /// Ring of TW entries mimicking the storage of the lending pool.
+    struct Ring {
+        index: TwIndex,
+        entries: Vec<Option<TwEntry>>,
+    }
+
+    impl Ring {
+        fn new(size: u32) -> Self {
+            Ring {
+                index: TwIndex::new(size),
+                entries: vec![None; MAX_TEST_SIZE],
+            }
+        }
+
+        fn push(&mut self, timestamp: Timestamp) {
+            self.index = self.index.next();
+            self.entries[self.index.value as usize] = Some(TwEntry {
+                timestamp,
+                accumulator: timestamp,
+            });
+        }
+
+        fn resize(&mut self, size: u32) {
+            let latest = self.entries[self.index.value as usize].take();
+            self.index = self.index.resized(size, latest.as_ref()).unwrap();
+            self.entries[0] = latest;
+        }
+
+        fn latest(&self) -> TwEntry {
+            self.entries[self.index.value as usize].unwrap()
+        }
+
+        fn get_entry(&self) -> impl Fn(u32) -> Option<TwEntry> + '_ {
+            |index| self.entries[index as usize]
+        }
+
+        /// the most recent valid entry distant enough, found by checking all the entries.
+        fn expected(&self, period: u64) -> Option<TwEntry> {
+            let latest = self.latest();
+            (0..self.index.size)
+                .filter(|index| *index != self.index.value)
+                .filter_map(|index| {
+                    self.index.valid_entry(self.entries[index as usize]).ok()
+                })
+                .filter(|entry| latest.timestamp - entry.timestamp >= period)
+                .max_by_key(|entry| entry.timestamp)
+        }
+
+        fn check_search(&self) {
+            let latest = self.latest();
+            for period in 0..=latest.timestamp + 10 {
+                let found = self.index.search_distant_entry(
+                    period,
+                    &latest,
+                    &self.get_entry(),
+                );
+                assert_eq!(found.ok(), self.expected(period), "{}", period);
+            }
+        }
+    }
+
+    const MAX_TEST_SIZE: usize = 16;
+
+    #[test]
+    fn search_finds_the_most_recent_distant_entry() {
+        let mut ring = Ring::new(8);
+        let mut timestamp = 0;
+        for _ in 0..30 {
+            timestamp += 1 + timestamp % 7;
+            ring.push(timestamp);
+            ring.check_search();
+        }
+        ring.resize(5);
+        ring.check_search();
+        for _ in 0..7 {
+            timestamp += 3;
+            ring.push(timestamp);
+            ring.check_search();
+        }
+        ring.resize(MAX_TEST_SIZE as u32);
+        for _ in 0..20 {
+            timestamp += 2;
+            ring.push(timestamp);
+            ring.check_search();
+        }
+    }
+
+    #[test]
+    fn walk_agrees_with_search() {
+        let mut ring = Ring::new(8);
+        for timestamp in 1..=13 {
+            ring.push(timestamp * 10);
+        }
+        let latest = ring.latest();
+        // entries 60..=130 are in the ring, the oldest one at the index 6
+        for period in [10, 25, 70] {
+            let searched = ring
+                .index
+                .search_distant_entry(period, &latest, &ring.get_entry())
+                .unwrap();
+            for guessed_index in [6, 7, 0] {
+                let walked = ring.index.walk_to_distant_entry(
+                    period,
+                    guessed_index,
+                    &latest,
+                    &ring.get_entry(),
+                );
+                if ring.entries[guessed_index as usize].unwrap().timestamp
+                    <= searched.timestamp
+                {
+                    assert_eq!(walked, Ok(searched));
+                } else {
+                    assert_eq!(walked, Err(TwError::EntryTooRecent));
+                }
+            }
+        }
+        // walking never passes the latest entry
+        assert_eq!(
+            ring.index.walk_to_distant_entry(
+                0,
+                ring.index.value,
+                &latest,
+                &ring.get_entry()
+            ),
+            Err(TwError::EntryTooRecent)
+        );
+    }
+
+    #[test]
+    fn not_distant_enough_errors() {
+        let mut ring = Ring::new(4);
+        ring.push(10);
+        ring.push(20);
+        // not filled ring - nothing was overwritten yet
+        assert_eq!(
+            ring.index.search_distant_entry(
+                15,
+                &ring.latest(),
+                &ring.get_entry()
+            ),
+            Err(TwError::EntryTooRecent)
+        );
+        for timestamp in [30, 40, 50] {
+            ring.push(timestamp);
+        }
+        // entries 20..=50 are in the ring, 10 was overwritten
+        assert_eq!(
+            ring.index.search_distant_entry(
+                35,
+                &ring.latest(),
+                &ring.get_entry()
+            ),
+            Err(TwError::EntryOverwritten)
+        );
+        assert_eq!(
+            ring.index.walk_to_distant_entry(
+                20,
+                ring.index.value,
+                &ring.latest(),
+                &ring.get_entry()
+            ),
+            Err(TwError::EntryTooRecent)
+        );
+        ring.resize(8);
+        ring.push(60);
+        // the entries from before the resize are discarded
+        assert_eq!(
+            ring.index.search_distant_entry(
+                15,
+                &ring.latest(),
+                &ring.get_entry()
+            ),
+            Err(TwError::EntryOverwritten)
+        );
+        assert_eq!(
+            ring.index.walk_to_distant_entry(
+                15,
+                2,
+                &ring.latest(),
+                &ring.get_entry()
+            ),
+            Err(TwError::EntryNotFound)
+        );
+    }
+}
@@ -0,0 +1,121 @@
+import { E18bn } from '@c-forge/polkahat-network-helpers';
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+const E8 = new BN(10).pow(new BN(8));
+const U128_MAX = new BN(2).pow(new BN(128)).subn(1);
+// 1 WETH * 1500$ * 0.8 (WETH collateral coefficient) / 1.03 (DAI borrow coefficient) = 1165.048543 DAI
+const MAX_BORROWABLE_DAI = new BN('1165048543');
+// (1200$ - 1000 DAI * 1.03) / 0.8 / 1500$ = 0.141(6) WETH
+const MAX_WITHDRAWABLE_WETH = new BN('141666666666666666');
+
+makeSuite('LendingPool account health', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let collateralWethAmount: BN;
+
+  beforeEach('setup Env, supply DAI and deposit 1 WETH as collateral', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    [supplier, borrower] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit);
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit);
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+    await wethContract.tx.mint(borrower.address, collateralWethAmount);
+    await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+  });
+
+  it('the account without debt has the maximal health factor and can withdraw the whole deposit', async () => {
+    const accountHealth = (await lendingPool.query.viewAccountHealth(borrower.address)).value.ok!.ok!;
+    expect.soft(accountHealth.totalCollateralValueE8.toString()).to.equal(E8.muln(1500).toString());
+    expect.soft(accountHealth.collateralPowerE6.toString()).to.equal('1200000000');
+    expect.soft(accountHealth.totalDebtValueE8.toString()).to.equal('0');
+    expect.soft(accountHealth.debtPowerE6.toString()).to.equal('0');
+    expect.soft(accountHealth.healthFactorE18.toString()).to.equal(U128_MAX.toString());
+
+    const maxWithdrawable = (await lendingPool.query.viewMaxWithdrawable(borrower.address, wethContract.address)).value.ok!.ok!;
+    expect.soft(maxWithdrawable.toString()).to.equal(collateralWethAmount.toString());
+    expect.flushSoft();
+  });
+
+  it('the account can borrow exactly the max borrowable amount of DAI', async () => {
+    const maxBorrowable = (await lendingPool.query.viewMaxBorrowable(borrower.address, daiContract.address)).value.ok!.ok!;
+    expect(maxBorrowable.toString()).to.equal(MAX_BORROWABLE_DAI.toString());
+
+    const queryRes = (await lendingPool.withSigner(borrower).query.borrow(daiContract.address, borrower.address, maxBorrowable.addn(1), [])).value
+      .ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.InsufficientCollateral());
+    await expect(lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, maxBorrowable, [])).to.eventually.be.fulfilled;
+  });
+
+  describe('The borrower borrows 1000 DAI. Then ...', () => {
+    beforeEach('borrow DAI', async () => {
+      const debtDaiAmount = await convertToCurrencyDecimals(daiContract, 1000);
+      await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, []);
+    });
+
+    it('the health factor is the collateral power divided by the debt power', async () => {
+      const accountHealth = (await lendingPool.query.viewAccountHealth(borrower.address)).value.ok!.ok!;
+      // 1200$ / (1000 DAI * 1.03) = 1.165048543...
+      expect.soft(accountHealth.totalCollateralValueE8.toString()).to.equal(E8.muln(1500).toString());
+      expect.soft(accountHealth.totalDebtValueE8.gte(E8.muln(1000))).to.be.true;
+      expect.soft(accountHealth.debtPowerE6.gte(new BN('1030000000'))).to.be.true;
+      expect.soft(accountHealth.healthFactorE18.lte(new BN('1165048543689320388'))).to.be.true;
+      expect.soft(accountHealth.healthFactorE18.gt(new BN('1165000000000000000'))).to.be.true;
+      expect.flushSoft();
+    });
+
+    it('the account can withdraw at most the WETH not needed to back the debt', async () => {
+      const maxWithdrawable = (await lendingPool.query.viewMaxWithdrawable(borrower.address, wethContract.address)).value.ok!.ok!;
+      expect(maxWithdrawable.lte(MAX_WITHDRAWABLE_WETH)).to.be.true;
+      expect(maxWithdrawable.gt(E18bn.muln(14).divn(100))).to.be.true;
+
+      const queryRes = (
+        await lendingPool.withSigner(borrower).query.withdraw(wethContract.address, borrower.address, MAX_WITHDRAWABLE_WETH.addn(1), [])
+      ).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.InsufficientCollateral());
+      const amount = E18bn.muln(14).divn(100);
+      await expect(lendingPool.withSigner(borrower).tx.withdraw(wethContract.address, borrower.address, amount, [])).to.eventually.be.fulfilled;
+    });
+
+    it('the health factor falls below 1 and nothing can be borrowed or withdrawn once WETH price drops to 1280$', async () => {
+      await oracle.tx.setPrice('WETH/USD', toE18String(1280));
+
+      const accountHealth = (await lendingPool.query.viewAccountHealth(borrower.address)).value.ok!.ok!;
+      expect.soft(accountHealth.collateralPowerE6.toString()).to.equal('1024000000');
+      expect.soft(accountHealth.healthFactorE18.lt(E18bn)).to.be.true;
+
+      const maxBorrowable = (await lendingPool.query.viewMaxBorrowable(borrower.address, daiContract.address)).value.ok!.ok!;
+      const maxWithdrawable = (await lendingPool.query.viewMaxWithdrawable(borrower.address, wethContract.address)).value.ok!.ok!;
+      expect.soft(maxBorrowable.toString()).to.equal('0');
+      expect.soft(maxWithdrawable.toString()).to.equal('0');
+      expect.flushSoft();
+    });
+  });
+});
@@ -0,0 +1,113 @@
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { ReserveRestrictionsError } from 'typechain/types-arguments/lending_pool';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+makeSuite('LendingPool per-account limits', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let owner: KeyringPair;
+  let supplier: KeyringPair;
+  let otherSupplier: KeyringPair;
+  let borrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+
+  const mint = async (token: PSP22Emitable, account: KeyringPair, amount: BN) => {
+    await token.tx.mint(account.address, amount);
+    await token.withSigner(account).tx.approve(lendingPool.address, amount);
+  };
+
+  beforeEach('setup Env and deposit 10 WETH as the borrower collateral', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    [supplier, otherSupplier, borrower] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 10);
+    await mint(wethContract, borrower, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+  });
+
+  describe('The maximal account deposit of DAI is set to 5000 DAI and the maximal account debt to 500 DAI. Then ...', () => {
+    let maxAccountDeposit: BN;
+    let maxAccountDebt: BN;
+    beforeEach('set the DAI account limits', async () => {
+      maxAccountDeposit = await convertToCurrencyDecimals(daiContract, 5000);
+      maxAccountDebt = await convertToCurrencyDecimals(daiContract, 500);
+      await lendingPool.withSigner(owner).tx.setReserveAccountLimits(daiContract.address, {
+        maximalAccountDeposit: maxAccountDeposit,
+        maximalAccountDebt: maxAccountDebt,
+        debtConcentrationLimit: null,
+      });
+      await mint(daiContract, supplier, maxAccountDeposit.muln(2));
+      await mint(daiContract, otherSupplier, maxAccountDeposit);
+    });
+
+    it('an account can deposit up to the maximal account deposit while other accounts can still deposit', async () => {
+      await expect(lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, maxAccountDeposit, [])).to.eventually.be
+        .fulfilled;
+
+      const queryRes = (await lendingPool.withSigner(supplier).query.deposit(daiContract.address, supplier.address, new BN(1), [])).value.ok;
+      expect(queryRes).to.have.deep.property(
+        'err',
+        LendingPoolErrorBuilder.ReserveRestrictionsError(ReserveRestrictionsError.maxAccountDepositReached),
+      );
+
+      await expect(
+        lendingPool.withSigner(otherSupplier).tx.deposit(daiContract.address, otherSupplier.address, maxAccountDeposit, []),
+      ).to.eventually.be.fulfilled;
+    });
+
+    it('an account can borrow up to the maximal account debt', async () => {
+      await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, maxAccountDeposit, []);
+
+      const queryRes = (await lendingPool.withSigner(borrower).query.borrow(daiContract.address, borrower.address, maxAccountDebt.addn(1), [])).value
+        .ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ReserveRestrictionsError(ReserveRestrictionsError.maxAccountDebtReached));
+      await expect(lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, maxAccountDebt, [])).to.eventually.be.fulfilled;
+    });
+  });
+
+  describe('The debt concentration limit of DAI is set to 50% once the total debt reaches 100 DAI. Then ...', () => {
+    beforeEach('set the DAI debt concentration limit and supply DAI', async () => {
+      await lendingPool.withSigner(owner).tx.setReserveAccountLimits(daiContract.address, {
+        maximalAccountDeposit: null,
+        maximalAccountDebt: null,
+        debtConcentrationLimit: { maximalDebtShareE6: 500_000, minimalTotalDebt: await convertToCurrencyDecimals(daiContract, 100) },
+      });
+      const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+      await mint(daiContract, supplier, totalDaiDeposit);
+      await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+    });
+
+    it('the sole borrower can borrow only below the minimal total debt', async () => {
+      const atMinimalTotalDebt = await convertToCurrencyDecimals(daiContract, 100);
+      const queryRes = (await lendingPool.withSigner(borrower).query.borrow(daiContract.address, borrower.address, atMinimalTotalDebt, [])).value.ok;
+      expect(queryRes).to.have.deep.property(
+        'err',
+        LendingPoolErrorBuilder.ReserveRestrictionsError(ReserveRestrictionsError.maxDebtConcentrationReached),
+      );
+
+      const belowMinimalTotalDebt = await convertToCurrencyDecimals(daiContract, 99);
+      await expect(lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, belowMinimalTotalDebt, [])).to.eventually.be
+        .fulfilled;
+    });
+  });
+});
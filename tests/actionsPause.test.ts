@@ -0,0 +1,94 @@
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { PausableAction } from 'typechain/types-arguments/lending_pool';
+import { ActionsPauseErrorBuilder, LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+makeSuite('LendingPool actions pause', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let owner: KeyringPair;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let debtDaiAmount: BN;
+
+  beforeEach('setup Env and make a DAI borrow', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    [supplier, borrower] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit.muln(2));
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit.muln(2));
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+    await wethContract.tx.mint(borrower.address, collateralWethAmount);
+    await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+
+    debtDaiAmount = await convertToCurrencyDecimals(daiContract, 100);
+    await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, []);
+    await daiContract.withSigner(borrower).tx.approve(lendingPool.address, debtDaiAmount);
+  });
+
+  describe('The EMERGENCY_ADMIN pauses borrows of DAI. Then ...', () => {
+    beforeEach('pause DAI borrows', async () => {
+      await lendingPool.withSigner(owner).tx.setReserveActionPaused(daiContract.address, PausableAction.borrow, true);
+    });
+
+    it('DAI can not be borrowed', async () => {
+      const queryRes = (await lendingPool.withSigner(borrower).query.borrow(daiContract.address, borrower.address, debtDaiAmount, [])).value.ok;
+      expect(queryRes).to.have.deep.property(
+        'err',
+        LendingPoolErrorBuilder.ActionsPauseError(ActionsPauseErrorBuilder.ActionPaused(PausableAction.borrow)),
+      );
+    });
+
+    it('DAI can still be repaid and deposited', async () => {
+      const repayAmount = debtDaiAmount.divn(2);
+      await expect(lendingPool.withSigner(borrower).tx.repay(daiContract.address, borrower.address, repayAmount, [])).to.eventually.be.fulfilled;
+      await expect(lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, debtDaiAmount, [])).to.eventually.be.fulfilled;
+    });
+
+    it('DAI can be borrowed again once the borrows are unpaused', async () => {
+      await lendingPool.withSigner(owner).tx.setReserveActionPaused(daiContract.address, PausableAction.borrow, false);
+      await expect(lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, [])).to.eventually.be.fulfilled;
+    });
+  });
+
+  describe('The EMERGENCY_ADMIN pauses deposits globally. Then ...', () => {
+    beforeEach('pause deposits', async () => {
+      await lendingPool.withSigner(owner).tx.setGlobalActionPaused(PausableAction.deposit, true);
+    });
+
+    it('no asset can be deposited while DAI can still be repaid', async () => {
+      const queryRes = (await lendingPool.withSigner(supplier).query.deposit(daiContract.address, supplier.address, debtDaiAmount, [])).value.ok;
+      expect(queryRes).to.have.deep.property(
+        'err',
+        LendingPoolErrorBuilder.ActionsPauseError(ActionsPauseErrorBuilder.ActionPaused(PausableAction.deposit)),
+      );
+
+      const repayAmount = debtDaiAmount.divn(2);
+      await expect(lendingPool.withSigner(borrower).tx.repay(daiContract.address, borrower.address, repayAmount, [])).to.eventually.be.fulfilled;
+    });
+  });
+});
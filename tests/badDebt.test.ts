@@ -0,0 +1,147 @@
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv, TestEnvReserves } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+makeSuite('LendingPool bad debt settlement', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let reserves: TestEnvReserves;
+  let accounts: KeyringPair[];
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+  let liquidator: KeyringPair;
+
+  beforeEach('setup Env', () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    reserves = testEnv.reserves;
+    oracle = testEnv.oracle;
+    accounts = testEnv.accounts;
+    supplier = accounts[0];
+    borrower = accounts[1];
+    liquidator = accounts[2];
+  });
+
+  describe('Borrower deposits WETH (1WETH = 1500$) as collateral, LINK (1LINK = 10$) not as collateral and borrows (variable) 1000 DAI (1DAI = 1$). Then ...', () => {
+    let daiContract: PSP22Emitable;
+    let wethContract: PSP22Emitable;
+    let linkContract: PSP22Emitable;
+
+    let totalDaiDeposit: BN;
+    let collateralWethAmount: BN;
+    let debtDaiAmount: BN;
+    let depositLinkAmount: BN;
+
+    beforeEach('make deposits and make borrow', async () => {
+      daiContract = reserves['DAI'].underlying;
+      wethContract = reserves['WETH'].underlying;
+      linkContract = reserves['LINK'].underlying;
+      await oracle.tx.setPrice('DAI/USD', toE18String(1));
+      await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+      await oracle.tx.setPrice('LINK/USD', toE18String(10));
+
+      totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+      await daiContract.tx.mint(supplier.address, totalDaiDeposit);
+      await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit);
+      await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+      collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+      await wethContract.tx.mint(borrower.address, collateralWethAmount);
+      await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+      await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+      await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+
+      depositLinkAmount = await convertToCurrencyDecimals(linkContract, 10);
+      await linkContract.tx.mint(borrower.address, depositLinkAmount);
+      await linkContract.withSigner(borrower).tx.approve(lendingPool.address, depositLinkAmount);
+      await lendingPool.withSigner(borrower).tx.deposit(linkContract.address, borrower.address, depositLinkAmount, []);
+
+      debtDaiAmount = await convertToCurrencyDecimals(daiContract, 1000);
+      await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, []);
+
+      await daiContract.tx.mint(liquidator.address, debtDaiAmount);
+      await daiContract.withSigner(liquidator).tx.approve(lendingPool.address, debtDaiAmount);
+    });
+
+    it('the debt can not be settled as the account has collateral', async () => {
+      const queryRes = (await lendingPool.withSigner(liquidator).query.settleBadDebt(borrower.address)).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.NoBadDebt());
+
+      const unbacked = (await lendingPool.query.viewAccountsWithUnbackedDebt(0, 100)).value.ok!;
+      expect(unbacked).not.to.include(borrower.address);
+    });
+
+    describe('WETH price drops to 100$ and the whole WETH collateral is liquidated. Then ...', () => {
+      beforeEach('liquidate', async () => {
+        await oracle.tx.setPrice('WETH/USD', toE18String(100));
+        await lendingPool
+          .withSigner(liquidator)
+          .tx.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount.divn(2), 1, []);
+      });
+
+      it('the account is listed as the one with unbacked debt', async () => {
+        const unbacked = (await lendingPool.query.viewAccountsWithUnbackedDebt(0, 100)).value.ok!;
+        expect(unbacked).to.include(borrower.address);
+      });
+
+      it('the remaining LINK deposit is seized to the LINK safety fund and the DAI debt is written off', async () => {
+        const daiReserveDataBefore = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+        const borrowerDaiBefore = (await lendingPool.query.viewAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+
+        const tx = lendingPool.withSigner(liquidator).tx.settleBadDebt(borrower.address);
+        await expect(tx).to.eventually.be.fulfilled;
+        await expect(tx).to.emitEvent(lendingPool, 'BadDebtDepositSeized', {
+          account: borrower.address,
+          asset: linkContract.address,
+          amount: depositLinkAmount.toString(),
+        });
+        await expect(tx).to.emitEvent(lendingPool, 'BadDebtSettled', {
+          account: borrower.address,
+          asset: daiContract.address,
+        });
+
+        const linkSafetyFund = (await lendingPool.query.viewReserveSafetyFund(linkContract.address)).value.ok!;
+        expect(linkSafetyFund?.toString()).to.equal(depositLinkAmount.toString());
+
+        const borrowerLink = (await lendingPool.query.viewAccountReserveData(linkContract.address, borrower.address)).value.ok!;
+        expect(borrowerLink.deposit.toString()).to.equal('0');
+        const borrowerDai = (await lendingPool.query.viewAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+        expect(borrowerDai.debt.toString()).to.equal('0');
+
+        // DAI safety fund is empty - the debt is socialized among DAI depositors
+        const daiReserveData = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+        expect(daiReserveData.totalDebt.toString()).to.equal(daiReserveDataBefore.totalDebt.sub(borrowerDaiBefore.debt).toString());
+        expect(daiReserveData.totalDeposit.lt(daiReserveDataBefore.totalDeposit)).to.be.true;
+
+        const unbacked = (await lendingPool.query.viewAccountsWithUnbackedDebt(0, 100)).value.ok!;
+        expect(unbacked).not.to.include(borrower.address);
+        const queryRes = (await lendingPool.withSigner(liquidator).query.settleBadDebt(borrower.address)).value.ok;
+        expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.NoBadDebt());
+      });
+
+      it('once LINK price rises to 1000$ only the LINK worth the DAI debt is seized and the rest stays with the account', async () => {
+        await oracle.tx.setPrice('LINK/USD', toE18String(1000));
+
+        await expect(lendingPool.withSigner(liquidator).tx.settleBadDebt(borrower.address)).to.eventually.be.fulfilled;
+
+        // the remaining DAI debt is below 1000 DAI = 1 LINK
+        const linkSafetyFund = (await lendingPool.query.viewReserveSafetyFund(linkContract.address)).value.ok!;
+        const borrowerLink = (await lendingPool.query.viewAccountReserveData(linkContract.address, borrower.address)).value.ok!;
+        expect.soft(linkSafetyFund!.gtn(0)).to.be.true;
+        expect.soft(linkSafetyFund!.lt(depositLinkAmount.divn(10))).to.be.true;
+        expect.soft(borrowerLink.deposit.add(linkSafetyFund!).toString()).to.equal(depositLinkAmount.toString());
+        const borrowerDai = (await lendingPool.query.viewAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+        expect.soft(borrowerDai.debt.toString()).to.equal('0');
+        expect.flushSoft();
+      });
+    });
+  });
+});
@@ -0,0 +1,96 @@
+import { time } from '@c-forge/polkahat-network-helpers';
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { ReserveDelistingError } from 'typechain/types-arguments/lending_pool';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+import { ONE_HOUR } from './setup/tokensToDeployForTesting';
+
+// for 1 absDAI (10^-6 DAI) 1/1500 * 10^12 absWETH = 6.(6) * 10^8 absWETH are taken without the penalty
+const ABOVE_PAR_RECEIVED_E18 = new BN('667000000000000000000000000');
+const BELOW_PAR_RECEIVED_E18 = new BN('666000000000000000000000000');
+
+makeSuite('LendingPool reserve delisting', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let owner: KeyringPair;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+  let liquidator: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let debtDaiAmount: BN;
+
+  beforeEach('setup Env, make a healthy DAI borrow and start the delisting of DAI', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    [supplier, borrower, liquidator] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit);
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit);
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+    await wethContract.tx.mint(borrower.address, collateralWethAmount);
+    await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+
+    debtDaiAmount = await convertToCurrencyDecimals(daiContract, 100);
+    await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, []);
+
+    await daiContract.tx.mint(liquidator.address, debtDaiAmount.muln(2));
+    await daiContract.withSigner(liquidator).tx.approve(lendingPool.address, debtDaiAmount.muln(2));
+
+    await lendingPool.withSigner(owner).tx.startReserveDelisting(daiContract.address, ONE_HOUR);
+  });
+
+  it('DAI can not be deposited', async () => {
+    const queryRes = (await lendingPool.withSigner(liquidator).query.deposit(daiContract.address, liquidator.address, debtDaiAmount, [])).value.ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ReserveDelistingError(ReserveDelistingError.delisting));
+  });
+
+  it('the collateralized borrower can not be liquidated before the settlement stage', async () => {
+    const queryRes = (
+      await lendingPool.withSigner(liquidator).query.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount, 1, [])
+    ).value.ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.Collaterized());
+  });
+
+  describe('The ramp down ends and the delisting of DAI is in the settlement stage. Then ...', () => {
+    beforeEach('wait for the end of the ramp down', async () => {
+      await time.increase(ONE_HOUR.toNumber());
+    });
+
+    it('the DAI debt of the collateralized borrower is liquidated without the penalty', async () => {
+      const aboveParRes = (
+        await lendingPool
+          .withSigner(liquidator)
+          .query.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount, ABOVE_PAR_RECEIVED_E18, [])
+      ).value.ok;
+      expect(aboveParRes).to.have.deep.property('err', LendingPoolErrorBuilder.MinimumRecieved());
+
+      await expect(
+        lendingPool
+          .withSigner(liquidator)
+          .tx.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount, BELOW_PAR_RECEIVED_E18, []),
+      ).to.eventually.be.fulfilled;
+    });
+  });
+});
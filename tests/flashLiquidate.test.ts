@@ -0,0 +1,133 @@
+import { E18bn } from '@c-forge/polkahat-network-helpers';
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import FlashLoanReceiverMock from 'typechain/contracts/flash_loan_receiver_mock';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import FlashLoanReceiverMockDeployer from 'typechain/deployers/flash_loan_receiver_mock';
+import { FlashLoanReceiverErrorBuilder, LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+// for 1 absDAI 1/1280 * 10^12 absWETH with the penalty of 1.115 = 8.7109375 * 10^8 absWETH are taken
+const MINIMUM_RECEIVED_E18 = new BN('871093750000000000000000000');
+
+makeSuite('LendingPool flash liquidation', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+  let liquidator: KeyringPair;
+  let flashLoanReceiver: FlashLoanReceiverMock;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let debtDaiAmount: BN;
+
+  beforeEach('setup Env, make a DAI borrow and make the borrower undercollateralized', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    [supplier, borrower, liquidator] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+    flashLoanReceiver = (await new FlashLoanReceiverMockDeployer(testEnv.api, liquidator).new()).contract;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit);
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit);
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+    await wethContract.tx.mint(borrower.address, collateralWethAmount);
+    await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+
+    debtDaiAmount = await convertToCurrencyDecimals(daiContract, 1000);
+    await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, []);
+
+    await oracle.tx.setPrice('WETH/USD', toE18String(1280));
+    // the DAI the receiver would get by swapping the seized WETH
+    await daiContract.tx.mint(flashLoanReceiver.address, debtDaiAmount);
+  });
+
+  it('the flash liquidation fails if the receiver fails to execute the operation', async () => {
+    await flashLoanReceiver.tx.setFailExecuteOperation(true);
+    await expect(
+      lendingPool
+        .withSigner(liquidator)
+        .query.flashLiquidate(
+          borrower.address,
+          daiContract.address,
+          wethContract.address,
+          debtDaiAmount,
+          MINIMUM_RECEIVED_E18,
+          flashLoanReceiver.address,
+          [],
+        ),
+    ).to.be.revertedWithError(LendingPoolErrorBuilder.FlashLoanReceiverError(FlashLoanReceiverErrorBuilder.Custom('ExecuteOperationFailed')));
+  });
+
+  it('the liquidator repays the debt without own funds and the receiver gets the seized WETH and repays the flash loan with the fee', async () => {
+    const lendingPoolDAIBalanceBefore = (await daiContract.query.balanceOf(lendingPool.address)).value.ok!;
+    const liquidatorDAIBalanceBefore = (await daiContract.query.balanceOf(liquidator.address)).value.ok!;
+    const borrowersDAIDataBefore = (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+    expect(borrowersDAIDataBefore.debt.gtn(0)).to.be.true;
+
+    await expect(
+      lendingPool
+        .withSigner(liquidator)
+        .tx.flashLiquidate(
+          borrower.address,
+          daiContract.address,
+          wethContract.address,
+          debtDaiAmount,
+          MINIMUM_RECEIVED_E18,
+          flashLoanReceiver.address,
+          [],
+        ),
+    ).to.eventually.be.fulfilled.and.not.to.have.deep.property('error');
+
+    const borrowersDAIDataAfter = (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+    const liquidatorsWETHDataAfter = (await lendingPool.query.viewUnupdatedAccountReserveData(wethContract.address, liquidator.address)).value.ok!;
+    const receiverWETHBalanceAfter = (await wethContract.query.balanceOf(flashLoanReceiver.address)).value.ok!;
+    const lendingPoolDAIBalanceAfter = (await daiContract.query.balanceOf(lendingPool.address)).value.ok!;
+    const liquidatorDAIBalanceAfter = (await daiContract.query.balanceOf(liquidator.address)).value.ok!;
+    const receiverDAIBalanceAfter = (await daiContract.query.balanceOf(flashLoanReceiver.address)).value.ok!;
+    const flashLoanFee = debtDaiAmount.divn(1000);
+
+    expect.soft(borrowersDAIDataAfter.debt.toString()).to.equal('0', 'the debt of the borrower should be repaid');
+    expect.soft(receiverWETHBalanceAfter.gt(E18bn.muln(8).divn(10))).to.be.true;
+    expect.soft(liquidatorsWETHDataAfter.deposit.toString()).to.equal('0', 'the seized WETH should not be deposited to the liquidator');
+    expect.soft(lendingPoolDAIBalanceAfter.sub(lendingPoolDAIBalanceBefore).toString()).to.equal(debtDaiAmount.add(flashLoanFee).toString());
+    expect.soft(receiverDAIBalanceAfter.toString()).to.equal('0', 'the receiver should repay the flash loan with the fee');
+    expect.soft(liquidatorDAIBalanceAfter.toString()).to.equal(liquidatorDAIBalanceBefore.toString());
+    expect.flushSoft();
+  });
+
+  it('the flash liquidation of a collateralized borrower fails', async () => {
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+    const queryRes = (
+      await lendingPool
+        .withSigner(liquidator)
+        .query.flashLiquidate(
+          borrower.address,
+          daiContract.address,
+          wethContract.address,
+          debtDaiAmount,
+          MINIMUM_RECEIVED_E18,
+          flashLoanReceiver.address,
+          [],
+        )
+    ).value.ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.Collaterized());
+  });
+});
@@ -51,13 +51,15 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
 
     expect(stringifyNumericProps(interestRateModel)).to.deep.equal(
       stringifyNumericProps({
-        targetUrE6: 900_000,
-        minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
-        maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
-        rateAtTargetUrE18: 2 * ONE_PERCENT_APR_E18,
-        rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
-        minimalTimeBetweenAdjustments: ONE_HOUR,
-        lastAdjustmentTimestamp: initialTimestamp,
+        adaptive: {
+          targetUrE6: 900_000,
+          minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
+          maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
+          rateAtTargetUrE18: 2 * ONE_PERCENT_APR_E18,
+          rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
+          minimalTimeBetweenAdjustments: ONE_HOUR,
+          lastAdjustmentTimestamp: initialTimestamp,
+        },
       }),
     );
   });
@@ -125,13 +127,15 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
 
         expect(stringifyNumericProps(interestRateModel)).to.deep.equal(
           stringifyNumericProps({
-            targetUrE6: 900_000,
-            minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
-            maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
-            rateAtTargetUrE18: 2 * ONE_PERCENT_APR_E18,
-            rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
-            minimalTimeBetweenAdjustments: ONE_HOUR,
-            lastAdjustmentTimestamp: await time.latest(),
+            adaptive: {
+              targetUrE6: 900_000,
+              minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
+              maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
+              rateAtTargetUrE18: 2 * ONE_PERCENT_APR_E18,
+              rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
+              minimalTimeBetweenAdjustments: ONE_HOUR,
+              lastAdjustmentTimestamp: await time.latest(),
+            },
           }),
         );
       });
@@ -142,7 +146,7 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
 
         const tx = lendingPool.withSigner(supplier).query.adjustRateAtTarget(wethContract.address, 2);
 
-        await expect(tx).to.be.revertedWithError({ twEntryInvalidIndex: 3 });
+        await expect(tx).to.be.revertedWithError({ twError: 'EntryTooRecent' });
       });
 
       it('should be possible after the minimum time between adjustments passes', async () => {
@@ -201,6 +205,9 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
       await lendingPool.tx.accumulateInterest(wethContract.address);
 
       const q = await lendingPool.withSigner(supplier).query.adjustRateAtTarget(wethContract.address, 2);
+      // without the guessed index the tw entry is binary searched
+      const searched = await lendingPool.withSigner(supplier).query.adjustRateAtTarget(wethContract.address, null);
+      expect(searched.value.ok!.ok!.toString()).to.equal(q.value.ok!.ok!.toString());
       const tx = lendingPool.withSigner(supplier).tx.adjustRateAtTarget(wethContract.address, 2);
       await expect(tx).to.be.eventually.fulfilled;
 
@@ -208,13 +215,15 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
 
       expect(stringifyNumericProps(interestRateModel)).to.deep.equal(
         stringifyNumericProps({
-          targetUrE6: 900_000,
-          minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
-          maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
-          rateAtTargetUrE18: 10 * ONE_PERCENT_APR_E18,
-          rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
-          minimalTimeBetweenAdjustments: ONE_HOUR,
-          lastAdjustmentTimestamp: await time.latest(),
+          adaptive: {
+            targetUrE6: 900_000,
+            minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
+            maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
+            rateAtTargetUrE18: 10 * ONE_PERCENT_APR_E18,
+            rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
+            minimalTimeBetweenAdjustments: ONE_HOUR,
+            lastAdjustmentTimestamp: await time.latest(),
+          },
         }),
       );
     });
@@ -247,13 +256,15 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
 
       expect(stringifyNumericProps(interestRateModel)).to.deep.equal(
         stringifyNumericProps({
-          targetUrE6: 900_000,
-          minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
-          maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
-          rateAtTargetUrE18: 21879756,
-          rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
-          minimalTimeBetweenAdjustments: ONE_HOUR,
-          lastAdjustmentTimestamp: await time.latest(),
+          adaptive: {
+            targetUrE6: 900_000,
+            minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
+            maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
+            rateAtTargetUrE18: 21879756,
+            rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
+            minimalTimeBetweenAdjustments: ONE_HOUR,
+            lastAdjustmentTimestamp: await time.latest(),
+          },
         }),
       );
     });
@@ -295,13 +306,15 @@ makeSuite('LendingPool interest rate model tests', (getTestEnv) => {
 
       expect(stringifyNumericProps(interestRateModel)).to.deep.equal(
         stringifyNumericProps({
-          targetUrE6: 900_000,
-          minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
-          maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
-          rateAtTargetUrE18: 17636034,
-          rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
-          minimalTimeBetweenAdjustments: ONE_HOUR,
-          lastAdjustmentTimestamp: await time.latest(),
+          adaptive: {
+            targetUrE6: 900_000,
+            minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
+            maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
+            rateAtTargetUrE18: 17636034,
+            rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
+            minimalTimeBetweenAdjustments: ONE_HOUR,
+            lastAdjustmentTimestamp: await time.latest(),
+          },
         }),
       );
     });
@@ -0,0 +1,107 @@
+import { E18bn } from '@c-forge/polkahat-network-helpers';
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+// for 1 absDAI 1/1280 * 10^12 absWETH with the penalty of 1.115 = 8.7109375 * 10^8 absWETH are taken
+const MINIMUM_RECEIVED_E18 = new BN('871093750000000000000000000');
+const RECEIVE_UNDERLYING = [1];
+
+makeSuite('LendingPool liquidation - liquidator receiving the underlying asset', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+  let liquidator: KeyringPair;
+  let wethBorrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let debtDaiAmount: BN;
+
+  beforeEach('setup Env, make a DAI borrow and make the borrower undercollateralized', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    [supplier, borrower, liquidator, wethBorrower] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit);
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit);
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+    await wethContract.tx.mint(borrower.address, collateralWethAmount);
+    await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+
+    debtDaiAmount = await convertToCurrencyDecimals(daiContract, 1000);
+    await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, debtDaiAmount, []);
+
+    await daiContract.tx.mint(liquidator.address, debtDaiAmount.muln(2));
+    await daiContract.withSigner(liquidator).tx.approve(lendingPool.address, debtDaiAmount.muln(2));
+
+    await oracle.tx.setPrice('WETH/USD', toE18String(1280));
+  });
+
+  it('the liquidator receives the seized WETH instead of a deposit', async () => {
+    const liquidatorWETHBalanceBefore = (await wethContract.query.balanceOf(liquidator.address)).value.ok!;
+
+    await expect(
+      lendingPool
+        .withSigner(liquidator)
+        .tx.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount, MINIMUM_RECEIVED_E18, RECEIVE_UNDERLYING),
+    ).to.eventually.be.fulfilled.and.not.to.have.deep.property('error');
+
+    const liquidatorWETHBalanceAfter = (await wethContract.query.balanceOf(liquidator.address)).value.ok!;
+    const liquidatorsWETHDataAfter = (await lendingPool.query.viewUnupdatedAccountReserveData(wethContract.address, liquidator.address)).value.ok!;
+    expect.soft(liquidatorWETHBalanceAfter.sub(liquidatorWETHBalanceBefore).gt(E18bn.muln(8).divn(10))).to.be.true;
+    expect.soft(liquidatorsWETHDataAfter.deposit.toString()).to.equal('0', 'the seized WETH should not be deposited to the liquidator');
+    expect.flushSoft();
+  });
+
+  describe('Half of the deposited WETH is borrowed out of the pool. Then ...', () => {
+    beforeEach('borrow 0.5 WETH', async () => {
+      const collateralDaiAmount = await convertToCurrencyDecimals(daiContract, 10000);
+      await daiContract.tx.mint(wethBorrower.address, collateralDaiAmount);
+      await daiContract.withSigner(wethBorrower).tx.approve(lendingPool.address, collateralDaiAmount);
+      await lendingPool.withSigner(wethBorrower).tx.deposit(daiContract.address, wethBorrower.address, collateralDaiAmount, []);
+      await lendingPool.withSigner(wethBorrower).tx.setAsCollateral(daiContract.address, true);
+      await lendingPool.withSigner(wethBorrower).tx.borrow(wethContract.address, wethBorrower.address, E18bn.divn(2), []);
+    });
+
+    it('the liquidator can not receive the seized WETH as the pool lacks the liquidity', async () => {
+      const queryRes = (
+        await lendingPool
+          .withSigner(liquidator)
+          .query.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount, MINIMUM_RECEIVED_E18, RECEIVE_UNDERLYING)
+      ).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.InsufficientLiquidity());
+    });
+
+    it('the liquidator can still receive the seized WETH as a deposit', async () => {
+      await expect(
+        lendingPool
+          .withSigner(liquidator)
+          .tx.liquidate(borrower.address, daiContract.address, wethContract.address, debtDaiAmount, MINIMUM_RECEIVED_E18, []),
+      ).to.eventually.be.fulfilled.and.not.to.have.deep.property('error');
+
+      const liquidatorsWETHDataAfter = (await lendingPool.query.viewUnupdatedAccountReserveData(wethContract.address, liquidator.address)).value.ok!;
+      expect(liquidatorsWETHDataAfter.deposit.gt(E18bn.muln(8).divn(10))).to.be.true;
+    });
+  });
+});
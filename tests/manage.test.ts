@@ -93,11 +93,13 @@ makeSuite('Manage tests', (getTestEnv) => {
       },
       reserveFees: { depositFeeE6: '100000', debtFeeE6: '100000' },
       interestRateModel: {
-        targetUrE6: 1,
-        minRateAtTargetE18: 2,
-        maxRateAtTargetE18: 3,
-        rateAtMaxUrE18: 4,
-        minimalTimeBetweenAdjustments: 5,
+        adaptive: {
+          targetUrE6: 1,
+          minRateAtTargetE18: 2,
+          maxRateAtTargetE18: 3,
+          rateAtMaxUrE18: 4,
+          minimalTimeBetweenAdjustments: 5,
+        },
       },
     };
 
@@ -152,11 +154,13 @@ makeSuite('Manage tests', (getTestEnv) => {
             args: {
               asset: PARAMS.asset,
               interestRateModelParams: {
-                targetUrE6: '1',
-                minRateAtTargetE18: '2',
-                maxRateAtTargetE18: '3',
-                rateAtMaxUrE18: '4',
-                minimalTimeBetweenAdjustments: '5',
+                adaptive: {
+                  targetUrE6: '1',
+                  minRateAtTargetE18: '2',
+                  maxRateAtTargetE18: '3',
+                  rateAtMaxUrE18: '4',
+                  minimalTimeBetweenAdjustments: '5',
+                },
               },
             },
           },
@@ -204,13 +208,15 @@ makeSuite('Manage tests', (getTestEnv) => {
         });
         expect.soft(stringifyNumericProps(reserveRestrictions)).to.deep.equal(PARAMS.reserveRestrictions);
         expect.soft(stringifyNumericProps(reserveModel)).to.deep.equal({
-          targetUrE6: '1',
-          minRateAtTargetE18: '2',
-          maxRateAtTargetE18: '3',
-          rateAtTargetUrE18: '2',
-          rateAtMaxUrE18: '4',
-          minimalTimeBetweenAdjustments: '5',
-          lastAdjustmentTimestamp: (await time.latest()).toString(),
+          adaptive: {
+            targetUrE6: '1',
+            minRateAtTargetE18: '2',
+            maxRateAtTargetE18: '3',
+            rateAtTargetUrE18: '2',
+            rateAtMaxUrE18: '4',
+            minimalTimeBetweenAdjustments: '5',
+            lastAdjustmentTimestamp: (await time.latest()).toString(),
+          },
         });
         expect.soft(stringifyNumericProps(reserveFees)).to.deep.equal({ ...PARAMS.reserveFees, earnedFee: '0' });
         expect.soft(stringifyNumericProps(reserveIndexes)).to.deep.equal({
@@ -423,7 +429,8 @@ makeSuite('Manage tests', (getTestEnv) => {
     type params = Parameters<typeof lendingPool.query.setReserveIsFrozen>;
     const PARAMS = {
       asset: '',
-      active: true,
+      freeze: true,
+      reasonCode: 7,
     };
     beforeEach(() => {
       PARAMS.asset = testEnv.reserves['DAI'].underlying.address;
@@ -439,7 +446,7 @@ makeSuite('Manage tests', (getTestEnv) => {
     for (const role_name of ROLES_WITH_ACCESS) {
       it(role_name + ' should succeed, event should be emitted, storage should be modified', async () => {
         const reserveDataBefore = (await lendingPool.query.viewReserveData(PARAMS.asset)).value.ok!;
-        expect.soft(reserveDataBefore.activated).to.equal(true);
+        expect.soft(reserveDataBefore.frozen).to.equal(false);
 
         const tx = lendingPool.withSigner(adminOf[role_name]).tx.setReserveIsFrozen(...(Object.values(PARAMS) as params));
         await expect(tx).to.eventually.be.fulfilled.and.not.to.have.deep.property('error');
@@ -450,21 +457,26 @@ makeSuite('Manage tests', (getTestEnv) => {
             name: 'abax_contracts::lending_pool::events::ReserveFrozen',
             args: {
               asset: PARAMS.asset,
-              frozen: PARAMS.active,
+              frozen: PARAMS.freeze,
+              reasonCode: PARAMS.reasonCode.toString(),
             },
           },
         ]);
 
         const reserveData = (await lendingPool.query.viewReserveData(PARAMS.asset)).value.ok!;
-        expect.soft(reserveData.activated).to.equal(PARAMS.active);
+        expect.soft(reserveData.frozen).to.equal(PARAMS.freeze);
+        expect.soft(reserveData.activated).to.equal(true);
+
+        const operationalState = (await lendingPool.query.viewReserveOperationalState(PARAMS.asset)).value.ok!;
+        expect.soft(operationalState?.freeze?.reasonCode.toString()).to.equal(PARAMS.reasonCode.toString());
 
         expect.flushSoft();
       });
     }
-    it('roles with permission should fail to activate already activated reserve with Err AlreadyActivated', async () => {
+    it('roles with permission should fail to unfreeze not frozen reserve with Err AlreadySet', async () => {
       for (const role_name of ROLES_WITH_ACCESS) {
         const queryRes = (
-          await lendingPool.withSigner(adminOf[role_name]).query.setReserveIsFrozen(...(Object.values({ ...PARAMS, active: false }) as params))
+          await lendingPool.withSigner(adminOf[role_name]).query.setReserveIsFrozen(...(Object.values({ ...PARAMS, freeze: false }) as params))
         ).value.ok;
         expect.soft(queryRes).to.have.deep.property('err', { reserveDataError: 'AlreadySet' });
       }
@@ -479,11 +491,13 @@ makeSuite('Manage tests', (getTestEnv) => {
     const PARAMS = {
       asset: '',
       interestRateModel: {
-        targetUrE6: 1,
-        minRateAtTargetE18: 2,
-        maxRateAtTargetE18: 3,
-        rateAtMaxUrE18: 4,
-        minimalTimeBetweenAdjustments: 5,
+        adaptive: {
+          targetUrE6: 1,
+          minRateAtTargetE18: 2,
+          maxRateAtTargetE18: 3,
+          rateAtMaxUrE18: 4,
+          minimalTimeBetweenAdjustments: 5,
+        },
       },
     };
     beforeEach(() => {
@@ -509,11 +523,13 @@ makeSuite('Manage tests', (getTestEnv) => {
             args: {
               asset: PARAMS.asset,
               interestRateModelParams: {
-                targetUrE6: '1',
-                minRateAtTargetE18: '2',
-                maxRateAtTargetE18: '3',
-                rateAtMaxUrE18: '4',
-                minimalTimeBetweenAdjustments: '5',
+                adaptive: {
+                  targetUrE6: '1',
+                  minRateAtTargetE18: '2',
+                  maxRateAtTargetE18: '3',
+                  rateAtMaxUrE18: '4',
+                  minimalTimeBetweenAdjustments: '5',
+                },
               },
             },
           },
@@ -521,13 +537,15 @@ makeSuite('Manage tests', (getTestEnv) => {
 
         const interestRateModel = (await lendingPool.query.viewInterestRateModel(PARAMS.asset)).value.ok!;
         expect.soft(stringifyNumericProps(interestRateModel)).to.deep.equal({
-          targetUrE6: '1',
-          minRateAtTargetE18: '2',
-          maxRateAtTargetE18: '3',
-          rateAtTargetUrE18: '2',
-          rateAtMaxUrE18: '4',
-          minimalTimeBetweenAdjustments: '5',
-          lastAdjustmentTimestamp: (await time.latest()).toString(),
+          adaptive: {
+            targetUrE6: '1',
+            minRateAtTargetE18: '2',
+            maxRateAtTargetE18: '3',
+            rateAtTargetUrE18: '2',
+            rateAtMaxUrE18: '4',
+            minimalTimeBetweenAdjustments: '5',
+            lastAdjustmentTimestamp: (await time.latest()).toString(),
+          },
         });
 
         expect.flushSoft();
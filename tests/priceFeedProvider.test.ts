@@ -0,0 +1,204 @@
+import { time } from '@c-forge/polkahat-network-helpers';
+import { KeyringPair } from '@polkadot/keyring/types';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import DiaOracleDeployer from 'typechain/deployers/dia_oracle';
+import { PriceFeedErrorBuilder } from 'typechain/types-returns/price_feed_provider';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import PriceFeedProvider from '../typechain/contracts/price_feed_provider';
+import { toE18String } from './helpers/converters';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+import { ONE_HOUR, ONE_SEC } from './setup/tokensToDeployForTesting';
+
+const ONE_PERCENT_E18 = '10000000000000000';
+
+makeSuite('PriceFeedProvider price limits', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let priceFeedProvider: PriceFeedProvider;
+  let oracle: DiaOracle;
+  let owner: KeyringPair;
+  let keeper: KeyringPair;
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let linkContract: PSP22Emitable;
+
+  beforeEach('setup Env and prices', async () => {
+    testEnv = getTestEnv();
+    priceFeedProvider = testEnv.priceFeedProvider;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    keeper = testEnv.accounts[0];
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+    linkContract = testEnv.reserves['LINK'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+    await oracle.tx.setPrice('LINK/USD', toE18String(12));
+  });
+
+  describe('The maximal price age of DAI is set to 1 hour. Then ...', () => {
+    beforeEach('set the maximal price age', async () => {
+      await priceFeedProvider.withSigner(owner).tx.setPriceLimits(daiContract.address, ONE_HOUR, null);
+    });
+
+    it('the price updated within the hour is returned', async () => {
+      const queryRes = (await priceFeedProvider.query.getLatestPrices([daiContract.address])).value.ok!;
+      expect(queryRes.ok![0].toString()).to.equal(toE18String(1));
+    });
+
+    it('the price older than an hour is rejected until it is updated', async () => {
+      await time.increase(ONE_HOUR.add(ONE_SEC).toNumber());
+
+      const staleRes = (await priceFeedProvider.query.getLatestPrices([daiContract.address])).value.ok;
+      expect(staleRes).to.have.deep.property('err', PriceFeedErrorBuilder.StalePrice());
+      // the age of WETH price is not limited
+      const wethRes = (await priceFeedProvider.query.getLatestPrices([wethContract.address])).value.ok!;
+      expect(wethRes.ok![0].toString()).to.equal(toE18String(1500));
+
+      await oracle.tx.setPrice('DAI/USD', toE18String(1));
+      const freshRes = (await priceFeedProvider.query.getLatestPrices([daiContract.address])).value.ok!;
+      expect(freshRes.ok![0].toString()).to.equal(toE18String(1));
+    });
+  });
+
+  describe('The price deviation of WETH is limited to 10% with 1 hour anchor cooldown and the anchor is set at 1500$. Then ...', () => {
+    beforeEach('set the deviation limit and the anchor', async () => {
+      await priceFeedProvider.withSigner(owner).tx.setPriceLimits(wethContract.address, null, {
+        maxDeviationE6: 100_000,
+        anchorCooldown: ONE_HOUR,
+      });
+      await priceFeedProvider.withSigner(keeper).tx.updatePriceAnchors([wethContract.address]);
+    });
+
+    it('the anchor is set to the current price', async () => {
+      const anchor = (await priceFeedProvider.query.getPriceAnchor(wethContract.address)).value.ok!;
+      expect(anchor?.price.toString()).to.equal(toE18String(1500));
+    });
+
+    it('the price within 10% of the anchor is returned', async () => {
+      await oracle.tx.setPrice('WETH/USD', toE18String(1600));
+      const queryRes = (await priceFeedProvider.query.getLatestPrices([wethContract.address])).value.ok!;
+      expect(queryRes.ok![0].toString()).to.equal(toE18String(1600));
+    });
+
+    describe('WETH price jumps to 1700$ (by more than 10%). Then ...', () => {
+      beforeEach('set price', async () => {
+        await oracle.tx.setPrice('WETH/USD', toE18String(1700));
+      });
+
+      it('the price is rejected while the prices of other assets are returned', async () => {
+        const queryRes = (await priceFeedProvider.query.getLatestPrices([daiContract.address, wethContract.address])).value.ok;
+        expect(queryRes).to.have.deep.property('err', PriceFeedErrorBuilder.PriceDeviationTooLarge());
+
+        const daiRes = (await priceFeedProvider.query.getLatestPrices([daiContract.address])).value.ok!;
+        expect(daiRes.ok![0].toString()).to.equal(toE18String(1));
+      });
+
+      it('the anchor can not be moved before the cooldown passes', async () => {
+        const updated = (await priceFeedProvider.withSigner(keeper).query.updatePriceAnchors([wethContract.address])).value.ok!;
+        expect(updated).to.deep.equal([false]);
+        await priceFeedProvider.withSigner(keeper).tx.updatePriceAnchors([wethContract.address]);
+
+        const anchor = (await priceFeedProvider.query.getPriceAnchor(wethContract.address)).value.ok!;
+        expect(anchor?.price.toString()).to.equal(toE18String(1500));
+        const queryRes = (await priceFeedProvider.query.getLatestPrices([wethContract.address])).value.ok;
+        expect(queryRes).to.have.deep.property('err', PriceFeedErrorBuilder.PriceDeviationTooLarge());
+      });
+
+      it('after the cooldown anyone can move the anchor to the new price and the price is returned', async () => {
+        await time.increase(ONE_HOUR.toNumber());
+
+        const updated = (await priceFeedProvider.withSigner(keeper).query.updatePriceAnchors([wethContract.address])).value.ok!;
+        expect(updated).to.deep.equal([true]);
+        await priceFeedProvider.withSigner(keeper).tx.updatePriceAnchors([wethContract.address]);
+
+        const anchor = (await priceFeedProvider.query.getPriceAnchor(wethContract.address)).value.ok!;
+        expect(anchor?.price.toString()).to.equal(toE18String(1700));
+        const queryRes = (await priceFeedProvider.query.getLatestPrices([wethContract.address])).value.ok!;
+        expect(queryRes.ok![0].toString()).to.equal(toE18String(1700));
+      });
+
+      it('the admin can reset the anchor so it is moved without waiting for the cooldown', async () => {
+        await priceFeedProvider.withSigner(owner).tx.resetPriceAnchor(wethContract.address);
+        const queryRes = (await priceFeedProvider.query.getLatestPrices([wethContract.address])).value.ok!;
+        expect(queryRes.ok![0].toString()).to.equal(toE18String(1700));
+
+        await priceFeedProvider.withSigner(keeper).tx.updatePriceAnchors([wethContract.address]);
+        const anchor = (await priceFeedProvider.query.getPriceAnchor(wethContract.address)).value.ok!;
+        expect(anchor?.price.toString()).to.equal(toE18String(1700));
+      });
+    });
+  });
+
+  describe('Asset price types. Then ...', () => {
+    it('the fixed price is returned without querying any source', async () => {
+      await priceFeedProvider.withSigner(owner).tx.setAssetPriceType(daiContract.address, { fixed: { priceE18: toE18String(1) } });
+      await oracle.tx.setPrice('DAI/USD', toE18String(2));
+
+      const [price, sources] = (await priceFeedProvider.query.getLatestPriceWithSources(daiContract.address)).value.ok!.ok!;
+      expect(price.toString()).to.equal(toE18String(1));
+      expect(sources).to.deep.equal([]);
+    });
+
+    it('the pegged price is the price of the base asset multiplied by the ratio', async () => {
+      // LINK is pegged to 1/100 of WETH
+      await priceFeedProvider.withSigner(owner).tx.setAssetPriceType(linkContract.address, {
+        pegged: { asset: wethContract.address, ratioE18: ONE_PERCENT_E18 },
+      });
+      const queryRes = (await priceFeedProvider.query.getLatestPrices([linkContract.address])).value.ok!;
+      expect(queryRes.ok![0].toString()).to.equal(toE18String(15));
+    });
+
+    it('the price can not be pegged to a pegged asset', async () => {
+      await priceFeedProvider.withSigner(owner).tx.setAssetPriceType(linkContract.address, {
+        pegged: { asset: wethContract.address, ratioE18: ONE_PERCENT_E18 },
+      });
+      await priceFeedProvider.withSigner(owner).tx.setAssetPriceType(daiContract.address, {
+        pegged: { asset: linkContract.address, ratioE18: toE18String(1) },
+      });
+      const queryRes = (await priceFeedProvider.query.getLatestPrices([daiContract.address])).value.ok;
+      expect(queryRes).to.have.deep.property('err', PriceFeedErrorBuilder.InvalidPriceType());
+    });
+
+    it('the derived prices are the product and the quotient of the symbols prices', async () => {
+      await priceFeedProvider.withSigner(owner).tx.setAssetPriceType(linkContract.address, {
+        quotient: { numerator: 'WETH/USD', denominator: 'LINK/USD' },
+      });
+      await priceFeedProvider.withSigner(owner).tx.setAssetPriceType(daiContract.address, {
+        product: { first: 'LINK/USD', second: 'WETH/USD' },
+      });
+      const queryRes = (await priceFeedProvider.query.getLatestPrices([linkContract.address, daiContract.address])).value.ok!;
+      // 1500 / 12 and 12 * 1500
+      expect(queryRes.ok![0].toString()).to.equal(toE18String(125));
+      expect(queryRes.ok![1].toString()).to.equal(toE18String(18000));
+    });
+  });
+
+  describe('WETH price is read from a primary oracle with no WETH price and falls back to the default oracle. Then ...', () => {
+    let primaryOracle: DiaOracle;
+    beforeEach('deploy the primary oracle and set the price sources', async () => {
+      primaryOracle = (await new DiaOracleDeployer(testEnv.api, owner).new()).contract;
+      await priceFeedProvider.withSigner(owner).tx.setPriceSources(wethContract.address, {
+        sources: [{ diaOracle: primaryOracle.address }, { diaOracle: oracle.address }],
+        aggregation: { primaryWithFallback: null },
+      });
+    });
+
+    it('the price of the fallback oracle is returned and reported as used', async () => {
+      const [price, sources] = (await priceFeedProvider.query.getLatestPriceWithSources(wethContract.address)).value.ok!.ok!;
+      expect(price.toString()).to.equal(toE18String(1500));
+      expect(sources).to.deep.equal([{ diaOracle: oracle.address }]);
+    });
+
+    it('the price of the primary oracle is returned once it is available', async () => {
+      await primaryOracle.withSigner(owner).tx.setPrice('WETH/USD', toE18String(1510));
+      const [price, sources] = (await priceFeedProvider.query.getLatestPriceWithSources(wethContract.address)).value.ok!.ok!;
+      expect(price.toString()).to.equal(toE18String(1510));
+      expect(sources).to.deep.equal([{ diaOracle: primaryOracle.address }]);
+
+      const queryRes = (await priceFeedProvider.query.getLatestPrices([wethContract.address])).value.ok!;
+      expect(queryRes.ok![0].toString()).to.equal(toE18String(1510));
+    });
+  });
+});
@@ -0,0 +1,98 @@
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+const FREEZE_REASON_CODE = 7;
+
+makeSuite('LendingPool reserve freezing and activation', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let owner: KeyringPair;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let daiAmount: BN;
+
+  beforeEach('setup Env and make a DAI borrow', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    [supplier, borrower] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit.muln(2));
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit.muln(2));
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 1);
+    await wethContract.tx.mint(borrower.address, collateralWethAmount);
+    await wethContract.withSigner(borrower).tx.approve(lendingPool.address, collateralWethAmount);
+    await lendingPool.withSigner(borrower).tx.deposit(wethContract.address, borrower.address, collateralWethAmount, []);
+    await lendingPool.withSigner(borrower).tx.setAsCollateral(wethContract.address, true);
+
+    daiAmount = await convertToCurrencyDecimals(daiContract, 100);
+    await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, daiAmount, []);
+    await daiContract.withSigner(borrower).tx.approve(lendingPool.address, daiAmount);
+  });
+
+  describe('The EMERGENCY_ADMIN freezes DAI. Then ...', () => {
+    beforeEach('freeze DAI', async () => {
+      await lendingPool.withSigner(owner).tx.setReserveIsFrozen(daiContract.address, true, FREEZE_REASON_CODE);
+    });
+
+    it('the reserve is reported as frozen but still active', async () => {
+      const operationalState = (await lendingPool.query.viewReserveOperationalState(daiContract.address)).value.ok!;
+      expect(operationalState?.frozen).to.equal(true);
+      expect(operationalState?.activated).to.equal(true);
+      expect(operationalState?.freeze?.reasonCode.toString()).to.equal(FREEZE_REASON_CODE.toString());
+    });
+
+    it('DAI can neither be deposited nor borrowed', async () => {
+      const depositRes = (await lendingPool.withSigner(supplier).query.deposit(daiContract.address, supplier.address, daiAmount, [])).value.ok;
+      expect(depositRes).to.have.deep.property('err', { reserveDataError: 'Frozen' });
+      const borrowRes = (await lendingPool.withSigner(borrower).query.borrow(daiContract.address, borrower.address, daiAmount, [])).value.ok;
+      expect(borrowRes).to.have.deep.property('err', { reserveDataError: 'Frozen' });
+    });
+
+    it('DAI can still be repaid and withdrawn', async () => {
+      const repayAmount = daiAmount.divn(2);
+      await expect(lendingPool.withSigner(borrower).tx.repay(daiContract.address, borrower.address, repayAmount, [])).to.eventually.be.fulfilled;
+      await expect(lendingPool.withSigner(supplier).tx.withdraw(daiContract.address, supplier.address, daiAmount, [])).to.eventually.be.fulfilled;
+    });
+  });
+
+  describe('The EMERGENCY_ADMIN deactivates DAI. Then ...', () => {
+    beforeEach('deactivate DAI', async () => {
+      await lendingPool.withSigner(owner).tx.setReserveIsActive(daiContract.address, false);
+    });
+
+    it('the reserve is reported as inactive but not frozen', async () => {
+      const operationalState = (await lendingPool.query.viewReserveOperationalState(daiContract.address)).value.ok!;
+      expect(operationalState?.activated).to.equal(false);
+      expect(operationalState?.frozen).to.equal(false);
+      expect(operationalState?.freeze).to.be.null;
+    });
+
+    it('DAI can neither be repaid nor withdrawn', async () => {
+      const repayRes = (await lendingPool.withSigner(borrower).query.repay(daiContract.address, borrower.address, daiAmount, [])).value.ok;
+      expect(repayRes).to.have.deep.property('err', { reserveDataError: 'Inactive' });
+      const withdrawRes = (await lendingPool.withSigner(supplier).query.withdraw(daiContract.address, supplier.address, daiAmount, [])).value.ok;
+      expect(withdrawRes).to.have.deep.property('err', { reserveDataError: 'Inactive' });
+    });
+  });
+});
@@ -0,0 +1,113 @@
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import { ReserveIsolationError } from 'typechain/types-arguments/lending_pool';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+
+const E8 = new BN(10).pow(new BN(8));
+const DEBT_CEILING_E8 = E8.muln(1000);
+const LINK_ISOLATION = { debtCeilingE8: DEBT_CEILING_E8, isIsolationBorrowable: false };
+
+makeSuite('LendingPool isolation mode', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let owner: KeyringPair;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let linkContract: PSP22Emitable;
+
+  const mintAndDeposit = async (token: PSP22Emitable, account: KeyringPair, amount: number) => {
+    const tokenAmount = await convertToCurrencyDecimals(token, amount);
+    await token.tx.mint(account.address, tokenAmount);
+    await token.withSigner(account).tx.approve(lendingPool.address, tokenAmount);
+    await lendingPool.withSigner(account).tx.deposit(token.address, account.address, tokenAmount, []);
+  };
+
+  beforeEach('setup Env, prices and supply DAI and WETH', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    [supplier, borrower] = testEnv.accounts;
+    daiContract = testEnv.reserves['DAI'].underlying;
+    wethContract = testEnv.reserves['WETH'].underlying;
+    linkContract = testEnv.reserves['LINK'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+    await oracle.tx.setPrice('LINK/USD', toE18String(10));
+
+    await mintAndDeposit(daiContract, supplier, 10000);
+    await mintAndDeposit(wethContract, supplier, 10);
+    await mintAndDeposit(linkContract, borrower, 1000);
+  });
+
+  describe('The borrower uses LINK as collateral. Then ...', () => {
+    beforeEach('set LINK as collateral', async () => {
+      await lendingPool.withSigner(borrower).tx.setAsCollateral(linkContract.address, true);
+    });
+
+    it('LINK can not be isolated', async () => {
+      const queryRes = (await lendingPool.withSigner(owner).query.setReserveIsolation(linkContract.address, LINK_ISOLATION)).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ReserveIsolationError(ReserveIsolationError.assetUsedAsCollateral));
+    });
+
+    it('LINK can be isolated once the borrower turns it off as collateral', async () => {
+      await lendingPool.withSigner(borrower).tx.setAsCollateral(linkContract.address, false);
+      await expect(lendingPool.withSigner(owner).tx.setReserveIsolation(linkContract.address, LINK_ISOLATION)).to.eventually.be.fulfilled;
+    });
+  });
+
+  describe('LINK is isolated with the debt ceiling of 1000$, DAI is isolation borrowable and the borrower uses LINK as collateral. Then ...', () => {
+    beforeEach('isolate LINK and set it as collateral', async () => {
+      await lendingPool.withSigner(owner).tx.setReserveIsolation(linkContract.address, LINK_ISOLATION);
+      await lendingPool.withSigner(owner).tx.setReserveIsolation(daiContract.address, { debtCeilingE8: null, isIsolationBorrowable: true });
+      await lendingPool.withSigner(borrower).tx.setAsCollateral(linkContract.address, true);
+    });
+
+    it('the borrower can not use other collateral together with LINK', async () => {
+      await mintAndDeposit(daiContract, borrower, 100);
+      const queryRes = (await lendingPool.withSigner(borrower).query.setAsCollateral(daiContract.address, true)).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ReserveIsolationError(ReserveIsolationError.isolatedCollateralNotSole));
+    });
+
+    it('the borrower can not borrow WETH that is not isolation borrowable', async () => {
+      const amount = await convertToCurrencyDecimals(wethContract, 1);
+      const queryRes = (await lendingPool.withSigner(borrower).query.borrow(wethContract.address, borrower.address, amount, [])).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ReserveIsolationError(ReserveIsolationError.isolationBorrowDisabled));
+    });
+
+    it('the borrower can borrow DAI up to the debt ceiling', async () => {
+      const overCeiling = await convertToCurrencyDecimals(daiContract, 1001);
+      const queryRes = (await lendingPool.withSigner(borrower).query.borrow(daiContract.address, borrower.address, overCeiling, [])).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ReserveIsolationError(ReserveIsolationError.debtCeilingReached));
+
+      const atCeiling = await convertToCurrencyDecimals(daiContract, 1000);
+      await expect(lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, atCeiling, [])).to.eventually.be.fulfilled;
+      const isolatedDebt = (await lendingPool.query.viewIsolatedDebtE8(linkContract.address)).value.ok!;
+      expect(isolatedDebt?.toString()).to.equal(DEBT_CEILING_E8.toString());
+    });
+
+    it('the isolated debt decreases when the borrower repays', async () => {
+      const borrowAmount = await convertToCurrencyDecimals(daiContract, 500);
+      await lendingPool.withSigner(borrower).tx.borrow(daiContract.address, borrower.address, borrowAmount, []);
+
+      const repayAmount = await convertToCurrencyDecimals(daiContract, 200);
+      await daiContract.withSigner(borrower).tx.approve(lendingPool.address, repayAmount);
+      await lendingPool.withSigner(borrower).tx.repay(daiContract.address, borrower.address, repayAmount, []);
+
+      const isolatedDebt = (await lendingPool.query.viewIsolatedDebtE8(linkContract.address)).value.ok!;
+      expect(isolatedDebt?.toString()).to.equal(E8.muln(300).toString());
+    });
+  });
+});
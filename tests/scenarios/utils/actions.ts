@@ -25,6 +25,16 @@ import {
 } from './comparisons';
 import { TestEnv, TokenReserve } from './make-suite';
 
+type AssetsBitmap = { pages: { toString(): string }[] };
+
+// the bitmap is stored in 128-bit pages - the bit of asset with id `assetId` is the `assetId % 128` bit of the `assetId / 128` page
+export const isAssetInBitmap = (bitmap: AssetsBitmap, assetId: number) => {
+  const page = bitmap.pages[Math.floor(assetId / 128)];
+  return page !== undefined && new BN(page.toString()).testn(assetId % 128);
+};
+
+const bitmapToString = (bitmap: AssetsBitmap) => bitmap.pages.map((page) => page.toString()).join(',');
+
 export const convertToCurrencyDecimals = async (token: any, amount: BN | number | string) => {
   const decimals = (await token.query.tokenDecimals()).value.ok!;
   const { amountParsed, amountParsedDecimals } = BN.isBN(amount) ? { amountParsed: amount, amountParsedDecimals: 0 } : parseAmountToBN(amount);
@@ -287,22 +297,21 @@ export const setUseAsCollateral = async (
 
     const { accountConfig: accountConfigAfter } = await getAccountReserveDataWithTimestamp(underlying, caller, lendingPool);
 
-    if (accountConfigBefore.collaterals !== accountConfigAfter.collaterals) {
+    const isCollateralAfter = isAssetInBitmap(accountConfigAfter.collaterals, assetId.toNumber());
+    if (bitmapToString(accountConfigBefore.collaterals) !== bitmapToString(accountConfigAfter.collaterals)) {
       expect(txResult.events).to.deep.equal([
         {
           args: {
             asset: testEnv.reserves[reserveSymbol].underlying.address,
             caller: caller.address,
-            set: (accountConfigAfter.collaterals.toNumber() >> assetId.toNumber()) % 2 === 1 ? true : false,
+            set: isCollateralAfter,
           },
           name: 'abax_contracts::lending_pool::events::CollateralSet',
         },
       ]);
     }
     expect.toBeDefined(accountConfigAfter);
-    expect((accountConfigAfter.collaterals.toNumber() >> assetId.toNumber()) % 2, 'setUseAsCollateral didnt work').to.equal(
-      useAsCollateralToSet ? 1 : 0,
-    );
+    expect(isCollateralAfter, 'setUseAsCollateral didnt work').to.equal(useAsCollateralToSet);
   } else if (expectedResult === 'revert') {
     if (expectedErrorName) {
       const queryRes = (await lendingPool.withSigner(caller).query.setAsCollateral(...args)).value.ok;
@@ -13,12 +13,14 @@ export const ONE_YEAR = ONE_DAY.muln(365);
 
 /* eslint-disable */
 export const DEFAULT_INTEREST_RATE_MODEL_FOR_TESTING: InterestRateModelParams = {
-  targetUrE6: 900_000, //90%
-  minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
-  maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
+  adaptive: {
+    targetUrE6: 900_000, //90%
+    minRateAtTargetE18: 2 * ONE_PERCENT_APR_E18,
+    maxRateAtTargetE18: 10 * ONE_PERCENT_APR_E18,
 
-  rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
-  minimalTimeBetweenAdjustments: ONE_HOUR,
+    rateAtMaxUrE18: 100 * ONE_PERCENT_APR_E18,
+    minimalTimeBetweenAdjustments: ONE_HOUR,
+  },
 };
 
 export const E6: number = 1_000_000;
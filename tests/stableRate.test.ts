@@ -0,0 +1,187 @@
+import { KeyringPair } from '@polkadot/keyring/types';
+import BN from 'bn.js';
+import PSP22Emitable from 'typechain/contracts/test_psp22';
+import STokenDeployer from 'typechain/deployers/s_token';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import DiaOracle from '../typechain/contracts/dia_oracle';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { MAX_U128 } from './consts';
+import { toE18String } from './helpers/converters';
+import { convertToCurrencyDecimals } from './scenarios/utils/actions';
+import { makeSuite, TestEnv, TestEnvReserves } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+import { ONE_PERCENT_APR_E18 } from './setup/tokensToDeployForTesting';
+
+const STABLE_RATE_PREMIUM_E18 = 5 * ONE_PERCENT_APR_E18;
+const UNREACHABLE_REBALANCE_THRESHOLD_E18 = new BN('1000000000000000');
+
+makeSuite('LendingPool stable rate borrowing', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let oracle: DiaOracle;
+  let reserves: TestEnvReserves;
+  let owner: KeyringPair;
+  let supplier: KeyringPair;
+  let borrower: KeyringPair;
+  let rebalancer: KeyringPair;
+  let variableBorrower: KeyringPair;
+
+  let daiContract: PSP22Emitable;
+  let wethContract: PSP22Emitable;
+  let stableDebtDaiAmount: BN;
+
+  beforeEach('setup Env, enable DAI stable borrowing and make stable borrow', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    reserves = testEnv.reserves;
+    oracle = testEnv.oracle;
+    owner = testEnv.owner;
+    [supplier, borrower, rebalancer, variableBorrower] = testEnv.accounts;
+    daiContract = reserves['DAI'].underlying;
+    wethContract = reserves['WETH'].underlying;
+
+    await oracle.tx.setPrice('DAI/USD', toE18String(1));
+    await oracle.tx.setPrice('WETH/USD', toE18String(1500));
+
+    const sTokenContract = await new STokenDeployer(testEnv.api, owner).new('Abacus Stable Debt Token', 'SToken', 0, owner.address, owner.address);
+    const { codeHash: sTokenCodeHash } = (await testEnv.api.query.contracts.contractInfoOf(sTokenContract.contract.address)).toHuman() as {
+      codeHash: string;
+    };
+    await lendingPool.withSigner(owner).tx.registerStableDebtToken(daiContract.address, sTokenCodeHash as any, 'DAI', 'DAI', reserves['DAI'].decimals);
+    await lendingPool.withSigner(owner).tx.setStableRateModel(daiContract.address, {
+      stableRatePremiumE18: STABLE_RATE_PREMIUM_E18,
+      rebalanceThresholdE18: UNREACHABLE_REBALANCE_THRESHOLD_E18,
+    });
+
+    const totalDaiDeposit = await convertToCurrencyDecimals(daiContract, 10000);
+    await daiContract.tx.mint(supplier.address, totalDaiDeposit);
+    await daiContract.withSigner(supplier).tx.approve(lendingPool.address, totalDaiDeposit);
+    await lendingPool.withSigner(supplier).tx.deposit(daiContract.address, supplier.address, totalDaiDeposit, []);
+
+    for (const account of [borrower, variableBorrower]) {
+      const collateralWethAmount = await convertToCurrencyDecimals(wethContract, 10);
+      await wethContract.tx.mint(account.address, collateralWethAmount);
+      await wethContract.withSigner(account).tx.approve(lendingPool.address, collateralWethAmount);
+      await lendingPool.withSigner(account).tx.deposit(wethContract.address, account.address, collateralWethAmount, []);
+      await lendingPool.withSigner(account).tx.setAsCollateral(wethContract.address, true);
+    }
+
+    stableDebtDaiAmount = await convertToCurrencyDecimals(daiContract, 100);
+    await lendingPool.withSigner(borrower).tx.borrowStable(daiContract.address, borrower.address, stableDebtDaiAmount, []);
+  });
+
+  it('the stable debt is taken with the stable rate offered at the moment of borrowing', async () => {
+    const accountData = (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+    expect(accountData.stableDebt.toString()).to.equal(stableDebtDaiAmount.toString());
+    expect(accountData.debt.toString()).to.equal('0');
+    // there was no debt in the reserve before => the variable debt rate was 0
+    expect(accountData.stableRateE18.toString()).to.equal(STABLE_RATE_PREMIUM_E18.toString());
+
+    const reserveData = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+    expect(reserveData.totalStableDebt.toString()).to.equal(stableDebtDaiAmount.toString());
+    expect(reserveData.averageStableRateE18.toString()).to.equal(STABLE_RATE_PREMIUM_E18.toString());
+    // the stable debt counts into the utilization
+    expect(new BN(reserveData.currentDebtRateE18.toString()).gtn(0)).to.be.true;
+
+    const borrowerDaiBalance = (await daiContract.query.balanceOf(borrower.address)).value.ok!;
+    expect(borrowerDaiBalance.toString()).to.equal(stableDebtDaiAmount.toString());
+  });
+
+  it('the stable debt can not be taken once the stable rate model is removed', async () => {
+    await lendingPool.withSigner(owner).tx.setStableRateModel(daiContract.address, null);
+    const queryRes = (await lendingPool.withSigner(borrower).query.borrowStable(daiContract.address, borrower.address, stableDebtDaiAmount, []))
+      .value.ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.StableBorrowDisabled());
+  });
+
+  it('repaying more than the stable debt repays the whole stable debt only', async () => {
+    const repayAmount = stableDebtDaiAmount.muln(2);
+    await daiContract.tx.mint(borrower.address, stableDebtDaiAmount);
+    await daiContract.withSigner(borrower).tx.approve(lendingPool.address, repayAmount);
+
+    const tx = lendingPool.withSigner(borrower).tx.repayStable(daiContract.address, borrower.address, repayAmount, []);
+    await expect(tx).to.eventually.be.fulfilled;
+
+    const accountData = (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+    expect(accountData.stableDebt.toString()).to.equal('0');
+    expect(accountData.stableRateE18.toString()).to.equal('0');
+
+    const reserveData = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+    expect(reserveData.totalStableDebt.toString()).to.equal('0');
+    expect(reserveData.averageStableRateE18.toString()).to.equal('0');
+
+    // only the debt with the accumulated interest was taken
+    const borrowerDaiBalance = (await daiContract.query.balanceOf(borrower.address)).value.ok!;
+    expect(borrowerDaiBalance.gt(new BN(0))).to.be.true;
+    expect(borrowerDaiBalance.lt(stableDebtDaiAmount)).to.be.true;
+  });
+
+  it('the stable rate can not be rebalanced while it does not diverge from the offered rate by more than the threshold', async () => {
+    const queryRes = (await lendingPool.withSigner(rebalancer).query.rebalanceStableRate(daiContract.address, borrower.address)).value.ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.StableRateRebalanceNotAllowed());
+  });
+
+  it('the stable rate can not be rebalanced if the account has no stable debt', async () => {
+    await lendingPool.withSigner(owner).tx.setStableRateModel(daiContract.address, {
+      stableRatePremiumE18: STABLE_RATE_PREMIUM_E18,
+      rebalanceThresholdE18: 0,
+    });
+    const queryRes = (await lendingPool.withSigner(rebalancer).query.rebalanceStableRate(daiContract.address, variableBorrower.address)).value
+      .ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.InsufficientDebt());
+  });
+
+  describe('The rebalance threshold is set to 0 and another account borrows (variable) 6000 DAI. Then ...', () => {
+    let variableDebtDaiAmount: BN;
+
+    beforeEach('set the threshold and borrow', async () => {
+      await lendingPool.withSigner(owner).tx.setStableRateModel(daiContract.address, {
+        stableRatePremiumE18: STABLE_RATE_PREMIUM_E18,
+        rebalanceThresholdE18: 0,
+      });
+      variableDebtDaiAmount = await convertToCurrencyDecimals(daiContract, 6000);
+      await lendingPool.withSigner(variableBorrower).tx.borrow(daiContract.address, variableBorrower.address, variableDebtDaiAmount, []);
+    });
+
+    it('anyone can rebalance the stable rate up to the offered rate', async () => {
+      const reserveDataBefore = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+      const expectedRate = new BN(reserveDataBefore.currentDebtRateE18.toString()).addn(STABLE_RATE_PREMIUM_E18);
+      expect(expectedRate.gtn(STABLE_RATE_PREMIUM_E18)).to.be.true;
+
+      const tx = lendingPool.withSigner(rebalancer).tx.rebalanceStableRate(daiContract.address, borrower.address);
+      await expect(tx).to.eventually.be.fulfilled;
+      await expect(tx).to.emitEvent(lendingPool, 'StableRateRebalanced', {
+        asset: daiContract.address,
+        caller: rebalancer.address,
+        account: borrower.address,
+        newStableRateE18: expectedRate.toString(),
+      });
+
+      const accountData = (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+      expect(accountData.stableRateE18.toString()).to.equal(expectedRate.toString());
+      const reserveData = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+      expect(reserveData.averageStableRateE18.toString()).to.equal(expectedRate.toString());
+    });
+
+    it('after the variable debt is repaid anyone can rebalance the stable rate down to the offered rate', async () => {
+      await lendingPool.withSigner(rebalancer).tx.rebalanceStableRate(daiContract.address, borrower.address);
+      const rateAfterFirstRebalance = new BN(
+        (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!.stableRateE18.toString(),
+      );
+
+      await daiContract.tx.mint(variableBorrower.address, variableDebtDaiAmount);
+      await daiContract.withSigner(variableBorrower).tx.approve(lendingPool.address, MAX_U128);
+      await lendingPool.withSigner(variableBorrower).tx.repay(daiContract.address, variableBorrower.address, MAX_U128, []);
+
+      const reserveDataBefore = (await lendingPool.query.viewReserveData(daiContract.address)).value.ok!;
+      const expectedRate = new BN(reserveDataBefore.currentDebtRateE18.toString()).addn(STABLE_RATE_PREMIUM_E18);
+      expect(expectedRate.lt(rateAfterFirstRebalance)).to.be.true;
+
+      const tx = lendingPool.withSigner(rebalancer).tx.rebalanceStableRate(daiContract.address, borrower.address);
+      await expect(tx).to.eventually.be.fulfilled;
+
+      const accountData = (await lendingPool.query.viewUnupdatedAccountReserveData(daiContract.address, borrower.address)).value.ok!;
+      expect(accountData.stableRateE18.toString()).to.equal(expectedRate.toString());
+    });
+  });
+});
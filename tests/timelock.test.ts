@@ -0,0 +1,76 @@
+import { time } from '@c-forge/polkahat-network-helpers';
+import { KeyringPair } from '@polkadot/keyring/types';
+import { ParameterBoundsError } from 'typechain/types-arguments/lending_pool';
+import { LendingPoolErrorBuilder } from 'typechain/types-returns/lending_pool';
+import LendingPoolContract from '../typechain/contracts/lending_pool';
+import { ROLES } from './consts';
+import { makeSuite, TestEnv } from './scenarios/utils/make-suite';
+import { expect } from './setup/chai';
+import { ONE_DAY, ONE_SEC } from './setup/tokensToDeployForTesting';
+
+const MANAGE_ACTION_GRACE_PERIOD = ONE_DAY.muln(14);
+
+makeSuite('LendingPool timelock', (getTestEnv) => {
+  let testEnv: TestEnv;
+  let lendingPool: LendingPoolContract;
+  let owner: KeyringPair;
+  let proposer: KeyringPair;
+  let executor: KeyringPair;
+
+  beforeEach('grant PARAMETERS_ADMIN to the proposer and enable the timelock', async () => {
+    testEnv = getTestEnv();
+    lendingPool = testEnv.lendingPool;
+    owner = testEnv.owner;
+    [proposer, executor] = testEnv.accounts;
+
+    await lendingPool.withSigner(owner).tx.grantRole(ROLES['PARAMETERS_ADMIN'], proposer.address);
+    await lendingPool.withSigner(owner).tx.setTimelockDelay(ONE_DAY);
+  });
+
+  it('the action with parameters out of bounds can not be queued', async () => {
+    const queryRes = (await lendingPool.withSigner(proposer).query.queueManageAction({ setFlashLoanFeeE6: { flashLoanFeeE6: 1_000_001 } })).value.ok;
+    expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ParameterBoundsError(ParameterBoundsError.flashLoanFeeTooHigh));
+  });
+
+  describe('The proposer queues the change of the flash loan fee to 0.1%. Then ...', () => {
+    let actionId: number;
+    beforeEach('queue the action', async () => {
+      const action = { setFlashLoanFeeE6: { flashLoanFeeE6: 1_000 } };
+      actionId = (await lendingPool.withSigner(proposer).query.queueManageAction(action)).value.ok!.ok!;
+      await lendingPool.withSigner(proposer).tx.queueManageAction(action);
+    });
+
+    it('the action can not be executed before the eta', async () => {
+      const queryRes = (await lendingPool.withSigner(executor).query.executeManageAction(actionId)).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ManageActionNotReady());
+    });
+
+    it('anyone can execute the action after the eta', async () => {
+      await time.increase(ONE_DAY.toNumber());
+      await expect(lendingPool.withSigner(executor).tx.executeManageAction(actionId)).to.eventually.be.fulfilled;
+
+      const flashLoanFee = (await lendingPool.query.viewFlashLoanFeeE6()).value.ok!;
+      expect(flashLoanFee.toString()).to.equal('1000');
+      const queuedAction = (await lendingPool.query.viewQueuedManageAction(actionId)).value.ok!;
+      expect(queuedAction).to.be.null;
+    });
+
+    it('the action expires after the grace period and can be cancelled', async () => {
+      await time.increase(ONE_DAY.add(MANAGE_ACTION_GRACE_PERIOD).add(ONE_SEC).toNumber());
+      const queryRes = (await lendingPool.withSigner(executor).query.executeManageAction(actionId)).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ManageActionExpired());
+
+      await expect(lendingPool.withSigner(owner).tx.cancelManageAction(actionId)).to.eventually.be.fulfilled;
+      const queuedAction = (await lendingPool.query.viewQueuedManageAction(actionId)).value.ok!;
+      expect(queuedAction).to.be.null;
+    });
+
+    it('the action can not be executed once the proposer loses PARAMETERS_ADMIN', async () => {
+      await lendingPool.withSigner(owner).tx.revokeRole(ROLES['PARAMETERS_ADMIN'], proposer.address);
+      await time.increase(ONE_DAY.toNumber());
+
+      const queryRes = (await lendingPool.withSigner(executor).query.executeManageAction(actionId)).value.ok;
+      expect(queryRes).to.have.deep.property('err', LendingPoolErrorBuilder.ManageActionProposerLostRole());
+    });
+  });
+});